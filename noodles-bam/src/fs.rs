@@ -1,6 +1,6 @@
 //! BAM filesystem operations.
 
-mod index;
+pub(crate) mod index;
 
 use std::{fs::File, io, path::Path};
 
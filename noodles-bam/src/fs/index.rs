@@ -33,7 +33,7 @@ where
     index_inner(&mut reader)
 }
 
-fn index_inner<R>(reader: &mut Reader<R>) -> io::Result<bai::Index>
+pub(crate) fn index_inner<R>(reader: &mut Reader<R>) -> io::Result<bai::Index>
 where
     R: bgzf::io::Read,
 {
@@ -77,7 +77,7 @@ where
     Ok(builder.build(header.reference_sequences().len()))
 }
 
-fn is_coordinate_sorted(header: &sam::Header) -> bool {
+pub(crate) fn is_coordinate_sorted(header: &sam::Header) -> bool {
     header
         .header()
         .and_then(|hdr| hdr.other_fields().get(&SORT_ORDER))
@@ -1,9 +1,14 @@
 //! BAM I/O.
 
 pub mod indexed_reader;
+mod indexing_writer;
+pub mod multithreaded_reader;
 pub mod reader;
 pub mod writer;
 
-pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
+pub use self::{
+    indexed_reader::IndexedReader, indexing_writer::IndexingWriter,
+    multithreaded_reader::MultithreadedReader, reader::Reader, writer::Writer,
+};
 
 pub(crate) const MAGIC_NUMBER: [u8; 4] = *b"BAM\x01";
@@ -0,0 +1,312 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::binning_index::{
+    Indexer,
+    index::reference_sequence::{bin::Chunk, index::LinearIndex},
+};
+use noodles_sam::{self as sam, alignment::io::Write as _};
+
+use super::Writer;
+use crate::{Record, bai, fs::index::is_coordinate_sorted};
+
+/// A BAM writer that builds a BAM index (BAI) as records are written.
+///
+/// The output must be coordinate-sorted and marked as such in the SAM header, i.e.,
+/// `SO:coordinate`. The index is equivalent to the one built by [`crate::fs::index`] but does not
+/// require a second pass over the written file.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_bam as bam;
+/// use noodles_csi::BinningIndex;
+/// use noodles_sam::{
+///     self as sam,
+///     header::record::value::{
+///         map::{self, header::{sort_order::COORDINATE, tag::SORT_ORDER}},
+///         Map,
+///     },
+/// };
+///
+/// let header = sam::Header::builder()
+///     .set_header(Map::<map::Header>::builder().insert(SORT_ORDER, COORDINATE).build()?)
+///     .build();
+///
+/// let mut writer = bam::io::IndexingWriter::new(Vec::new());
+/// writer.write_header(&header)?;
+///
+/// let record = bam::Record::default();
+/// writer.write_record(&header, &record)?;
+///
+/// let index = writer.finish(&header)?;
+/// assert_eq!(index.unplaced_unmapped_record_count(), Some(1));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct IndexingWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: Indexer<LinearIndex>,
+    last_key: Option<SortKey>,
+}
+
+impl<W> IndexingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexing BAM writer with a default compression level.
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a SAM header.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the header is not marked as coordinate-sorted.
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        if !is_coordinate_sorted(header) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid sort order: expected coordinate",
+            ));
+        }
+
+        self.inner.write_header(header)
+    }
+
+    /// Writes a BAM record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the record is out of coordinate order.
+    pub fn write_record(&mut self, header: &sam::Header, record: &Record) -> io::Result<()> {
+        self.write_alignment_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the BAM index.
+    pub fn finish(&mut self, header: &sam::Header) -> io::Result<bai::Index> {
+        self.inner.try_finish()?;
+
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build(header.reference_sequences().len()))
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::default(),
+            last_key: None,
+        }
+    }
+}
+
+impl<W> sam::alignment::io::Write for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_id = record.reference_sequence_id(header).transpose()?;
+        let alignment_start = record.alignment_start().transpose()?;
+
+        let key = SortKey::new(reference_sequence_id, alignment_start);
+
+        if self.last_key.is_some_and(|last_key| key < last_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        let alignment_context = match (
+            reference_sequence_id,
+            alignment_start,
+            record.alignment_end().transpose()?,
+        ) {
+            (Some(id), Some(start), Some(end)) => {
+                let is_mapped = !record.flags()?.is_unmapped();
+                Some((id, start, end, is_mapped))
+            }
+            _ => None,
+        };
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_alignment_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+        self.indexer.add_record(alignment_context, chunk)?;
+
+        self.last_key = Some(key);
+
+        Ok(())
+    }
+
+    fn finish(&mut self, _: &sam::Header) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Coordinate-sorted records are ordered by reference sequence ID and then alignment start.
+// Records without a reference sequence ID (unplaced unmapped records) are placed last.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct SortKey(bool, Option<usize>, Option<Position>);
+
+impl SortKey {
+    fn new(reference_sequence_id: Option<usize>, alignment_start: Option<Position>) -> Self {
+        Self(
+            reference_sequence_id.is_none(),
+            reference_sequence_id,
+            alignment_start,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_sam::{
+        alignment::{RecordBuf, record::Flags},
+        header::record::value::{
+            Map,
+            map::{
+                self, ReferenceSequence,
+                header::{sort_order::COORDINATE, tag::SORT_ORDER},
+            },
+        },
+    };
+
+    use super::*;
+    use crate::io::Reader;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .set_header(
+                Map::<map::Header>::builder()
+                    .insert(SORT_ORDER, COORDINATE)
+                    .build()?,
+            )
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(1 << 20)?),
+            )
+            .add_reference_sequence(
+                "sq1",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(1 << 20)?),
+            )
+            .build())
+    }
+
+    fn build_record(
+        reference_sequence_id: Option<usize>,
+        alignment_start: Option<usize>,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let mut builder = RecordBuf::builder().set_flags(Flags::UNMAPPED);
+
+        if let Some(id) = reference_sequence_id {
+            builder = builder.set_reference_sequence_id(id);
+        }
+
+        if let Some(start) = alignment_start {
+            builder = builder
+                .set_flags(Flags::empty())
+                .set_alignment_start(Position::try_from(start)?);
+        }
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::fs::index::index_inner;
+
+        let header = build_header()?;
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for i in 0..4096 {
+            let record = build_record(Some(0), Some(i * 64 + 1))?;
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        writer.write_alignment_record(&header, &build_record(Some(1), Some(8))?)?;
+        writer.write_alignment_record(&header, &build_record(None, None)?)?;
+
+        let actual = writer.finish(&header)?;
+
+        let data = writer.into_inner().into_inner();
+        let mut reader = Reader::new(&data[..]);
+        let expected = index_inner(&mut reader)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_header_with_unsorted_header() {
+        let mut writer = IndexingWriter::new(Vec::new());
+
+        assert!(matches!(
+            writer.write_header(&sam::Header::default()),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let header = build_header()?;
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_alignment_record(&header, &build_record(Some(0), Some(13))?)?;
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &build_record(Some(0), Some(8))?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        writer.write_alignment_record(&header, &build_record(None, None)?)?;
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &build_record(Some(1), Some(8))?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,514 @@
+//! Multithreaded BAM reader.
+
+use std::{
+    io::{self, Read},
+    mem,
+    num::NonZeroUsize,
+    thread::{self, JoinHandle},
+    vec,
+};
+
+use crossbeam_channel::{Receiver, Sender};
+use noodles_sam::{self as sam, alignment::RecordBuf};
+
+use super::reader::{header::read_header, read_record};
+
+// The number of records per batch.
+const BATCH_SIZE: usize = 1024;
+
+type Batch = Vec<io::Result<(usize, RecordBuf)>>;
+type DecodedTx = Sender<Batch>;
+type DecodedRx = Receiver<Batch>;
+type DecodeTx = Sender<(io::Result<Buffer>, DecodedTx)>;
+type DecodeRx = Receiver<(io::Result<Buffer>, DecodedTx)>;
+type ReadTx = Sender<DecodedRx>;
+type ReadRx = Receiver<DecodedRx>;
+
+enum State<R> {
+    Paused(R),
+    Running {
+        reader_handle: JoinHandle<R>,
+        decoder_handles: Vec<JoinHandle<()>>,
+        read_rx: ReadRx,
+    },
+    Done,
+}
+
+// A batch of raw records.
+#[derive(Debug, Default)]
+struct Buffer {
+    buf: Vec<u8>,
+    ends: Vec<usize>,
+}
+
+/// A multithreaded BAM reader.
+///
+/// This is a BAM reader that uses a thread pool to decode alignment record buffers. The inner
+/// reader is placed on its own thread, which splits the decompressed stream into batches of raw
+/// records. Each batch is decoded by a worker, and records are returned in the order they appear
+/// in the stream.
+///
+/// The inner reader is expected to be a BGZF decoder, e.g., [`noodles_bgzf::io::Reader`] or
+/// [`noodles_bgzf::io::MultithreadedReader`]. Decompressing and decoding then happen in separate
+/// pipeline stages.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::{fs::File, io, num::NonZeroUsize, thread};
+/// use noodles_bam as bam;
+/// use noodles_bgzf as bgzf;
+///
+/// let worker_count = thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+///
+/// let decoder = File::open("sample.bam")
+///     .map(|f| bgzf::io::MultithreadedReader::with_worker_count(worker_count, f))?;
+/// let mut reader = bam::io::MultithreadedReader::with_worker_count(worker_count, decoder);
+///
+/// let header = reader.read_header()?;
+///
+/// for result in reader.record_bufs(&header) {
+///     let record = result?;
+///     // ...
+/// }
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct MultithreadedReader<R> {
+    state: State<R>,
+    worker_count: NonZeroUsize,
+    batch: vec::IntoIter<io::Result<(usize, RecordBuf)>>,
+}
+
+impl<R> MultithreadedReader<R> {
+    /// Shuts down the reader.
+    ///
+    /// Any records read from the inner reader but not yet returned are discarded.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the reader was already shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let mut reader = bam::io::MultithreadedReader::new(io::empty());
+    /// reader.finish()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn finish(&mut self) -> io::Result<R> {
+        let state = mem::replace(&mut self.state, State::Done);
+        self.batch = Vec::new().into_iter();
+
+        match state {
+            State::Paused(inner) => Ok(inner),
+            State::Running {
+                reader_handle,
+                mut decoder_handles,
+                read_rx,
+            } => {
+                drop(read_rx);
+
+                let inner = reader_handle.join().unwrap();
+
+                for handle in decoder_handles.drain(..) {
+                    handle.join().unwrap();
+                }
+
+                Ok(inner)
+            }
+            State::Done => Err(finished_error()),
+        }
+    }
+}
+
+impl<R> MultithreadedReader<R>
+where
+    R: Read + Send + 'static,
+{
+    /// Creates a multithreaded BAM reader with a worker count of 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_bam as bam;
+    /// let reader = bam::io::MultithreadedReader::new(io::empty());
+    /// ```
+    pub fn new(inner: R) -> Self {
+        Self::with_worker_count(NonZeroUsize::MIN, inner)
+    }
+
+    /// Creates a multithreaded BAM reader with a worker count.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use std::num::NonZeroUsize;
+    /// use noodles_bam as bam;
+    /// let reader = bam::io::MultithreadedReader::with_worker_count(NonZeroUsize::MIN, io::empty());
+    /// ```
+    pub fn with_worker_count(worker_count: NonZeroUsize, inner: R) -> Self {
+        Self {
+            state: State::Paused(inner),
+            worker_count,
+            batch: Vec::new().into_iter(),
+        }
+    }
+
+    /// Reads the SAM header.
+    ///
+    /// This must be called before any records are read, as the inner reader is then moved to its
+    /// own thread.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the header is invalid or records were already read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let decoder = File::open("sample.bam").map(bgzf::io::Reader::new)?;
+    /// let mut reader = bam::io::MultithreadedReader::new(decoder);
+    /// let header = reader.read_header()?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_header(&mut self) -> io::Result<sam::Header> {
+        match &mut self.state {
+            State::Paused(inner) => read_header(inner),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "records were already read",
+            )),
+        }
+    }
+
+    /// Reads a record into an alignment record buffer.
+    ///
+    /// This is the multithreaded equivalent of [`super::Reader::read_record_buf`]. Records are
+    /// decoded ahead of time by the workers, and the next decoded record replaces the given
+    /// record.
+    ///
+    /// If successful, the record block size is returned. If a block size of 0 is returned, the
+    /// stream reached EOF.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if a record is invalid or the reader was shut down.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    /// use noodles_sam::alignment::RecordBuf;
+    ///
+    /// let decoder = File::open("sample.bam").map(bgzf::io::Reader::new)?;
+    /// let mut reader = bam::io::MultithreadedReader::new(decoder);
+    /// let header = reader.read_header()?;
+    ///
+    /// let mut record = RecordBuf::default();
+    /// reader.read_record_buf(&header, &mut record)?;
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn read_record_buf(
+        &mut self,
+        _header: &sam::Header,
+        record: &mut RecordBuf,
+    ) -> io::Result<usize> {
+        match self.next_record_buf() {
+            Some(result) => {
+                let (block_size, next_record) = result?;
+                *record = next_record;
+                Ok(block_size)
+            }
+            None => Ok(0),
+        }
+    }
+
+    /// Returns an iterator over alignment record buffers starting from the current stream
+    /// position.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::{fs::File, io};
+    /// use noodles_bam as bam;
+    /// use noodles_bgzf as bgzf;
+    ///
+    /// let decoder = File::open("sample.bam").map(bgzf::io::Reader::new)?;
+    /// let mut reader = bam::io::MultithreadedReader::new(decoder);
+    /// let header = reader.read_header()?;
+    ///
+    /// for result in reader.record_bufs(&header) {
+    ///     let record = result?;
+    ///     // ...
+    /// }
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn record_bufs<'r>(
+        &'r mut self,
+        _header: &'r sam::Header,
+    ) -> impl Iterator<Item = io::Result<RecordBuf>> + 'r {
+        std::iter::from_fn(move || {
+            self.next_record_buf()
+                .map(|result| result.map(|(_, record)| record))
+        })
+    }
+
+    fn next_record_buf(&mut self) -> Option<io::Result<(usize, RecordBuf)>> {
+        loop {
+            if let Some(result) = self.batch.next() {
+                return Some(result);
+            }
+
+            if let Err(e) = self.resume() {
+                return Some(Err(e));
+            }
+
+            let State::Running { read_rx, .. } = &self.state else {
+                panic!("invalid state");
+            };
+
+            let batch = read_rx.recv().ok()?.recv().ok()?;
+            self.batch = batch.into_iter();
+        }
+    }
+
+    fn resume(&mut self) -> io::Result<()> {
+        match self.state {
+            State::Paused(_) => {}
+            State::Running { .. } => return Ok(()),
+            State::Done => return Err(finished_error()),
+        }
+
+        let state = mem::replace(&mut self.state, State::Done);
+
+        let State::Paused(inner) = state else {
+            panic!("invalid state");
+        };
+
+        let worker_count = self.worker_count.get();
+
+        let (decode_tx, decode_rx) = crossbeam_channel::bounded(worker_count);
+        let (read_tx, read_rx) = crossbeam_channel::bounded(worker_count);
+
+        let reader_handle = spawn_reader(inner, decode_tx, read_tx);
+        let decoder_handles = spawn_decoders(self.worker_count, decode_rx);
+
+        self.state = State::Running {
+            reader_handle,
+            decoder_handles,
+            read_rx,
+        };
+
+        Ok(())
+    }
+}
+
+impl<R> Drop for MultithreadedReader<R> {
+    fn drop(&mut self) {
+        if !matches!(self.state, State::Done) {
+            let _ = self.finish();
+        }
+    }
+}
+
+fn finished_error() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, "reader was shut down")
+}
+
+fn spawn_reader<R>(mut reader: R, decode_tx: DecodeTx, read_tx: ReadTx) -> JoinHandle<R>
+where
+    R: Read + Send + 'static,
+{
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+
+        loop {
+            let mut buffer = Buffer::default();
+            let mut error = None;
+
+            while buffer.ends.len() < BATCH_SIZE {
+                match read_record(&mut reader, &mut buf) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        buffer.buf.extend_from_slice(&buf);
+                        buffer.ends.push(buffer.buf.len());
+                    }
+                    Err(e) => {
+                        error = Some(e);
+                        break;
+                    }
+                }
+            }
+
+            // A partial batch means the stream reached EOF or failed.
+            let is_done = buffer.ends.len() < BATCH_SIZE;
+
+            if !buffer.ends.is_empty() && !send(&decode_tx, &read_tx, Ok(buffer)) {
+                break;
+            }
+
+            if let Some(e) = error {
+                send(&decode_tx, &read_tx, Err(e));
+                break;
+            }
+
+            if is_done {
+                break;
+            }
+        }
+
+        reader
+    })
+}
+
+fn send(decode_tx: &DecodeTx, read_tx: &ReadTx, buffer: io::Result<Buffer>) -> bool {
+    let (decoded_tx, decoded_rx) = crossbeam_channel::bounded(1);
+    decode_tx.send((buffer, decoded_tx)).is_ok() && read_tx.send(decoded_rx).is_ok()
+}
+
+fn spawn_decoders(worker_count: NonZeroUsize, decode_rx: DecodeRx) -> Vec<JoinHandle<()>> {
+    (0..worker_count.get())
+        .map(|_| {
+            let decode_rx = decode_rx.clone();
+
+            thread::spawn(move || {
+                while let Ok((result, decoded_tx)) = decode_rx.recv() {
+                    let batch = match result {
+                        Ok(buffer) => decode_batch(&buffer),
+                        Err(e) => vec![Err(e)],
+                    };
+
+                    // The receiver is dropped when the reader is shut down.
+                    decoded_tx.send(batch).ok();
+                }
+            })
+        })
+        .collect()
+}
+
+fn decode_batch(buffer: &Buffer) -> Batch {
+    use crate::record::codec::decode;
+
+    let mut start = 0;
+
+    buffer
+        .ends
+        .iter()
+        .map(|&end| {
+            let mut src = &buffer.buf[start..end];
+            let block_size = end - start;
+            start = end;
+
+            let mut record = RecordBuf::default();
+
+            decode(&mut src, &mut record)
+                .map(|_| (block_size, record))
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use noodles_bgzf as bgzf;
+    use noodles_core::Position;
+    use noodles_sam::{
+        alignment::io::Write,
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    fn build_data(record_count: usize) -> io::Result<(sam::Header, Vec<u8>)> {
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::new(NonZeroUsize::try_from(1 << 20).unwrap()),
+            )
+            .build();
+
+        let mut writer = crate::io::Writer::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for i in 0..record_count {
+            let record = RecordBuf::builder()
+                .set_name(format!("r{i}"))
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(i + 1).unwrap())
+                .build();
+
+            writer.write_alignment_record(&header, &record)?;
+        }
+
+        let data = writer.into_inner().finish()?;
+
+        Ok((header, data))
+    }
+
+    #[test]
+    fn test_record_bufs() -> io::Result<()> {
+        let (header, data) = build_data(BATCH_SIZE * 2 + 8)?;
+
+        let mut reader = crate::io::Reader::new(&data[..]);
+        reader.read_header()?;
+        let expected: Vec<_> = reader.record_bufs(&header).collect::<io::Result<_>>()?;
+
+        let worker_count = NonZeroUsize::try_from(4).unwrap();
+        let decoder = bgzf::io::Reader::new(Cursor::new(data));
+        let mut reader = MultithreadedReader::with_worker_count(worker_count, decoder);
+        let actual_header = reader.read_header()?;
+        let actual: Vec<_> = reader.record_bufs(&header).collect::<io::Result<_>>()?;
+
+        assert_eq!(actual_header, header);
+        assert_eq!(actual, expected);
+
+        let mut record = RecordBuf::default();
+        assert_eq!(reader.read_record_buf(&header, &mut record)?, 0);
+
+        assert!(matches!(
+            reader.read_header(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_before_eof() -> io::Result<()> {
+        let (header, data) = build_data(BATCH_SIZE * 8)?;
+
+        let decoder = bgzf::io::Reader::new(Cursor::new(data));
+        let mut reader = MultithreadedReader::new(decoder);
+        reader.read_header()?;
+
+        let mut record = RecordBuf::default();
+        assert!(reader.read_record_buf(&header, &mut record)? > 0);
+        assert_eq!(record.name(), Some(b"r0".into()));
+
+        reader.finish()?;
+
+        assert!(matches!(
+            reader.read_record_buf(&header, &mut record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            reader.finish(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
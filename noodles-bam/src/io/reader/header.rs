@@ -104,7 +104,7 @@ where
     }
 }
 
-pub(super) fn read_header<R>(reader: &mut R) -> io::Result<sam::Header>
+pub(crate) fn read_header<R>(reader: &mut R) -> io::Result<sam::Header>
 where
     R: Read,
 {
@@ -3,7 +3,7 @@ use std::{
     mem,
 };
 
-pub(super) fn read_record<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<usize>
+pub(crate) fn read_record<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<usize>
 where
     R: Read,
 {
@@ -0,0 +1,255 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::{
+    self as csi,
+    binning_index::{
+        Indexer,
+        index::reference_sequence::{bin::Chunk, index::BinnedIndex},
+    },
+};
+use noodles_vcf::{self as vcf, variant::io::Write as _};
+
+use super::Writer;
+use crate::Record;
+
+/// A BCF writer that builds a coordinate-sorted index (CSI) as records are written.
+///
+/// The index is equivalent to the one built by [`crate::fs::index`] but does not require a second
+/// pass over the written file.
+///
+/// Records must be coordinate-sorted, i.e., ordered by contig (as listed in the header) and then
+/// by position.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_bcf as bcf;
+/// use noodles_core::Position;
+/// use noodles_vcf::{self as vcf, variant::io::Write};
+///
+/// let header = vcf::Header::builder()
+///     .add_contig("sq0", Default::default())
+///     .build();
+///
+/// let mut writer = bcf::io::IndexingWriter::new(Vec::new());
+/// writer.write_header(&header)?;
+///
+/// let record = vcf::variant::RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.finish(&header)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexingWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: Indexer<BinnedIndex>,
+    last_key: Option<(usize, Position)>,
+}
+
+impl<W> IndexingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexing BCF writer with a default compression level.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a VCF header.
+    pub fn write_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a BCF record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the record is out of coordinate order.
+    pub fn write_record(&mut self, header: &vcf::Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the coordinate-sorted index.
+    pub fn finish(&mut self, header: &vcf::Header) -> io::Result<csi::Index> {
+        self.inner.try_finish()?;
+
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build(header.contigs().len()))
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        Self {
+            inner,
+            indexer: Indexer::default(),
+            last_key: None,
+        }
+    }
+}
+
+impl<W> vcf::variant::io::Write for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &vcf::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &vcf::Header,
+        record: &dyn vcf::variant::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let reference_sequence_id = self
+            .inner
+            .string_maps()
+            .contigs()
+            .get_index_of(reference_sequence_name)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("missing contig: {reference_sequence_name}"),
+                )
+            })?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        let key = (reference_sequence_id, start);
+
+        if self.last_key.is_some_and(|last_key| key < last_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+        self.indexer
+            .add_record(Some((reference_sequence_id, start, end, true)), chunk)?;
+
+        self.last_key = Some(key);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_vcf::variant::RecordBuf;
+
+    use super::*;
+    use crate::{fs::index::index_inner, io::Reader};
+
+    fn build_header() -> vcf::Header {
+        vcf::Header::builder()
+            .add_contig("sq0", Default::default())
+            .add_contig("sq1", Default::default())
+            .build()
+    }
+
+    fn build_record(reference_sequence_name: &str, position: usize) -> io::Result<RecordBuf> {
+        let position = Position::try_from(position)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_variant_start(position)
+            .set_reference_bases("A")
+            .build())
+    }
+
+    #[test]
+    fn test_finish() -> io::Result<()> {
+        let header = build_header();
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for i in 0..4096 {
+            writer.write_variant_record(&header, &build_record("sq0", i * 64 + 1)?)?;
+        }
+
+        writer.write_variant_record(&header, &build_record("sq1", 8)?)?;
+
+        let actual = writer.finish(&header)?;
+
+        let data = writer.into_inner().into_inner();
+        let mut reader = Reader::new(&data[..]);
+        let expected = index_inner(&mut reader)?;
+
+        // The reader ends the last chunk at the start of the next (EOF) block rather than at the
+        // end of the final data block, so only the complete reference sequences are compared.
+        assert_eq!(
+            actual.reference_sequences().len(),
+            expected.reference_sequences().len()
+        );
+        assert_eq!(
+            actual.reference_sequences()[0],
+            expected.reference_sequences()[0]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_variant_record_with_unsorted_records() -> io::Result<()> {
+        let header = build_header();
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_variant_record(&header, &build_record("sq1", 13)?)?;
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq1", 8)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
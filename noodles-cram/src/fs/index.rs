@@ -1,4 +1,10 @@
-use std::{cmp, collections::HashMap, fs::File, io, path::Path};
+use std::{
+    cmp,
+    collections::HashMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
 
 use noodles_core::Position;
 use noodles_fasta as fasta;
@@ -27,6 +33,13 @@ where
     P: AsRef<Path>,
 {
     let mut reader = File::open(src).map(Reader::new)?;
+    index_inner(&mut reader)
+}
+
+pub(crate) fn index_inner<R>(reader: &mut Reader<R>) -> io::Result<crai::Index>
+where
+    R: Read + Seek,
+{
     let header = reader.read_header()?;
 
     let mut index = Vec::new();
@@ -0,0 +1,326 @@
+use std::io::{self, Write};
+
+use noodles_core::Position;
+use noodles_sam::{self as sam, alignment::io::Write as _};
+
+use super::Writer;
+use crate::crai;
+
+/// A CRAM writer that builds a CRAM index (CRAI) as records are written.
+///
+/// Index records are added as each container is written. The index is equivalent to the one
+/// built by [`crate::fs::index`] but does not require a second pass over the written file.
+///
+/// Records must be coordinate-sorted.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram as cram;
+/// use noodles_sam as sam;
+///
+/// let header = sam::Header::default();
+///
+/// let mut writer = cram::io::IndexingWriter::new(Vec::new());
+/// writer.write_header(&header)?;
+///
+/// let record = cram::Record::default();
+/// writer.write_record(&header, &record)?;
+///
+/// let index = writer.finish(&header)?;
+/// assert_eq!(index.len(), 1);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexingWriter<W> {
+    inner: Writer<W>,
+    last_key: Option<SortKey>,
+}
+
+impl<W> IndexingWriter<W> {
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner.into_inner()
+    }
+}
+
+impl<W> IndexingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexing CRAM writer with default options.
+    ///
+    /// To set options, e.g., a reference sequence repository, create a writer using
+    /// [`super::writer::Builder`] and convert it using [`IndexingWriter::from`].
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(inner))
+    }
+
+    /// Writes a SAM header.
+    pub fn write_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a CRAM record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the record is out of coordinate order.
+    pub fn write_record(
+        &mut self,
+        header: &sam::Header,
+        record: &crate::Record<'_>,
+    ) -> io::Result<()> {
+        self.write_alignment_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the CRAM index.
+    ///
+    /// This flushes any buffered records as a final container before writing the EOF container.
+    pub fn finish(&mut self, header: &sam::Header) -> io::Result<crai::Index> {
+        self.inner.try_finish(header)?;
+        Ok(self.inner.index.take().unwrap_or_default())
+    }
+}
+
+impl<W> From<Writer<W>> for IndexingWriter<W> {
+    fn from(mut inner: Writer<W>) -> Self {
+        inner.index = Some(crai::Index::new());
+
+        Self {
+            inner,
+            last_key: None,
+        }
+    }
+}
+
+impl<W> sam::alignment::io::Write for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn write_alignment_header(&mut self, header: &sam::Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_alignment_record(
+        &mut self,
+        header: &sam::Header,
+        record: &dyn sam::alignment::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_id = record.reference_sequence_id(header).transpose()?;
+        let alignment_start = record.alignment_start().transpose()?;
+
+        let key = SortKey::new(reference_sequence_id, alignment_start);
+
+        if self.last_key.is_some_and(|last_key| key < last_key) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        self.inner.write_alignment_record(header, record)?;
+        self.last_key = Some(key);
+
+        Ok(())
+    }
+
+    fn finish(&mut self, header: &sam::Header) -> io::Result<()> {
+        IndexingWriter::finish(self, header).map(|_| ())
+    }
+}
+
+// Coordinate-sorted records are ordered by reference sequence ID and then alignment start.
+// Records without a reference sequence ID (unplaced unmapped records) are placed last.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct SortKey(bool, Option<usize>, Option<Position>);
+
+impl SortKey {
+    fn new(reference_sequence_id: Option<usize>, alignment_start: Option<Position>) -> Self {
+        Self(
+            reference_sequence_id.is_none(),
+            reference_sequence_id,
+            alignment_start,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, num::NonZeroUsize};
+
+    use noodles_sam::{
+        alignment::{
+            RecordBuf,
+            record::{
+                Flags,
+                cigar::{Op, op::Kind},
+            },
+            record_buf::{QualityScores, Sequence},
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+    use crate::{fs::index::index_inner, io::Reader};
+
+    const REFERENCE_SEQUENCE_LENGTH: usize = 256;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        let length = NonZeroUsize::try_from(REFERENCE_SEQUENCE_LENGTH)?;
+
+        Ok(sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(length))
+            .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(length))
+            .build())
+    }
+
+    fn build_writer() -> IndexingWriter<Vec<u8>> {
+        use noodles_fasta::{
+            self as fasta,
+            record::{Definition, Sequence},
+        };
+
+        use crate::io::writer::Builder;
+
+        let reference_sequences: Vec<_> = ["sq0", "sq1"]
+            .into_iter()
+            .map(|name| {
+                fasta::Record::new(
+                    Definition::new(name, None),
+                    Sequence::from(b"ACGT".repeat(REFERENCE_SEQUENCE_LENGTH / 4)),
+                )
+            })
+            .collect();
+
+        let writer = Builder::default()
+            .set_reference_sequence_repository(fasta::Repository::new(reference_sequences))
+            .build_from_writer(Vec::new());
+
+        IndexingWriter::from(writer)
+    }
+
+    fn build_record(
+        reference_sequence_id: Option<usize>,
+        alignment_start: Option<usize>,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let mut builder = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED)
+            .set_sequence(Sequence::from(b"ACGT"))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]));
+
+        if let Some(id) = reference_sequence_id {
+            builder = builder.set_reference_sequence_id(id);
+        }
+
+        if let Some(start) = alignment_start {
+            builder = builder
+                .set_flags(Flags::empty())
+                .set_alignment_start(Position::try_from(start)?)
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect());
+        }
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_finish() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let mut writer = build_writer();
+        writer.write_header(&header)?;
+
+        for i in 0..16 {
+            writer.write_alignment_record(&header, &build_record(Some(0), Some(i * 8 + 1))?)?;
+        }
+
+        let actual = writer.finish(&header)?;
+
+        let data = writer.into_inner();
+        let mut reader = Reader::new(Cursor::new(data));
+        let expected = index_inner(&mut reader)?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_finish_with_multi_reference_slice() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::io::reader::Container;
+
+        let header = build_header()?;
+
+        let mut writer = build_writer();
+        writer.write_header(&header)?;
+
+        writer.write_alignment_record(&header, &build_record(Some(0), Some(1))?)?;
+        writer.write_alignment_record(&header, &build_record(Some(0), Some(13))?)?;
+        writer.write_alignment_record(&header, &build_record(Some(1), Some(8))?)?;
+        writer.write_alignment_record(&header, &build_record(None, None)?)?;
+
+        let index = writer.finish(&header)?;
+
+        let actual: Vec<_> = index
+            .iter()
+            .map(|record| {
+                (
+                    record.reference_sequence_id(),
+                    record.alignment_start(),
+                    record.alignment_span(),
+                )
+            })
+            .collect();
+
+        let expected = [
+            (None, None, 0),
+            (Some(0), Some(Position::try_from(1)?), 16),
+            (Some(1), Some(Position::try_from(8)?), 4),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let data = writer.into_inner();
+        let mut reader = Reader::new(Cursor::new(data));
+        reader.read_header()?;
+
+        let container_position = reader.position()?;
+        let mut container = Container::default();
+        let container_len = reader.read_container(&mut container)?;
+
+        for record in &index {
+            assert_eq!(record.offset(), container_position);
+            assert_eq!(record.landmark(), container.header().landmarks()[0] as u64);
+            assert_eq!(
+                record.landmark() + record.slice_length(),
+                container_len as u64
+            );
+        }
+
+        assert!(container.slices().all(|result| result.is_ok()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_alignment_record_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let header = build_header()?;
+
+        let mut writer = build_writer();
+        writer.write_header(&header)?;
+
+        writer.write_alignment_record(&header, &build_record(Some(0), Some(13))?)?;
+
+        assert!(matches!(
+            writer.write_alignment_record(&header, &build_record(Some(0), Some(8))?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ pub(crate) mod builder;
 mod collections;
 pub(crate) mod container;
 pub(crate) mod header;
+mod index;
 pub(crate) mod num;
 mod options;
 pub(crate) mod record;
@@ -19,7 +20,7 @@ use self::{
     header::{write_file_definition, write_file_header, write_header},
 };
 pub(crate) use self::{options::Options, record::Record};
-use crate::FileDefinition;
+use crate::{FileDefinition, crai};
 
 const DEFAULT_SLICES_PER_CONTAINER: usize = 1;
 const DEFAULT_RECORDS_PER_SLICE: usize = 10240;
@@ -55,6 +56,8 @@ pub struct Writer<W> {
     options: Options,
     records: Vec<Record>,
     record_counter: u64,
+    position: u64,
+    pub(crate) index: Option<crai::Index>,
 }
 
 impl<W> Writer<W> {
@@ -139,7 +142,10 @@ where
     pub fn try_finish(&mut self, header: &sam::Header) -> io::Result<()> {
         use self::container::write_eof_container;
         self.flush(header)?;
-        write_eof_container(&mut self.inner)
+        write_eof_container(&mut CountingWriter::new(
+            &mut self.inner,
+            &mut self.position,
+        ))
     }
 
     /// Writes a CRAM file definition.
@@ -157,7 +163,10 @@ where
     /// ```
     pub fn write_file_definition(&mut self) -> io::Result<()> {
         let file_definition = FileDefinition::new(self.options.version, Default::default());
-        write_file_definition(&mut self.inner, &file_definition)
+        write_file_definition(
+            &mut CountingWriter::new(&mut self.inner, &mut self.position),
+            &file_definition,
+        )
     }
 
     /// Writes a CRAM file header container.
@@ -184,7 +193,11 @@ where
     /// # Ok::<(), io::Error>(())
     /// ```
     pub fn write_file_header(&mut self, header: &sam::Header) -> io::Result<()> {
-        write_file_header(&mut self.inner, &self.reference_sequence_repository, header)
+        write_file_header(
+            &mut CountingWriter::new(&mut self.inner, &mut self.position),
+            &self.reference_sequence_repository,
+            header,
+        )
     }
 
     /// Writes a SAM header.
@@ -207,7 +220,7 @@ where
         let file_definition = FileDefinition::new(self.options.version, Default::default());
 
         write_header(
-            &mut self.inner,
+            &mut CountingWriter::new(&mut self.inner, &mut self.position),
             &self.reference_sequence_repository,
             &file_definition,
             header,
@@ -253,8 +266,12 @@ where
     }
 
     fn flush(&mut self, header: &sam::Header) -> io::Result<()> {
-        write_container(
-            &mut self.inner,
+        use self::index::push_index_records;
+
+        let container_position = self.position;
+
+        let container = write_container(
+            &mut CountingWriter::new(&mut self.inner, &mut self.position),
             &self.reference_sequence_repository,
             &self.options,
             header,
@@ -262,6 +279,17 @@ where
             &mut self.records,
         )?;
 
+        if let (Some(index), Some((container_header, container_len))) = (&mut self.index, container)
+        {
+            push_index_records(
+                index,
+                &self.records,
+                container_header.landmarks(),
+                container_len,
+                container_position,
+            )?;
+        }
+
         let record_count = u64::try_from(self.records.len())
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
         self.record_counter += record_count;
@@ -293,3 +321,30 @@ where
         self.try_finish(header)
     }
 }
+
+// A writer adapter that tracks the position of the stream.
+struct CountingWriter<'a, W> {
+    inner: &'a mut W,
+    position: &'a mut u64,
+}
+
+impl<'a, W> CountingWriter<'a, W> {
+    fn new(inner: &'a mut W, position: &'a mut u64) -> Self {
+        Self { inner, position }
+    }
+}
+
+impl<W> Write for CountingWriter<'_, W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        *self.position += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
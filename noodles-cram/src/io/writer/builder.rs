@@ -126,6 +126,8 @@ impl Builder {
             options: self.options,
             records: Vec::with_capacity(RECORDS_PER_CONTAINER),
             record_counter: 0,
+            position: 0,
+            index: None,
         }
     }
 }
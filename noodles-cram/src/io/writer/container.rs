@@ -29,12 +29,12 @@ pub fn write_container<W>(
     header: &sam::Header,
     record_counter: u64,
     records: &mut [Record],
-) -> io::Result<()>
+) -> io::Result<Option<(Header, usize)>>
 where
     W: Write,
 {
     if records.is_empty() {
-        return Ok(());
+        return Ok(None);
     }
 
     let (header, container_size, blocks) = build_container(
@@ -51,7 +51,7 @@ where
         write_block(writer, &block)?;
     }
 
-    Ok(())
+    Ok(Some((header, container_size)))
 }
 
 fn build_container(
@@ -114,10 +114,9 @@ fn build_container(
 
         blocks.extend(slice.external_data_blocks);
 
-        let last_landmark = landmarks.last().copied().unwrap_or(0);
-        let landmark = last_landmark + slice_size;
-        landmarks.push(landmark);
-
+        // A landmark is the offset of the start of a slice from the start of the container data,
+        // i.e., the first slice starts after the compression header block.
+        landmarks.push(container_size);
         container_size += slice_size;
     }
 
@@ -212,3 +211,42 @@ where
 {
     writer.write_all(&EOF)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_container_landmarks() -> io::Result<()> {
+        let reference_sequence_repository = fasta::Repository::default();
+        let options = Options::default();
+        let header = sam::Header::default();
+        let mut records = vec![Record::default(); DEFAULT_RECORDS_PER_SLICE + 1];
+
+        let (container_header, container_size, blocks) = build_container(
+            &reference_sequence_repository,
+            &options,
+            &header,
+            0,
+            &mut records,
+        )?;
+
+        let mut slice_offsets = Vec::new();
+        let mut offset = 0;
+
+        for block in &blocks {
+            if block.content_type == ContentType::SliceHeader {
+                slice_offsets.push(offset);
+            }
+
+            offset += block.size()?;
+        }
+
+        assert_eq!(offset, container_size);
+        assert_eq!(slice_offsets.len(), 2);
+        assert_eq!(slice_offsets[0], blocks[0].size()?);
+        assert_eq!(container_header.landmarks(), slice_offsets);
+
+        Ok(())
+    }
+}
@@ -79,7 +79,7 @@ pub(super) fn build_slice(
     })
 }
 
-fn get_reference_sequence_context(records: &[Record]) -> ReferenceSequenceContext {
+pub(crate) fn get_reference_sequence_context(records: &[Record]) -> ReferenceSequenceContext {
     assert!(!records.is_empty());
 
     let record = &records[0];
@@ -0,0 +1,89 @@
+use std::{cmp, collections::BTreeMap, io};
+
+use noodles_core::Position;
+
+use super::{DEFAULT_RECORDS_PER_SLICE, Record, container::slice::get_reference_sequence_context};
+use crate::{container::ReferenceSequenceContext, crai};
+
+// Adds the index records of a written container.
+//
+// Slices are built from chunks of `DEFAULT_RECORDS_PER_SLICE` records, so the records of slice
+// `i` are the `i`th chunk.
+pub(super) fn push_index_records(
+    index: &mut crai::Index,
+    records: &[Record],
+    landmarks: &[usize],
+    container_len: usize,
+    container_position: u64,
+) -> io::Result<()> {
+    for (i, slice_records) in records.chunks(DEFAULT_RECORDS_PER_SLICE).enumerate() {
+        let landmark = landmarks
+            .get(i)
+            .copied()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing landmark"))?;
+
+        let slice_end = landmarks.get(i + 1).copied().unwrap_or(container_len);
+        let slice_length = slice_end - landmark;
+
+        let push = |index: &mut crai::Index, reference_sequence_id, alignment_start, span| {
+            index.push(crai::Record::new(
+                reference_sequence_id,
+                alignment_start,
+                span,
+                container_position,
+                landmark as u64,
+                slice_length as u64,
+            ));
+        };
+
+        match get_reference_sequence_context(slice_records) {
+            ReferenceSequenceContext::Some(context) => push(
+                index,
+                Some(context.reference_sequence_id()),
+                Some(context.alignment_start()),
+                context.alignment_span(),
+            ),
+            ReferenceSequenceContext::None => push(index, None, None, 0),
+            ReferenceSequenceContext::Many => {
+                for (reference_sequence_id, interval) in collect_intervals(slice_records) {
+                    match interval {
+                        Some((start, end)) => {
+                            let span = usize::from(end) - usize::from(start) + 1;
+                            push(index, reference_sequence_id, Some(start), span);
+                        }
+                        None => push(index, None, None, 0),
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Returns the alignment interval of each reference sequence in a multi-reference slice.
+fn collect_intervals(records: &[Record]) -> BTreeMap<Option<usize>, Option<(Position, Position)>> {
+    let mut intervals = BTreeMap::new();
+
+    for record in records {
+        match (
+            record.reference_sequence_id,
+            record.alignment_start,
+            record.alignment_end(),
+        ) {
+            (Some(id), Some(start), Some(end)) => {
+                let interval = intervals.entry(Some(id)).or_insert(Some((start, end)));
+
+                if let Some((interval_start, interval_end)) = interval {
+                    *interval_start = cmp::min(*interval_start, start);
+                    *interval_end = cmp::max(*interval_end, end);
+                }
+            }
+            _ => {
+                intervals.entry(None).or_insert(None);
+            }
+        }
+    }
+
+    intervals
+}
@@ -11,6 +11,7 @@ pub mod fs;
 mod huffman;
 pub mod io;
 pub mod record;
+pub mod repository;
 
 use md5::{Digest, Md5};
 
@@ -0,0 +1,3 @@
+//! CRAM reference sequence repository.
+
+pub mod adapters;
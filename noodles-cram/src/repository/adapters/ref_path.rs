@@ -0,0 +1,281 @@
+//! htslib-compatible `REF_PATH`/`REF_CACHE` adapter.
+
+mod builder;
+
+use std::{collections::HashMap, io};
+
+use noodles_fasta::{
+    self as fasta,
+    record::{Definition, Sequence},
+    repository::ref_cache,
+};
+use noodles_sam::header::record::value::map::reference_sequence::Md5Checksum;
+
+pub use self::builder::Builder;
+use crate::calculate_normalized_sequence_digest;
+
+/// An htslib-compatible `REF_PATH`/`REF_CACHE` adapter.
+///
+/// Reference sequence names are resolved to MD5 checksums using the `M5` fields of the `@SQ`
+/// records in a SAM header. Sequences are then looked up by checksum, first in the `REF_CACHE`
+/// and then in each `REF_PATH` directory template, where `%Ns` is replaced by the next `N`
+/// characters of the checksum and `%s`, by the remaining characters. Each file holds a raw,
+/// normalized sequence, i.e., without a FASTA definition or line breaks.
+///
+/// When a sequence is not found, the fallback adapter, if any, is queried. The fallback sequence
+/// must match the checksum and is normalized and written to the `REF_CACHE`, if set.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_cram::repository::adapters::RefPath;
+/// use noodles_fasta as fasta;
+/// use noodles_sam as sam;
+///
+/// let header = sam::Header::default();
+///
+/// let adapter = RefPath::builder()
+///     .set_ref_path("/data/ref/%2s/%2s/%s")
+///     .set_ref_cache("/tmp/hts-ref/%2s/%2s/%s")
+///     .build_from_header(&header);
+///
+/// let repository = fasta::Repository::new(adapter);
+/// ```
+pub struct RefPath {
+    checksums: HashMap<Vec<u8>, Md5Checksum>,
+    ref_path: Vec<String>,
+    ref_cache: Option<String>,
+    fallback: Option<Box<dyn fasta::repository::Adapter>>,
+}
+
+impl RefPath {
+    /// Returns a builder to create a `REF_PATH`/`REF_CACHE` adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::repository::adapters::RefPath;
+    /// let builder = RefPath::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn find(&self, checksum: &str) -> io::Result<Option<Vec<u8>>> {
+        let templates = self.ref_cache.iter().chain(&self.ref_path);
+
+        for template in templates {
+            let path = ref_cache::expand_path(template, checksum);
+
+            if let Some(buf) = ref_cache::read(path)? {
+                return Ok(Some(buf));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn get_from_fallback(
+        &mut self,
+        name: &[u8],
+        checksum: Md5Checksum,
+    ) -> Option<io::Result<Vec<u8>>> {
+        let fallback = self.fallback.as_mut()?;
+
+        let record = match fallback.get(name)? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let sequence = record.sequence().as_ref();
+        let actual_checksum = Md5Checksum::from(calculate_normalized_sequence_digest(sequence));
+
+        if actual_checksum != checksum {
+            return Some(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "reference sequence checksum mismatch: expected {checksum}, got {actual_checksum}"
+                ),
+            )));
+        }
+
+        let normalized_sequence = normalize(sequence);
+
+        if let Some(template) = &self.ref_cache {
+            let path = ref_cache::expand_path(template, &checksum.to_string());
+
+            if let Err(e) = ref_cache::write(path, &normalized_sequence) {
+                return Some(Err(e));
+            }
+        }
+
+        Some(Ok(normalized_sequence))
+    }
+}
+
+impl fasta::repository::Adapter for RefPath {
+    fn get(&mut self, name: &[u8]) -> Option<io::Result<fasta::Record>> {
+        let result = match self.checksums.get(name).copied() {
+            Some(checksum) => match self.find(&checksum.to_string()) {
+                Ok(Some(sequence)) => Ok(sequence),
+                Ok(None) => self.get_from_fallback(name, checksum)?,
+                Err(e) => Err(e),
+            },
+            None => return self.fallback.as_mut()?.get(name),
+        };
+
+        Some(result.map(|sequence| {
+            fasta::Record::new(Definition::new(name, None), Sequence::from(sequence))
+        }))
+    }
+}
+
+// _Sequence Alignment/Map Format Specification_ (2021-06-03) § 1.3.2 "Reference MD5 calculation"
+fn normalize(sequence: &[u8]) -> Vec<u8> {
+    sequence
+        .iter()
+        .filter(|b| b.is_ascii_graphic())
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, num::NonZeroUsize, process};
+
+    use noodles_fasta::repository::Adapter as _;
+    use noodles_sam::{
+        self as sam,
+        header::record::value::{
+            Map,
+            map::{ReferenceSequence, reference_sequence::tag},
+        },
+    };
+
+    use super::*;
+
+    // MD5 of `ACGT`.
+    const CHECKSUM: &str = "f1f8f4bf413b16ad135722aa4591043e";
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        let length = NonZeroUsize::try_from(4)?;
+
+        Ok(sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::builder()
+                    .set_length(length)
+                    .insert(tag::MD5_CHECKSUM, CHECKSUM)
+                    .build()?,
+            )
+            .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(length))
+            .build())
+    }
+
+    fn build_fallback() -> Vec<fasta::Record> {
+        vec![
+            fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"acgt".to_vec()),
+            ),
+            fasta::Record::new(
+                Definition::new("sq1", None),
+                Sequence::from(b"TTTT".to_vec()),
+            ),
+        ]
+    }
+
+    fn get_sequence(adapter: &mut RefPath, name: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        adapter
+            .get(name)
+            .transpose()
+            .map(|record| record.map(|r| r.sequence().as_ref().to_vec()))
+    }
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let root = env::temp_dir().join(format!("noodles-cram-ref-path-{}", process::id()));
+        let ref_path = root.join("ref");
+        let ref_cache = root.join("cache");
+
+        ref_cache::write(ref_path.join(CHECKSUM), b"ACGT")?;
+
+        let header = build_header()?;
+
+        let mut adapter = RefPath::builder()
+            .set_ref_path(format!("{}/%s", ref_path.display()))
+            .build_from_header(&header);
+
+        assert_eq!(get_sequence(&mut adapter, b"sq0")?, Some(b"ACGT".to_vec()));
+        assert!(get_sequence(&mut adapter, b"sq1")?.is_none());
+        assert!(get_sequence(&mut adapter, b"sq2")?.is_none());
+
+        fs::remove_dir_all(&ref_path)?;
+
+        let template = format!("{}/%2s/%2s/%s", ref_cache.display());
+
+        let mut adapter = RefPath::builder()
+            .set_ref_cache(template.clone())
+            .set_fallback(build_fallback())
+            .build_from_header(&header);
+
+        assert_eq!(get_sequence(&mut adapter, b"sq0")?, Some(b"ACGT".to_vec()));
+        assert_eq!(get_sequence(&mut adapter, b"sq1")?, Some(b"TTTT".to_vec()));
+
+        let cache_path = ref_cache.join("f1").join("f8").join(&CHECKSUM[4..]);
+        assert_eq!(fs::read(&cache_path)?, b"ACGT");
+
+        let mut adapter = RefPath::builder()
+            .set_ref_cache(template)
+            .build_from_header(&header);
+
+        assert_eq!(get_sequence(&mut adapter, b"sq0")?, Some(b"ACGT".to_vec()));
+
+        fs::remove_dir_all(&root)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_checksum_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        fn build_fallback() -> Vec<fasta::Record> {
+            vec![fasta::Record::new(
+                Definition::new("sq0", None),
+                Sequence::from(b"NNNN".to_vec()),
+            )]
+        }
+
+        let ref_cache =
+            env::temp_dir().join(format!("noodles-cram-ref-path-mismatch-{}", process::id()));
+
+        let header = build_header()?;
+
+        let mut adapter = RefPath::builder()
+            .set_ref_cache(format!("{}/%s", ref_cache.display()))
+            .set_fallback(build_fallback())
+            .build_from_header(&header);
+
+        assert!(matches!(
+            get_sequence(&mut adapter, b"sq0"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(!ref_cache.join(CHECKSUM).exists());
+
+        let mut adapter = RefPath::builder()
+            .set_fallback(build_fallback())
+            .build_from_header(&header);
+
+        assert!(matches!(
+            get_sequence(&mut adapter, b"sq0"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize(b"ac gt\nNN"), b"ACGTNN");
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use bstr::ByteSlice;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    header::record::value::map::reference_sequence::{Md5Checksum, tag},
+};
+
+use super::RefPath;
+
+/// A `REF_PATH`/`REF_CACHE` adapter builder.
+#[derive(Default)]
+pub struct Builder {
+    ref_path: Vec<String>,
+    ref_cache: Option<String>,
+    fallback: Option<Box<dyn fasta::repository::Adapter>>,
+}
+
+impl Builder {
+    /// Sets the directory templates to search.
+    ///
+    /// This uses the same format as htslib's `REF_PATH`, i.e., a colon-separated list of
+    /// templates. URL entries, e.g., `https://www.ebi.ac.uk/ena/cram/md5/%s`, are not supported
+    /// and are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::repository::adapters::RefPath;
+    /// let builder = RefPath::builder().set_ref_path("/data/ref/%2s/%2s/%s:/data/ref/%s");
+    /// ```
+    pub fn set_ref_path<S>(mut self, ref_path: S) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.ref_path = split_ref_path(ref_path.as_ref())
+            .filter(|template| !template.is_empty() && !template.contains("://"))
+            .map(String::from)
+            .collect();
+
+        self
+    }
+
+    /// Sets the cache directory template.
+    ///
+    /// This uses the same format as htslib's `REF_CACHE`, e.g., `$HOME/.cache/hts-ref/%2s/%2s/%s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::repository::adapters::RefPath;
+    /// let builder = RefPath::builder().set_ref_cache("/tmp/hts-ref/%2s/%2s/%s");
+    /// ```
+    pub fn set_ref_cache<S>(mut self, ref_cache: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.ref_cache = Some(ref_cache.into());
+        self
+    }
+
+    /// Sets the fallback adapter.
+    ///
+    /// The fallback is queried when a sequence is not found in any directory, e.g., a refget
+    /// adapter. Its sequences must match their checksums and are written to the cache, if set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::repository::adapters::RefPath;
+    /// use noodles_fasta::repository::adapters::Empty;
+    /// let builder = RefPath::builder().set_fallback(Empty);
+    /// ```
+    pub fn set_fallback<A>(mut self, fallback: A) -> Self
+    where
+        A: fasta::repository::Adapter + 'static,
+    {
+        self.fallback = Some(Box::new(fallback));
+        self
+    }
+
+    /// Builds a `REF_PATH`/`REF_CACHE` adapter using the MD5 checksums (`M5`) of the reference
+    /// sequences in the given SAM header.
+    ///
+    /// Reference sequences without a valid MD5 checksum are only queried from the fallback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_cram::repository::adapters::RefPath;
+    /// use noodles_sam as sam;
+    /// let header = sam::Header::default();
+    /// let adapter = RefPath::builder().build_from_header(&header);
+    /// ```
+    pub fn build_from_header(self, header: &sam::Header) -> RefPath {
+        let checksums = header
+            .reference_sequences()
+            .iter()
+            .filter_map(|(name, reference_sequence)| {
+                let value = reference_sequence
+                    .other_fields()
+                    .get(&tag::MD5_CHECKSUM)?
+                    .to_str()
+                    .ok()?;
+
+                let checksum: Md5Checksum = value.parse().ok()?;
+
+                Some((name.to_vec(), checksum))
+            })
+            .collect::<HashMap<_, _>>();
+
+        RefPath {
+            checksums,
+            ref_path: self.ref_path,
+            ref_cache: self.ref_cache,
+            fallback: self.fallback,
+        }
+    }
+}
+
+// Splits a `REF_PATH` value at colons, except those that start a URL scheme separator (`://`).
+fn split_ref_path(s: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let mut is_done = false;
+
+    std::iter::from_fn(move || {
+        if is_done {
+            return None;
+        }
+
+        let mut i = start;
+
+        loop {
+            match s[i..].find(':') {
+                Some(j) if s[i + j..].starts_with("://") => i += j + 1,
+                Some(j) => {
+                    let entry = &s[start..i + j];
+                    start = i + j + 1;
+                    return Some(entry);
+                }
+                None => {
+                    is_done = true;
+                    return Some(&s[start..]);
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_ref_path() {
+        let actual: Vec<_> =
+            split_ref_path("/data/ref/%2s/%2s/%s:https://www.ebi.ac.uk/ena/cram/md5/%s:/ref/%s")
+                .collect();
+
+        assert_eq!(
+            actual,
+            [
+                "/data/ref/%2s/%2s/%s",
+                "https://www.ebi.ac.uk/ena/cram/md5/%s",
+                "/ref/%s"
+            ]
+        );
+
+        assert_eq!(split_ref_path("").collect::<Vec<_>>(), [""]);
+    }
+
+    #[test]
+    fn test_set_ref_path() {
+        let builder = Builder::default().set_ref_path("/data/ref/%s::https://example.com/%s");
+        assert_eq!(builder.ref_path, ["/data/ref/%s"]);
+    }
+}
@@ -1,3 +1,6 @@
 //! Async FASTA.
 
 pub mod io;
+pub mod repository;
+
+pub use self::repository::Repository;
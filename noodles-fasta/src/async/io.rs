@@ -1,6 +1,7 @@
 //! Async FASTA I/O.
 
+pub mod indexed_reader;
 pub(crate) mod reader;
 pub mod writer;
 
-pub use self::{reader::Reader, writer::Writer};
+pub use self::{indexed_reader::IndexedReader, reader::Reader, writer::Writer};
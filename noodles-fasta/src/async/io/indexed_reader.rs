@@ -0,0 +1,97 @@
+//! Async indexed FASTA reader.
+
+use noodles_core::Region;
+use tokio::io::{self, AsyncBufRead, AsyncSeek};
+
+use super::Reader;
+use crate::{Record, fai};
+
+/// An async indexed FASTA reader.
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: fai::Index,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Creates a new async indexed FASTA reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::{self as fasta, fai};
+    /// use tokio::io;
+    /// let reader = fasta::r#async::io::IndexedReader::new(io::empty(), fai::Index::default());
+    /// ```
+    pub fn new(inner: R, index: fai::Index) -> Self {
+        Self {
+            inner: Reader::new(inner),
+            index,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Reads a raw definition line.
+    pub async fn read_definition(&mut self, buf: &mut String) -> io::Result<usize> {
+        self.inner.read_definition(buf).await
+    }
+
+    /// Reads a sequence.
+    pub async fn read_sequence(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.inner.read_sequence(buf).await
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &fai::Index {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Returns a record of the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_fasta::{self as fasta, fai};
+    ///
+    /// let data = b">sq0\nNNNN\n>sq1\nACGT\n";
+    /// let index = fai::Index::from(vec![
+    ///     fai::Record::new("sq0", 4, 5, 4, 5),
+    ///     fai::Record::new("sq1", 4, 15, 4, 5),
+    /// ]);
+    ///
+    /// let mut reader = fasta::r#async::io::IndexedReader::new(Cursor::new(data), index);
+    ///
+    /// let record = reader.query(&"sq1:2-3".parse()?).await?;
+    /// assert_eq!(record.sequence().as_ref(), b"CG");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query(&mut self, region: &Region) -> io::Result<Record> {
+        self.inner.query(&self.index, region).await
+    }
+}
@@ -1,10 +1,15 @@
 mod sequence;
 
+use noodles_core::{Position, Region};
 use tokio::io::{
     self, AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom,
 };
 
-use self::sequence::read_sequence;
+use self::sequence::{read_sequence, read_sequence_limit};
+use crate::{
+    Record, fai,
+    record::{Definition, Sequence},
+};
 
 /// An async FASTA reader.
 pub struct Reader<R> {
@@ -134,6 +139,59 @@ where
     }
 }
 
+impl<R> Reader<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Returns a record of the given region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use noodles_fasta::{self as fasta, fai, record::{Definition, Sequence}};
+    ///
+    /// let data = b">sq0\nNNNN\n>sq1\nACGT\n>sq2\nNNNN\n";
+    /// let index = fai::Index::from(vec![
+    ///     fai::Record::new("sq0", 4, 5, 4, 5),
+    ///     fai::Record::new("sq1", 4, 15, 4, 5),
+    ///     fai::Record::new("sq2", 4, 25, 4, 5),
+    /// ]);
+    ///
+    /// let mut reader = fasta::r#async::io::Reader::new(Cursor::new(data));
+    ///
+    /// let region = "sq1:2-3".parse()?;
+    /// let record = reader.query(&index, &region).await?;
+    /// assert_eq!(record, fasta::Record::new(
+    ///     Definition::new("sq1:2-3", None),
+    ///     Sequence::from(b"CG".to_vec()),
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn query(&mut self, index: &fai::Index, region: &Region) -> io::Result<Record> {
+        let pos = index.query(region)?;
+        self.inner.seek(SeekFrom::Start(pos)).await?;
+
+        let definition = Definition::new(region.to_string(), None);
+
+        let interval = region.interval();
+        let start = usize::from(interval.start().unwrap_or(Position::MIN));
+        let end = usize::from(interval.end().unwrap_or(Position::MAX));
+        let len = end - start + 1;
+
+        let mut raw_sequence = Vec::new();
+        read_sequence_limit(&mut self.inner, len, &mut raw_sequence).await?;
+
+        let sequence = Sequence::from(raw_sequence);
+
+        Ok(Record::new(definition, sequence))
+    }
+}
+
 pub(crate) async fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
 where
     R: AsyncBufRead + Unpin,
@@ -1,16 +1,15 @@
+use memchr::memchr;
 use tokio::io::{self, AsyncBufRead, AsyncBufReadExt};
 
+use crate::io::reader::DEFINITION_PREFIX;
+
+const LINE_FEED: u8 = b'\n';
+const CARRIAGE_RETURN: u8 = b'\r';
+
 pub(super) async fn read_sequence<R>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<usize>
 where
     R: AsyncBufRead + Unpin,
 {
-    use memchr::memchr;
-
-    use crate::io::reader::DEFINITION_PREFIX;
-
-    const LINE_FEED: u8 = b'\n';
-    const CARRIAGE_RETURN: u8 = b'\r';
-
     let mut n = 0;
 
     loop {
@@ -47,6 +46,46 @@ where
     Ok(n)
 }
 
+pub(super) async fn read_sequence_limit<R>(
+    reader: &mut R,
+    max_bases: usize,
+    buf: &mut Vec<u8>,
+) -> io::Result<usize>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut len = 0;
+
+    while buf.len() < max_bases {
+        let src = reader.fill_buf().await?;
+
+        if src.first().map(|&b| b == DEFINITION_PREFIX).unwrap_or(true) {
+            break;
+        }
+
+        let (line, line_len) = match memchr(LINE_FEED, src) {
+            Some(i) => (&src[..i], i + 1),
+            None => (src, src.len()),
+        };
+
+        let bases = line.strip_suffix(&[CARRIAGE_RETURN]).unwrap_or(line);
+        let remaining_bases = max_bases - buf.len();
+
+        let (n, consumed_len) = if bases.len() <= remaining_bases {
+            (bases.len(), line_len)
+        } else {
+            (remaining_bases, remaining_bases)
+        };
+
+        buf.extend(&bases[..n]);
+        reader.consume(consumed_len);
+
+        len += n;
+    }
+
+    Ok(len)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +107,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_read_sequence_limit() -> io::Result<()> {
+        async fn t(
+            buf: &mut Vec<u8>,
+            mut reader: &[u8],
+            max_bases: usize,
+            expected: &[u8],
+        ) -> io::Result<()> {
+            buf.clear();
+            read_sequence_limit(&mut reader, max_bases, buf).await?;
+            assert_eq!(buf, expected);
+            Ok(())
+        }
+
+        let mut buf = Vec::new();
+
+        t(&mut buf, b"ACGT\n", 2, b"AC").await?;
+        t(&mut buf, b"ACGT\n", 8, b"ACGT").await?;
+        t(&mut buf, b"ACGT\n>sq1\n", 8, b"ACGT").await?;
+        t(&mut buf, b"NNNN\r\nACGT\r\nNN\r\n", 6, b"NNNNAC").await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,177 @@
+//! Async sequence repository and adapters.
+
+mod adapter;
+pub mod adapters;
+
+pub use self::adapter::Adapter;
+
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use tokio::{io, sync::RwLock};
+
+use crate::record::Sequence;
+
+struct AdapterCache {
+    adapter: Box<dyn Adapter>,
+    cache: HashMap<Vec<u8>, Sequence>,
+}
+
+/// An async caching sequence repository.
+///
+/// This has the same cache semantics as [`crate::Repository`]: sequences are fetched from the
+/// adapter once and then shared by all clones of the repository.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main]
+/// # async fn main() -> tokio::io::Result<()> {
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+///
+/// let records = vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGT".to_vec()),
+/// )];
+///
+/// let repository = fasta::r#async::Repository::new(records);
+///
+/// let sequence = repository.get(b"sq0").await.transpose()?;
+/// assert_eq!(sequence, Some(Sequence::from(b"ACGT".to_vec())));
+/// # Ok(())
+/// # }
+/// ```
+pub struct Repository(Arc<RwLock<AdapterCache>>);
+
+impl Repository {
+    /// Creates an async sequence repository.
+    pub fn new<A>(adapter: A) -> Self
+    where
+        A: Adapter + 'static,
+    {
+        Self(Arc::new(RwLock::new(AdapterCache {
+            adapter: Box::new(adapter),
+            cache: HashMap::new(),
+        })))
+    }
+
+    /// Returns the sequence of the given name.
+    pub async fn get(&self, name: &[u8]) -> Option<io::Result<Sequence>> {
+        {
+            let lock = self.0.read().await;
+
+            if let Some(sequence) = lock.cache.get(name) {
+                return Some(Ok(sequence.clone()));
+            }
+        }
+
+        let mut lock = self.0.write().await;
+
+        if let Some(sequence) = lock.cache.get(name) {
+            return Some(Ok(sequence.clone()));
+        }
+
+        let record = match lock.adapter.get(name).await? {
+            Ok(record) => record,
+            Err(e) => return Some(Err(e)),
+        };
+
+        lock.cache
+            .entry(name.into())
+            .or_insert_with(|| record.sequence().clone());
+
+        Some(Ok(record.sequence().clone()))
+    }
+
+    /// Returns the number of cached sequences.
+    pub async fn len(&self) -> usize {
+        self.0.read().await.cache.len()
+    }
+
+    /// Returns whether any sequences are cached.
+    pub async fn is_empty(&self) -> bool {
+        self.0.read().await.cache.is_empty()
+    }
+
+    /// Clears the sequence cache.
+    pub async fn clear(&self) {
+        self.0.write().await.cache.clear();
+    }
+}
+
+impl Clone for Repository {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl fmt::Debug for Repository {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Repository").finish_non_exhaustive()
+    }
+}
+
+impl Default for Repository {
+    fn default() -> Self {
+        Self::new(adapters::Empty::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{
+        Record, fai,
+        record::{Definition, Sequence},
+    };
+
+    #[tokio::test]
+    async fn test_get() -> io::Result<()> {
+        let sq0 = Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGT".to_vec()),
+        );
+        let repository = Repository::new(vec![sq0.clone()]);
+
+        assert_eq!(
+            repository.get(b"sq0").await.transpose()?,
+            Some(sq0.sequence().clone())
+        );
+        assert_eq!(repository.get(b"sq1").await.transpose()?, None);
+
+        assert_eq!(repository.len().await, 1);
+
+        repository.clear().await;
+        assert!(repository.is_empty().await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_with_indexed_reader_adapter() -> io::Result<()> {
+        let data = b">sq0\nNNNN\n>sq1\nACGT\nAC\n";
+        let index = fai::Index::from(vec![
+            fai::Record::new("sq0", 4, 5, 4, 5),
+            fai::Record::new("sq1", 6, 15, 4, 5),
+        ]);
+
+        let reader = crate::r#async::io::IndexedReader::new(Cursor::new(data), index);
+        let repository = Repository::new(adapters::IndexedReader::new(reader));
+
+        let clone = repository.clone();
+        let sequence = tokio::spawn(async move { clone.get(b"sq1").await.transpose() })
+            .await
+            .map_err(io::Error::other)??;
+
+        assert_eq!(sequence, Some(Sequence::from(b"ACGTAC".to_vec())));
+        assert_eq!(repository.len().await, 1);
+
+        assert!(matches!(
+            repository.get(b"sq2").await,
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
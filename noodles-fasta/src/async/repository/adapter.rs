@@ -0,0 +1,14 @@
+use std::{future::Future, pin::Pin};
+
+use tokio::io;
+
+use crate::Record;
+
+/// An async repository adapter.
+pub trait Adapter: Send + Sync {
+    /// Returns the record with the given name.
+    fn get<'a>(
+        &'a mut self,
+        name: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Option<io::Result<Record>>> + Send + 'a>>;
+}
@@ -0,0 +1,38 @@
+use std::{future::Future, pin::Pin};
+
+use tokio::io;
+
+use crate::{Record, r#async::repository::Adapter};
+
+/// An empty async adapter.
+///
+/// This adapter always returns `None`.
+#[derive(Default)]
+pub struct Empty;
+
+impl Empty {
+    /// Creates an empty async adapter.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Adapter for Empty {
+    fn get<'a>(
+        &'a mut self,
+        _: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Option<io::Result<Record>>> + Send + 'a>> {
+        Box::pin(async { None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get() {
+        let mut adapter = Empty::new();
+        assert!(adapter.get(b"sq0").await.is_none());
+    }
+}
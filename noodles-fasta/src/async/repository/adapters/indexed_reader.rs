@@ -0,0 +1,51 @@
+use std::{future::Future, pin::Pin};
+
+use noodles_core::Region;
+use tokio::io::{self, AsyncBufRead, AsyncSeek};
+
+use crate::{Record, r#async::repository::Adapter};
+
+/// An async indexed reader adapter.
+pub struct IndexedReader<R> {
+    reader: crate::r#async::io::IndexedReader<R>,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    /// Creates an async indexed reader adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::{
+    ///     self as fasta,
+    ///     fai,
+    ///     r#async::repository::adapters::IndexedReader,
+    /// };
+    /// use tokio::io;
+    ///
+    /// let index = fai::Index::default();
+    /// let reader = fasta::r#async::io::IndexedReader::new(io::empty(), index);
+    /// let adapter = IndexedReader::new(reader);
+    /// ```
+    pub fn new(reader: crate::r#async::io::IndexedReader<R>) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R> Adapter for IndexedReader<R>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin + Send + Sync,
+{
+    fn get<'a>(
+        &'a mut self,
+        name: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Option<io::Result<Record>>> + Send + 'a>> {
+        Box::pin(async move {
+            let region = Region::new(name, ..);
+            Some(self.reader.query(&region).await)
+        })
+    }
+}
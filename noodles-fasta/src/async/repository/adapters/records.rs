@@ -0,0 +1,15 @@
+use std::{future::Future, pin::Pin};
+
+use tokio::io;
+
+use crate::{Record, r#async::repository::Adapter};
+
+impl Adapter for Vec<Record> {
+    fn get<'a>(
+        &'a mut self,
+        name: &'a [u8],
+    ) -> Pin<Box<dyn Future<Output = Option<io::Result<Record>>> + Send + 'a>> {
+        let record = self.iter().find(|record| record.name() == name).cloned();
+        Box::pin(async { record.map(Ok) })
+    }
+}
@@ -0,0 +1,324 @@
+//! Reference sequence composition and masking reports.
+
+use std::{
+    io::{self, BufRead, Write},
+    num::NonZeroUsize,
+};
+
+use bstr::BString;
+use noodles_bed::{self as bed, feature::RecordBuf};
+use noodles_core::Position;
+
+use crate::{io::Reader, record::Definition};
+
+/// Writes GC content, gap, and soft-masked intervals of each sequence as BED records.
+///
+/// This reads all remaining records in a single pass, without buffering whole sequences.
+///
+/// * GC content is written as BED3+1 records, where the extra field is the fraction of `G` and
+///   `C` bases of all `A`, `C`, `G`, and `T` bases. This is either written per window, when a
+///   window size is given, or per sequence. Intervals without any of these bases, e.g., those
+///   only consisting of `N`, are not written.
+/// * Gaps are runs of `N` or `n` bases.
+/// * Soft-masked intervals are runs of lowercase bases.
+///
+/// Bases are case-insensitive when calculating GC content.
+///
+/// # Examples
+///
+/// ```
+/// # use std::{io, num::NonZeroUsize};
+/// use noodles_bed as bed;
+/// use noodles_fasta::{self as fasta, composition};
+///
+/// let mut reader = fasta::io::Reader::new(&b">sq0\nACgtNNNNGGCC\n"[..]);
+///
+/// let mut gc_writer = bed::io::Writer::<3, _>::new(Vec::new());
+/// let mut gap_writer = bed::io::Writer::<3, _>::new(Vec::new());
+/// let mut mask_writer = bed::io::Writer::<3, _>::new(Vec::new());
+///
+/// composition::write_reports(
+///     &mut reader,
+///     NonZeroUsize::new(4),
+///     &mut gc_writer,
+///     &mut gap_writer,
+///     &mut mask_writer,
+/// )?;
+///
+/// assert_eq!(gc_writer.get_ref(), b"sq0\t0\t4\t0.5\nsq0\t8\t12\t1\n");
+/// assert_eq!(gap_writer.get_ref(), b"sq0\t4\t8\n");
+/// assert_eq!(mask_writer.get_ref(), b"sq0\t2\t4\n");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn write_reports<R, G, N, M>(
+    reader: &mut Reader<R>,
+    window_size: Option<NonZeroUsize>,
+    gc_writer: &mut bed::io::Writer<3, G>,
+    gap_writer: &mut bed::io::Writer<3, N>,
+    mask_writer: &mut bed::io::Writer<3, M>,
+) -> io::Result<()>
+where
+    R: BufRead,
+    G: Write,
+    N: Write,
+    M: Write,
+{
+    let mut line_buf = String::new();
+
+    loop {
+        line_buf.clear();
+
+        if reader.read_definition(&mut line_buf)? == 0 {
+            break;
+        }
+
+        let definition: Definition = line_buf
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut state = State::new(definition.name().into(), window_size);
+        let mut sequence_reader = reader.sequence_reader();
+
+        loop {
+            let src = sequence_reader.fill_buf()?;
+
+            if src.is_empty() {
+                break;
+            }
+
+            for &b in src {
+                state.push(b, gc_writer, gap_writer, mask_writer)?;
+            }
+
+            let len = src.len();
+            sequence_reader.consume(len);
+        }
+
+        state.finish(gc_writer, gap_writer, mask_writer)?;
+    }
+
+    Ok(())
+}
+
+struct State {
+    name: BString,
+    window_size: Option<NonZeroUsize>,
+    position: usize,
+    window_start: usize,
+    gc_count: u64,
+    at_count: u64,
+    gap_start: Option<usize>,
+    mask_start: Option<usize>,
+}
+
+impl State {
+    fn new(name: BString, window_size: Option<NonZeroUsize>) -> Self {
+        Self {
+            name,
+            window_size,
+            position: 0,
+            window_start: 0,
+            gc_count: 0,
+            at_count: 0,
+            gap_start: None,
+            mask_start: None,
+        }
+    }
+
+    fn push<G, N, M>(
+        &mut self,
+        base: u8,
+        gc_writer: &mut bed::io::Writer<3, G>,
+        gap_writer: &mut bed::io::Writer<3, N>,
+        mask_writer: &mut bed::io::Writer<3, M>,
+    ) -> io::Result<()>
+    where
+        G: Write,
+        N: Write,
+        M: Write,
+    {
+        match base.to_ascii_uppercase() {
+            b'G' | b'C' => self.gc_count += 1,
+            b'A' | b'T' => self.at_count += 1,
+            _ => {}
+        }
+
+        let is_gap = base.eq_ignore_ascii_case(&b'N');
+        update_run(&mut self.gap_start, is_gap, self.position, |start, end| {
+            write_interval(gap_writer, &self.name, start, end, None)
+        })?;
+
+        let is_masked = base.is_ascii_lowercase();
+        update_run(
+            &mut self.mask_start,
+            is_masked,
+            self.position,
+            |start, end| write_interval(mask_writer, &self.name, start, end, None),
+        )?;
+
+        self.position += 1;
+
+        if let Some(window_size) = self.window_size {
+            if self.position - self.window_start == window_size.get() {
+                self.flush_window(gc_writer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish<G, N, M>(
+        mut self,
+        gc_writer: &mut bed::io::Writer<3, G>,
+        gap_writer: &mut bed::io::Writer<3, N>,
+        mask_writer: &mut bed::io::Writer<3, M>,
+    ) -> io::Result<()>
+    where
+        G: Write,
+        N: Write,
+        M: Write,
+    {
+        if self.position > self.window_start {
+            self.flush_window(gc_writer)?;
+        }
+
+        if let Some(start) = self.gap_start {
+            write_interval(gap_writer, &self.name, start, self.position, None)?;
+        }
+
+        if let Some(start) = self.mask_start {
+            write_interval(mask_writer, &self.name, start, self.position, None)?;
+        }
+
+        Ok(())
+    }
+
+    fn flush_window<G>(&mut self, gc_writer: &mut bed::io::Writer<3, G>) -> io::Result<()>
+    where
+        G: Write,
+    {
+        let called_count = self.gc_count + self.at_count;
+
+        if called_count > 0 {
+            let gc_content = self.gc_count as f64 / called_count as f64;
+
+            write_interval(
+                gc_writer,
+                &self.name,
+                self.window_start,
+                self.position,
+                Some(gc_content),
+            )?;
+        }
+
+        self.window_start = self.position;
+        self.gc_count = 0;
+        self.at_count = 0;
+
+        Ok(())
+    }
+}
+
+// Starts or ends a run at the given 0-based position.
+fn update_run<F>(
+    start: &mut Option<usize>,
+    is_in_run: bool,
+    position: usize,
+    f: F,
+) -> io::Result<()>
+where
+    F: FnOnce(usize, usize) -> io::Result<()>,
+{
+    match (*start, is_in_run) {
+        (None, true) => *start = Some(position),
+        (Some(s), false) => {
+            f(s, position)?;
+            *start = None;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+// Writes a BED record of the given 0-based, half-open interval.
+fn write_interval<W>(
+    writer: &mut bed::io::Writer<3, W>,
+    name: &BString,
+    start: usize,
+    end: usize,
+    value: Option<f64>,
+) -> io::Result<()>
+where
+    W: Write,
+{
+    let feature_start =
+        Position::new(start + 1).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+    let feature_end =
+        Position::new(end).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?;
+
+    let other_fields = value
+        .map(|n| vec![bed::feature::record_buf::other_fields::Value::Float64(n)])
+        .unwrap_or_default();
+
+    let record = RecordBuf::<3>::builder()
+        .set_reference_sequence_name(name.clone())
+        .set_feature_start(feature_start)
+        .set_feature_end(feature_end)
+        .set_other_fields(other_fields.into())
+        .build();
+
+    writer.write_feature_record(&record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Reports = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+    fn run(src: &[u8], window_size: Option<usize>) -> io::Result<Reports> {
+        let mut reader = Reader::new(src);
+
+        let mut gc_writer = bed::io::Writer::<3, _>::new(Vec::new());
+        let mut gap_writer = bed::io::Writer::<3, _>::new(Vec::new());
+        let mut mask_writer = bed::io::Writer::<3, _>::new(Vec::new());
+
+        write_reports(
+            &mut reader,
+            window_size.and_then(NonZeroUsize::new),
+            &mut gc_writer,
+            &mut gap_writer,
+            &mut mask_writer,
+        )?;
+
+        Ok((
+            gc_writer.into_inner(),
+            gap_writer.into_inner(),
+            mask_writer.into_inner(),
+        ))
+    }
+
+    #[test]
+    fn test_write_reports() -> io::Result<()> {
+        let src = b">sq0\nNNac\ngtAA\nAAnn\n>sq1 desc\nGGCC\nNN\n";
+
+        let (gc, gaps, masks) = run(src, None)?;
+
+        assert_eq!(gc, b"sq0\t0\t12\t0.25\nsq1\t0\t6\t1\n");
+        assert_eq!(gaps, b"sq0\t0\t2\nsq0\t10\t12\nsq1\t4\t6\n");
+        assert_eq!(masks, b"sq0\t2\t6\nsq0\t10\t12\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_reports_with_window_size() -> io::Result<()> {
+        let src = b">sq0\nNNNNGCAT\nGGG\n";
+
+        let (gc, _, _) = run(src, Some(4))?;
+        assert_eq!(gc, b"sq0\t4\t8\t0.5\nsq0\t8\t11\t1\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! FASTA sequence dictionary.
+//!
+//! A sequence dictionary is a SAM header with a reference sequence (`@SQ`) record for each
+//! sequence in a FASTA. Each record includes the sequence length (`LN`) and the MD5 checksum of
+//! the normalized sequence (`M5`). When written to a file, this is typically given the extension
+//! `.dict`, e.g., `reference.dict`.
+
+mod builder;
+
+use std::{fs::File, io, path::Path};
+
+use md5::{Digest, Md5};
+use noodles_sam as sam;
+
+pub use self::builder::Builder;
+
+/// Builds a sequence dictionary from a FASTA file.
+///
+/// This is a convenience function and is equivalent to using a default [`Builder`] to scan the
+/// file at the given path.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_fasta::dict;
+/// let header = dict::build("reference.fa")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn build<P>(src: P) -> io::Result<sam::Header>
+where
+    P: AsRef<Path>,
+{
+    Builder::default().build_from_path(src)
+}
+
+/// Writes a sequence dictionary to a file.
+///
+/// The output is a Picard-style sequence dictionary, i.e., a SAM header.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_fasta::dict;
+/// let header = dict::build("reference.fa")?;
+/// dict::write("reference.dict", &header)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write<P>(dst: P, header: &sam::Header) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let mut writer = File::create(dst)
+        .map(io::BufWriter::new)
+        .map(sam::io::Writer::new)?;
+
+    writer.write_header(header)?;
+
+    io::Write::flush(writer.get_mut())
+}
+
+// _Sequence Alignment/Map Format Specification_ (2021-06-03) § 1.3.2 "Reference MD5 calculation"
+fn calculate_normalized_sequence_digest(sequence: &[u8]) -> [u8; 16] {
+    let mut hasher = Md5::new();
+
+    for chunk in sequence.split(|b| !b.is_ascii_graphic()) {
+        if chunk.iter().any(|b| b.is_ascii_lowercase()) {
+            hasher.update(chunk.to_ascii_uppercase());
+        } else {
+            hasher.update(chunk);
+        }
+    }
+
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_normalized_sequence_digest() {
+        const EXPECTED: [u8; 16] = [
+            0xf1, 0xf8, 0xf4, 0xbf, 0x41, 0x3b, 0x16, 0xad, 0x13, 0x57, 0x22, 0xaa, 0x45, 0x91,
+            0x04, 0x3e,
+        ];
+
+        assert_eq!(calculate_normalized_sequence_digest(b"ACGT"), EXPECTED);
+        assert_eq!(calculate_normalized_sequence_digest(b" AC\tgt\n"), EXPECTED);
+    }
+
+    #[test]
+    fn test_write() -> Result<(), Box<dyn std::error::Error>> {
+        use std::{env, fs, process};
+
+        use crate::io::Reader;
+
+        let mut reader = Reader::new(&b">sq0\nACGT\n"[..]);
+        let header = Builder::default().build_from_reader(&mut reader)?;
+
+        let dst = env::temp_dir().join(format!("noodles-fasta-dict-{}.dict", process::id()));
+        write(&dst, &header)?;
+
+        let actual = fs::read_to_string(&dst)?;
+        fs::remove_file(&dst)?;
+
+        let expected = "@HD\tVN:1.6\n@SQ\tSN:sq0\tLN:4\tM5:f1f8f4bf413b16ad135722aa4591043e\n";
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
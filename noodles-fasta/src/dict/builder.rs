@@ -0,0 +1,335 @@
+use std::{
+    io::{self, BufRead, Seek},
+    num::NonZeroUsize,
+    path::Path,
+};
+
+use bstr::BString;
+use noodles_core::Region;
+use noodles_sam::{
+    self as sam,
+    header::{
+        ReferenceSequences,
+        record::value::{
+            Map,
+            map::{
+                self, ReferenceSequence,
+                reference_sequence::{Md5Checksum, tag},
+            },
+        },
+    },
+};
+
+use super::calculate_normalized_sequence_digest;
+use crate::{
+    Record,
+    io::{IndexedReader, Reader},
+};
+
+/// A FASTA sequence dictionary builder.
+///
+/// The optional URI (`UR`), assembly ID (`AS`), and species (`SP`) are added to every reference
+/// sequence record.
+#[derive(Debug, Default)]
+pub struct Builder {
+    uri: Option<BString>,
+    assembly_id: Option<BString>,
+    species: Option<BString>,
+}
+
+impl Builder {
+    /// Sets the URI of the sequences (`UR`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::dict;
+    /// let builder = dict::Builder::default().set_uri("file:///data/reference.fa");
+    /// ```
+    pub fn set_uri<S>(mut self, uri: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.uri = Some(uri.into());
+        self
+    }
+
+    /// Sets the genome assembly identifier (`AS`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::dict;
+    /// let builder = dict::Builder::default().set_assembly_id("GRCh38");
+    /// ```
+    pub fn set_assembly_id<S>(mut self, assembly_id: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.assembly_id = Some(assembly_id.into());
+        self
+    }
+
+    /// Sets the species (`SP`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::dict;
+    /// let builder = dict::Builder::default().set_species("Homo sapiens");
+    /// ```
+    pub fn set_species<S>(mut self, species: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.species = Some(species.into());
+        self
+    }
+
+    /// Builds a sequence dictionary by scanning a FASTA file.
+    ///
+    /// bgzip-compressed files (`.gz`, `.bgz`) are decompressed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_fasta::dict;
+    /// let header = dict::Builder::default().build_from_path("reference.fa")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<sam::Header>
+    where
+        P: AsRef<Path>,
+    {
+        let mut reader = crate::io::reader::Builder.build_from_path(src)?;
+        self.build_from_reader(&mut reader)
+    }
+
+    /// Builds a sequence dictionary by reading all records from a FASTA reader.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if a record cannot be read, if a sequence is empty, or if a
+    /// name is duplicated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::{self as fasta, dict};
+    ///
+    /// let mut reader = fasta::io::Reader::new(&b">sq0\nACGT\n"[..]);
+    /// let header = dict::Builder::default().build_from_reader(&mut reader)?;
+    ///
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_reader<R>(self, reader: &mut Reader<R>) -> io::Result<sam::Header>
+    where
+        R: BufRead,
+    {
+        let mut reference_sequences = ReferenceSequences::default();
+
+        for result in reader.records() {
+            let record = result?;
+            let reference_sequence = self.build_reference_sequence(&record)?;
+            add_reference_sequence(&mut reference_sequences, record.name(), reference_sequence)?;
+        }
+
+        Ok(build_header(reference_sequences))
+    }
+
+    /// Builds a sequence dictionary using the records of a FASTA index.
+    ///
+    /// Sequences are read in index order.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if a sequence cannot be read or is empty, or if a name is
+    /// duplicated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::Cursor;
+    /// use noodles_fasta::{self as fasta, dict, fai};
+    ///
+    /// let index = fai::Index::from(vec![fai::Record::new("sq0", 4, 5, 4, 5)]);
+    /// let mut reader = fasta::io::IndexedReader::new(Cursor::new(b">sq0\nACGT\n"), index);
+    /// let header = dict::Builder::default().build_from_indexed_reader(&mut reader)?;
+    ///
+    /// assert_eq!(header.reference_sequences().len(), 1);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_indexed_reader<R>(
+        self,
+        reader: &mut IndexedReader<R>,
+    ) -> io::Result<sam::Header>
+    where
+        R: BufRead + Seek,
+    {
+        let names: Vec<_> = reader
+            .index()
+            .as_ref()
+            .iter()
+            .map(|record| record.name().to_owned())
+            .collect();
+
+        let mut reference_sequences = ReferenceSequences::default();
+
+        for name in names {
+            let region = Region::new(name.clone(), ..);
+            let record = reader.query(&region)?;
+            let reference_sequence = self.build_reference_sequence(&record)?;
+            add_reference_sequence(&mut reference_sequences, name, reference_sequence)?;
+        }
+
+        Ok(build_header(reference_sequences))
+    }
+
+    fn build_reference_sequence(&self, record: &Record) -> io::Result<Map<ReferenceSequence>> {
+        let sequence = record.sequence().as_ref();
+
+        let length = NonZeroUsize::try_from(sequence.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let checksum = Md5Checksum::from(calculate_normalized_sequence_digest(sequence));
+
+        let mut builder = Map::<ReferenceSequence>::builder()
+            .set_length(length)
+            .insert(tag::MD5_CHECKSUM, checksum.to_string());
+
+        let optional_fields = [
+            (tag::URI, &self.uri),
+            (tag::ASSEMBLY_ID, &self.assembly_id),
+            (tag::SPECIES, &self.species),
+        ];
+
+        for (tag, value) in optional_fields {
+            if let Some(value) = value {
+                builder = builder.insert(tag, value.clone());
+            }
+        }
+
+        builder
+            .build()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    }
+}
+
+fn add_reference_sequence<N>(
+    reference_sequences: &mut ReferenceSequences,
+    name: N,
+    reference_sequence: Map<ReferenceSequence>,
+) -> io::Result<()>
+where
+    N: Into<BString>,
+{
+    let name = name.into();
+
+    if reference_sequences.contains_key(&name) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("duplicate reference sequence name: {name}"),
+        ));
+    }
+
+    reference_sequences.insert(name, reference_sequence);
+
+    Ok(())
+}
+
+fn build_header(reference_sequences: ReferenceSequences) -> sam::Header {
+    sam::Header::builder()
+        .set_header(Map::<map::Header>::default())
+        .set_reference_sequences(reference_sequences)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use bstr::ByteSlice;
+
+    use super::*;
+    use crate::fai;
+
+    const SRC: &[u8] = b">sq0\nACGT\n>sq1\nnnnn\nNN\n";
+
+    #[test]
+    fn test_build_from_reader() -> io::Result<()> {
+        let mut reader = Reader::new(SRC);
+
+        let header = Builder::default()
+            .set_uri("file:///tmp/reference.fa")
+            .set_assembly_id("asm0")
+            .set_species("Homo sapiens")
+            .build_from_reader(&mut reader)?;
+
+        let reference_sequences = header.reference_sequences();
+        assert_eq!(reference_sequences.len(), 2);
+
+        let sq0 = &reference_sequences[&b"sq0"[..]];
+        assert_eq!(sq0.length().get(), 4);
+
+        let other_fields = sq0.other_fields();
+        assert_eq!(
+            other_fields.get(&tag::MD5_CHECKSUM).map(|v| v.as_bstr()),
+            Some(b"f1f8f4bf413b16ad135722aa4591043e".as_bstr())
+        );
+        assert_eq!(
+            other_fields.get(&tag::URI).map(|v| v.as_bstr()),
+            Some(b"file:///tmp/reference.fa".as_bstr())
+        );
+        assert_eq!(
+            other_fields.get(&tag::ASSEMBLY_ID).map(|v| v.as_bstr()),
+            Some(b"asm0".as_bstr())
+        );
+        assert_eq!(
+            other_fields.get(&tag::SPECIES).map(|v| v.as_bstr()),
+            Some(b"Homo sapiens".as_bstr())
+        );
+
+        let sq1 = &reference_sequences[&b"sq1"[..]];
+        assert_eq!(sq1.length().get(), 6);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_from_reader_with_empty_sequence() {
+        let mut reader = Reader::new(&b">sq0\n"[..]);
+
+        assert!(matches!(
+            Builder::default().build_from_reader(&mut reader),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_build_from_reader_with_duplicate_name() {
+        let mut reader = Reader::new(&b">sq0\nACGT\n>sq0\nNNNN\n"[..]);
+
+        assert!(matches!(
+            Builder::default().build_from_reader(&mut reader),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+
+    #[test]
+    fn test_build_from_indexed_reader() -> io::Result<()> {
+        let index = fai::Index::from(vec![
+            fai::Record::new("sq0", 4, 5, 4, 5),
+            fai::Record::new("sq1", 6, 15, 4, 5),
+        ]);
+
+        let mut reader = IndexedReader::new(Cursor::new(SRC), index);
+        let actual = Builder::default().build_from_indexed_reader(&mut reader)?;
+
+        let expected = Builder::default().build_from_reader(&mut Reader::new(SRC))?;
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -2,6 +2,9 @@
 
 pub mod indexed_reader;
 mod indexer;
+mod indexing_writer;
+#[cfg(feature = "mmap")]
+pub mod mmap_indexed_reader;
 pub mod reader;
 pub mod writer;
 
@@ -9,7 +12,12 @@ use std::io::{self, BufRead, Read, Seek, SeekFrom};
 
 use noodles_bgzf as bgzf;
 
-pub use self::{indexed_reader::IndexedReader, indexer::Indexer, reader::Reader, writer::Writer};
+#[cfg(feature = "mmap")]
+pub use self::mmap_indexed_reader::MmapIndexedReader;
+pub use self::{
+    indexed_reader::IndexedReader, indexer::Indexer, indexing_writer::IndexingWriter,
+    reader::Reader, writer::Writer,
+};
 
 /// A buffered FASTA reader.
 pub enum BufReader<R> {
@@ -0,0 +1,280 @@
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf::{self as bgzf, gzi};
+
+use super::writer::{Builder, builder::DEFAULT_LINE_BASE_COUNT};
+use crate::{Record, fai};
+
+/// A bgzipped FASTA writer that builds a FASTA index (FAI) and gzip index (GZI) as records are
+/// written.
+///
+/// Sequence lines are hard wrapped at a fixed number of bases, and the indices are equivalent to
+/// the ones built from the written file by [`crate::fs::index`] and `bgzip --reindex`,
+/// respectively. FAI offsets are positions in the uncompressed stream.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fasta::{self as fasta, fai, record::{Definition, Sequence}};
+///
+/// let mut writer = fasta::io::IndexingWriter::new(Vec::new());
+///
+/// let definition = Definition::new("sq0", None);
+/// let sequence = Sequence::from(b"ACGT".to_vec());
+/// let record = fasta::Record::new(definition, sequence);
+/// writer.write_record(&record)?;
+///
+/// let (index, _) = writer.finish()?;
+/// assert_eq!(index, fai::Index::from(vec![fai::Record::new("sq0", 4, 5, 4, 5)]));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexingWriter<W>
+where
+    W: Write,
+{
+    inner: super::Writer<Inner<W>>,
+    line_base_count: usize,
+    records: Vec<fai::Record>,
+}
+
+impl<W> IndexingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexing FASTA writer with a default compression level.
+    ///
+    /// Sequence lines are hard wrapped at 80 bases.
+    pub fn new(inner: W) -> Self {
+        Self::from(bgzf::io::Writer::new(inner))
+    }
+
+    /// Creates an indexing FASTA writer with the given number of bases per line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta as fasta;
+    /// let writer = fasta::io::IndexingWriter::with_line_base_count(60, Vec::new());
+    /// ```
+    pub fn with_line_base_count(line_base_count: usize, inner: W) -> Self {
+        Self::build(line_base_count, bgzf::io::Writer::new(inner))
+    }
+
+    fn build(line_base_count: usize, inner: bgzf::io::Writer<W>) -> Self {
+        let inner = Inner {
+            inner,
+            position: 0,
+            block_offsets: Vec::new(),
+        };
+
+        Self {
+            inner: Builder::default()
+                .set_line_base_count(line_base_count)
+                .build_from_writer(inner),
+            line_base_count,
+            records: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        &self.inner.get_ref().inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner().inner
+    }
+
+    /// Writes a FASTA record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the number of bases per line is 0.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        if self.line_base_count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid line base count: expected > 0",
+            ));
+        }
+
+        self.inner.write_record(record)?;
+
+        let length = record.sequence().len();
+        let line_bases = length.min(self.line_base_count);
+        let line_count = length.div_ceil(self.line_base_count);
+
+        let end = self.inner.get_ref().position;
+        let offset = end - (length + line_count) as u64;
+
+        self.records.push(fai::Record::new(
+            record.name(),
+            length as u64,
+            offset,
+            line_bases as u64,
+            line_bases as u64 + 1,
+        ));
+
+        Ok(())
+    }
+
+    /// Finishes the output stream and returns the FASTA index and gzip index.
+    pub fn finish(&mut self) -> io::Result<(fai::Index, gzi::Index)> {
+        let inner = self.inner.get_mut();
+        inner.inner.try_finish()?;
+
+        let fai_index = fai::Index::from(mem::take(&mut self.records));
+        let gzi_index = gzi::Index::from(mem::take(&mut inner.block_offsets));
+
+        Ok((fai_index, gzi_index))
+    }
+}
+
+impl<W> From<bgzf::io::Writer<W>> for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: bgzf::io::Writer<W>) -> Self {
+        Self::build(DEFAULT_LINE_BASE_COUNT, inner)
+    }
+}
+
+// A BGZF writer that tracks the uncompressed position and the start of each block.
+struct Inner<W>
+where
+    W: Write,
+{
+    inner: bgzf::io::Writer<W>,
+    position: u64,
+    block_offsets: Vec<(u64, u64)>,
+}
+
+impl<W> Write for Inner<W>
+where
+    W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let (compressed_position, uncompressed_position) = self.inner.virtual_position().into();
+
+        // The first block is implicitly at 0 and is not included in a GZ index.
+        if compressed_position > 0
+            && uncompressed_position == 0
+            && self
+                .block_offsets
+                .last()
+                .is_none_or(|&(c, _)| c != compressed_position)
+        {
+            self.block_offsets
+                .push((compressed_position, self.position));
+        }
+
+        let n = self.inner.write(buf)?;
+        self.position += n as u64;
+
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::record::{Definition, Sequence};
+
+    fn build_record(name: &str, sequence: Vec<u8>) -> Record {
+        Record::new(Definition::new(name, None), Sequence::from(sequence))
+    }
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let records = [
+            build_record("sq0", b"ACGT".to_vec()),
+            build_record("sq1", b"NNNNNNNNNN".to_vec()),
+            build_record("sq2", b"ACGTACGT".to_vec()),
+        ];
+
+        let mut writer = IndexingWriter::with_line_base_count(4, Vec::new());
+
+        for record in &records {
+            writer.write_record(record)?;
+        }
+
+        let (index, _) = writer.finish()?;
+
+        let mut reader = bgzf::io::Reader::new(writer.get_ref().get_ref().as_slice());
+        let mut buf = Vec::new();
+        io::Read::read_to_end(&mut reader, &mut buf)?;
+
+        assert_eq!(buf, b">sq0\nACGT\n>sq1\nNNNN\nNNNN\nNN\n>sq2\nACGT\nACGT\n");
+
+        let mut indexer = crate::io::Indexer::new(&buf[..]);
+        let mut expected = Vec::new();
+
+        while let Some(record) = indexer.index_record()? {
+            expected.push(record);
+        }
+
+        assert_eq!(index, fai::Index::from(expected));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_multiple_blocks() -> Result<(), Box<dyn std::error::Error>> {
+        let sequence: Vec<u8> = b"ACGT".iter().copied().cycle().take(150_000).collect();
+        let records = [
+            build_record("sq0", sequence.clone()),
+            build_record("sq1", sequence),
+        ];
+
+        let mut writer = IndexingWriter::new(Vec::new());
+
+        for record in &records {
+            writer.write_record(record)?;
+        }
+
+        let (index, gzi_index) = writer.finish()?;
+        assert!(gzi_index.as_ref().len() >= 4);
+
+        let data = writer.get_ref().get_ref().clone();
+        let inner = bgzf::io::IndexedReader::new(Cursor::new(data), gzi_index);
+        let mut reader = crate::io::IndexedReader::new(inner, index);
+
+        for record in &records {
+            let region =
+                format!("{}:149991-150000", String::from_utf8_lossy(record.name())).parse()?;
+            let actual = reader.query(&region)?;
+            assert_eq!(
+                actual.sequence().as_ref(),
+                &record.sequence().as_ref()[149_990..]
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_record_with_invalid_line_base_count() {
+        let mut writer = IndexingWriter::with_line_base_count(0, Vec::new());
+        let record = build_record("sq0", b"ACGT".to_vec());
+
+        assert!(matches!(
+            writer.write_record(&record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+    }
+}
@@ -0,0 +1,241 @@
+//! Memory-mapped indexed FASTA reader.
+
+mod sequence_ref;
+
+pub use self::sequence_ref::SequenceRef;
+
+use std::{
+    ffi::OsString,
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
+
+use memmap2::Mmap;
+use noodles_core::{Position, Region};
+
+use crate::fai;
+
+/// A memory-mapped indexed FASTA reader.
+///
+/// Unlike [`super::IndexedReader`], queries do not copy the sequence. They take `&self` and
+/// return a [`SequenceRef`], a view of the region in the mapped file that skips line breaks as it
+/// is read. A reader can therefore be shared across threads, e.g., using an [`std::sync::Arc`],
+/// without locking.
+///
+/// Only uncompressed FASTA files are supported.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta::{fai, io::MmapIndexedReader};
+///
+/// let data = b">sq0\nACGT\nAC\n".to_vec();
+/// let index = fai::Index::from(vec![fai::Record::new("sq0", 6, 5, 4, 5)]);
+/// let reader = MmapIndexedReader::new(data, index);
+///
+/// let sequence = reader.query(&"sq0:3-5".parse()?)?;
+/// assert_eq!(sequence.to_vec(), b"GTA");
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct MmapIndexedReader<B = Mmap> {
+    data: B,
+    index: fai::Index,
+}
+
+impl MmapIndexedReader {
+    /// Memory maps a FASTA file and reads its associated index (`<src>.fai`).
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other process, while the reader
+    /// exists. See [`Mmap::map`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_fasta::io::MmapIndexedReader;
+    /// // SAFETY: `reference.fa` is not modified while it is mapped.
+    /// let reader = unsafe { MmapIndexedReader::from_path("reference.fa")? };
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub unsafe fn from_path<P>(src: P) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let index = fai::fs::read(build_index_src(src))?;
+
+        let file = File::open(src)?;
+        // SAFETY: The caller guarantees that the file is not modified while it is mapped.
+        let data = unsafe { Mmap::map(&file)? };
+
+        Ok(Self::new(data, index))
+    }
+}
+
+impl<B> MmapIndexedReader<B>
+where
+    B: AsRef<[u8]>,
+{
+    /// Creates a memory-mapped indexed FASTA reader from the contents of a FASTA file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::{fai, io::MmapIndexedReader};
+    /// let reader = MmapIndexedReader::new(Vec::new(), fai::Index::default());
+    /// ```
+    pub fn new(data: B, index: fai::Index) -> Self {
+        Self { data, index }
+    }
+
+    /// Returns a reference to the underlying data.
+    pub fn get_ref(&self) -> &B {
+        &self.data
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &fai::Index {
+        &self.index
+    }
+
+    /// Returns a view of the sequence in the given region.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the reference sequence name is not in the index, the
+    /// interval is out of bounds, or the region is past the end of the data.
+    pub fn query(&self, region: &Region) -> io::Result<SequenceRef<'_>> {
+        let record = self
+            .index
+            .as_ref()
+            .iter()
+            .find(|record| record.name() == region.name())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid reference sequence name: {}", region.name()),
+                )
+            })?;
+
+        let length = usize::try_from(record.length())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let interval = region.interval();
+        let start = usize::from(interval.start().unwrap_or(Position::MIN));
+        let end = interval.end().map(usize::from).unwrap_or(length);
+
+        if end > length || start > end + 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid interval: {interval}"),
+            ));
+        }
+
+        let offset = usize::try_from(record.offset())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let src = self
+            .data
+            .as_ref()
+            .get(offset..)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof))?;
+
+        SequenceRef::new(
+            src,
+            start - 1,
+            end + 1 - start,
+            record.line_bases(),
+            record.line_width(),
+        )
+    }
+}
+
+fn build_index_src(src: &Path) -> PathBuf {
+    let mut s = OsString::from(src);
+    s.push(".fai");
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process, sync::Arc, thread};
+
+    use super::*;
+
+    const DATA: &[u8] = b">sq0\nACGT\nACGT\nAC\n>sq1\r\nNNNN\r\nGG\r\n";
+
+    fn build_index() -> fai::Index {
+        fai::Index::from(vec![
+            fai::Record::new("sq0", 10, 5, 4, 5),
+            fai::Record::new("sq1", 6, 24, 4, 6),
+        ])
+    }
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        let reader = MmapIndexedReader::new(DATA, build_index());
+
+        let sequence = reader.query(&"sq0".parse()?)?;
+        assert_eq!(sequence.to_vec(), b"ACGTACGTAC");
+
+        let sequence = reader.query(&"sq0:4-9".parse()?)?;
+        assert_eq!(sequence.to_vec(), b"TACGTA");
+
+        let sequence = reader.query(&"sq1:3".parse()?)?;
+        assert_eq!(sequence.to_vec(), b"NNGG");
+
+        assert!(matches!(
+            reader.query(&"sq0:8-11".parse()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        assert!(matches!(
+            reader.query(&"sq2".parse()?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let reader = MmapIndexedReader::new(&DATA[..15], build_index());
+
+        assert!(matches!(
+            reader.query(&"sq0".parse()?),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_from_multiple_threads() -> Result<(), Box<dyn std::error::Error>> {
+        let src = env::temp_dir().join(format!("noodles-fasta-mmap-{}.fa", process::id()));
+        fs::write(&src, DATA)?;
+        crate::fai::fs::write(build_index_src(&src), &build_index())?;
+
+        // SAFETY: The file is not modified until after the reader is dropped.
+        let reader = Arc::new(unsafe { MmapIndexedReader::from_path(&src)? });
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let reader = Arc::clone(&reader);
+
+                thread::spawn(move || {
+                    let region = "sq0:2-6".parse().unwrap();
+                    reader.query(&region).map(|sequence| sequence.to_vec())
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap()?, b"CGTAC");
+        }
+
+        drop(reader);
+
+        fs::remove_file(build_index_src(&src))?;
+        fs::remove_file(&src)?;
+
+        Ok(())
+    }
+}
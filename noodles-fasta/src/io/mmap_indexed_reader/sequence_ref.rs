@@ -0,0 +1,211 @@
+use std::{borrow::Cow, io};
+
+use crate::record::Sequence;
+
+/// A view of a sequence region in a FASTA file.
+///
+/// The view borrows from the underlying data and skips line breaks as it is read.
+#[derive(Clone, Copy, Debug)]
+pub struct SequenceRef<'a> {
+    src: &'a [u8],
+    start: usize,
+    len: usize,
+    line_bases: usize,
+    line_width: usize,
+}
+
+impl<'a> SequenceRef<'a> {
+    // `src` starts at the first base of the reference sequence, and `start` is 0-based.
+    pub(super) fn new(
+        src: &'a [u8],
+        start: usize,
+        len: usize,
+        line_bases: u64,
+        line_width: u64,
+    ) -> io::Result<Self> {
+        let line_bases = usize::try_from(line_bases)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let line_width = usize::try_from(line_width)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if len > 0 && (line_bases == 0 || line_width < line_bases) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid line bases or line width",
+            ));
+        }
+
+        let sequence = Self {
+            src,
+            start,
+            len,
+            line_bases,
+            line_width,
+        };
+
+        if let Some(i) = len.checked_sub(1) {
+            if sequence.position(i) >= src.len() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+        }
+
+        Ok(sequence)
+    }
+
+    /// Returns the number of bases in the sequence.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the base at the given 0-based index.
+    pub fn get(&self, i: usize) -> Option<u8> {
+        if i < self.len {
+            Some(self.src[self.position(i)])
+        } else {
+            None
+        }
+    }
+
+    /// Returns an iterator over contiguous runs of bases, i.e., each line in the region without
+    /// its line break.
+    pub fn lines(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        let Self {
+            src,
+            start,
+            len,
+            line_bases,
+            line_width,
+        } = *self;
+
+        let mut i = 0;
+
+        std::iter::from_fn(move || {
+            if i >= len {
+                return None;
+            }
+
+            let j = start + i;
+            let line_start = j / line_bases * line_width;
+            let k = j % line_bases;
+            let n = (line_bases - k).min(len - i);
+
+            i += n;
+
+            Some(&src[line_start + k..line_start + k + n])
+        })
+    }
+
+    /// Returns an iterator over the bases in the sequence.
+    pub fn iter(&self) -> impl Iterator<Item = u8> + use<'a> {
+        self.lines().flatten().copied()
+    }
+
+    /// Returns the bases as a slice.
+    ///
+    /// This borrows the underlying data when the region is within a single line. Otherwise, the
+    /// lines are copied into a new buffer.
+    pub fn to_cow(&self) -> Cow<'a, [u8]> {
+        let mut lines = self.lines();
+
+        match (lines.next(), lines.next()) {
+            (None, _) => Cow::Borrowed(&[]),
+            (Some(line), None) => Cow::Borrowed(line),
+            (Some(_), Some(_)) => Cow::Owned(self.to_vec()),
+        }
+    }
+
+    /// Copies the bases into a new buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(self.len);
+
+        for line in self.lines() {
+            buf.extend_from_slice(line);
+        }
+
+        buf
+    }
+
+    fn position(&self, i: usize) -> usize {
+        let j = self.start + i;
+        j / self.line_bases * self.line_width + j % self.line_bases
+    }
+}
+
+impl From<SequenceRef<'_>> for Sequence {
+    fn from(sequence: SequenceRef<'_>) -> Self {
+        Self::from(sequence.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRC: &[u8] = b"ACGT\nACGT\nAC\n";
+
+    #[test]
+    fn test_new() {
+        assert!(SequenceRef::new(SRC, 0, 10, 4, 5).is_ok());
+        assert!(SequenceRef::new(SRC, 0, 0, 0, 0).is_ok());
+
+        assert!(matches!(
+            SequenceRef::new(SRC, 0, 10, 0, 1),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        assert!(matches!(
+            SequenceRef::new(SRC, 0, 12, 4, 5),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let sequence = SequenceRef::new(SRC, 2, 6, 4, 5)?;
+
+        assert_eq!(sequence.get(0), Some(b'G'));
+        assert_eq!(sequence.get(2), Some(b'A'));
+        assert_eq!(sequence.get(5), Some(b'T'));
+        assert_eq!(sequence.get(6), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lines() -> io::Result<()> {
+        let sequence = SequenceRef::new(SRC, 2, 8, 4, 5)?;
+        let actual: Vec<_> = sequence.lines().collect();
+        let expected: [&[u8]; 3] = [b"GT", b"ACGT", b"AC"];
+        assert_eq!(actual, expected);
+
+        let sequence = SequenceRef::new(SRC, 0, 0, 4, 5)?;
+        assert!(sequence.lines().next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_cow() -> io::Result<()> {
+        let sequence = SequenceRef::new(SRC, 5, 3, 4, 5)?;
+        assert!(matches!(sequence.to_cow(), Cow::Borrowed(b"CGT")));
+
+        let sequence = SequenceRef::new(SRC, 3, 2, 4, 5)?;
+        assert!(matches!(sequence.to_cow(), Cow::Owned(buf) if buf == b"TA"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_sequence_ref_for_sequence() -> io::Result<()> {
+        let sequence = SequenceRef::new(SRC, 0, 10, 4, 5)?;
+        let actual = Sequence::from(sequence);
+        assert_eq!(actual.as_ref(), b"ACGTACGTAC");
+        Ok(())
+    }
+}
@@ -48,11 +48,16 @@
 #[cfg(feature = "async")]
 pub mod r#async;
 
+#[cfg(feature = "bed")]
+pub mod composition;
+#[cfg(feature = "sam")]
+pub mod dict;
 pub mod fai;
 pub mod fs;
 pub mod io;
 pub mod record;
 pub mod repository;
 pub mod sequence;
+pub mod two_bit;
 
 pub use self::{record::Record, repository::Repository};
@@ -1,6 +1,7 @@
 //! FASTA record sequence.
 
 pub mod complement;
+pub mod translation;
 
 pub use self::complement::Complement;
 
@@ -9,6 +10,11 @@ use std::ops::Index;
 use bytes::Bytes;
 use noodles_core::{position::SequenceIndex, region::Interval};
 
+use self::{
+    complement::ComplementError,
+    translation::{Frame, GeneticCode},
+};
+
 /// A FASTA record sequence.
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Sequence(Bytes);
@@ -136,6 +142,85 @@ impl Sequence {
     pub fn complement(&self) -> Complement<'_> {
         Complement::new(self.0.iter())
     }
+
+    /// Returns the reverse complement of the sequence.
+    ///
+    /// IUPAC ambiguity codes are complemented, e.g., `R` (A/G) becomes `Y` (C/T), and the case
+    /// of each base is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::Sequence;
+    /// let sequence = Sequence::from(b"ACGtnR".to_vec());
+    /// let actual = sequence.reverse_complement()?;
+    /// let expected = Sequence::from(b"YnaCGT".to_vec());
+    /// assert_eq!(actual, expected);
+    /// # Ok::<_, noodles_fasta::record::sequence::complement::ComplementError>(())
+    /// ```
+    pub fn reverse_complement(&self) -> Result<Self, ComplementError> {
+        self.complement().rev().collect()
+    }
+
+    /// Translates the sequence to amino acids using the given genetic code and reading frame.
+    ///
+    /// Trailing bases that do not make a complete codon are ignored. To translate the start of a
+    /// coding sequence, i.e., with its initiator codon as methionine, use
+    /// [`translation::translate`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::{
+    ///     sequence::translation::{Frame, GeneticCode},
+    ///     Sequence,
+    /// };
+    ///
+    /// let sequence = Sequence::from(b"ATGGCCTGA".to_vec());
+    /// let actual = sequence.translate(GeneticCode::STANDARD, Frame::First);
+    /// assert_eq!(actual, Sequence::from(b"MA*".to_vec()));
+    /// ```
+    pub fn translate(&self, genetic_code: GeneticCode, frame: Frame) -> Self {
+        Self::from(translation::translate(
+            self.as_ref(),
+            genetic_code,
+            frame,
+            false,
+        ))
+    }
+
+    /// Translates the sequence in all six reading frames.
+    ///
+    /// The translations are ordered by the three forward frames followed by the three frames of
+    /// the reverse complement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::{sequence::translation::GeneticCode, Sequence};
+    ///
+    /// let sequence = Sequence::from(b"ATGGCC".to_vec());
+    /// let translations = sequence.six_frame_translation(GeneticCode::STANDARD)?;
+    ///
+    /// assert_eq!(translations[0], Sequence::from(b"MA".to_vec()));
+    /// assert_eq!(translations[3], Sequence::from(b"GH".to_vec()));
+    /// # Ok::<_, noodles_fasta::record::sequence::complement::ComplementError>(())
+    /// ```
+    pub fn six_frame_translation(
+        &self,
+        genetic_code: GeneticCode,
+    ) -> Result<[Self; 6], ComplementError> {
+        let reverse_complement = self.reverse_complement()?;
+
+        Ok([
+            self.translate(genetic_code, Frame::First),
+            self.translate(genetic_code, Frame::Second),
+            self.translate(genetic_code, Frame::Third),
+            reverse_complement.translate(genetic_code, Frame::First),
+            reverse_complement.translate(genetic_code, Frame::Second),
+            reverse_complement.translate(genetic_code, Frame::Third),
+        ])
+    }
 }
 
 impl AsRef<[u8]> for Sequence {
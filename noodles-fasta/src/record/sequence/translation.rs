@@ -0,0 +1,109 @@
+//! FASTA record sequence translation.
+
+mod genetic_code;
+
+pub use self::genetic_code::{GeneticCode, TryFromIntError};
+
+/// A reading frame.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Frame {
+    /// Translation starts at the first base.
+    #[default]
+    First,
+    /// Translation starts at the second base.
+    Second,
+    /// Translation starts at the third base.
+    Third,
+}
+
+impl Frame {
+    fn offset(self) -> usize {
+        match self {
+            Self::First => 0,
+            Self::Second => 1,
+            Self::Third => 2,
+        }
+    }
+}
+
+/// Translates the bases of a nucleotide sequence to amino acids.
+///
+/// Translation starts at the given frame, and trailing bases that do not make a complete codon
+/// are ignored. When `is_initiator` is set, the first codon is translated as methionine (`M`) if
+/// it is a start codon of the genetic code, which is the case for the start of a coding sequence
+/// (CDS).
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta::record::sequence::translation::{self, Frame, GeneticCode};
+///
+/// let genetic_code = GeneticCode::STANDARD;
+///
+/// let actual = translation::translate(b"TTGGCCTAA", genetic_code, Frame::First, false);
+/// assert_eq!(actual, b"LA*");
+///
+/// let actual = translation::translate(b"TTGGCCTAA", genetic_code, Frame::First, true);
+/// assert_eq!(actual, b"MA*");
+///
+/// let actual = translation::translate(b"TTGGCCTAA", genetic_code, Frame::Second, false);
+/// assert_eq!(actual, b"WP");
+/// ```
+pub fn translate(
+    bases: &[u8],
+    genetic_code: GeneticCode,
+    frame: Frame,
+    is_initiator: bool,
+) -> Vec<u8> {
+    let bases = bases.get(frame.offset()..).unwrap_or_default();
+
+    bases
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let codon = [chunk[0], chunk[1], chunk[2]];
+
+            if i == 0 && is_initiator && genetic_code.is_start_codon(&codon) {
+                b'M'
+            } else {
+                genetic_code.translate_codon(&codon)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate() {
+        let genetic_code = GeneticCode::STANDARD;
+
+        assert_eq!(
+            translate(b"atgGCNtgA", genetic_code, Frame::First, false),
+            b"MA*"
+        );
+
+        assert_eq!(
+            translate(b"AATGGC", genetic_code, Frame::Second, false),
+            b"M"
+        );
+
+        assert_eq!(
+            translate(b"AATGGCC", genetic_code, Frame::Third, false),
+            b"W"
+        );
+        assert!(translate(b"AC", genetic_code, Frame::Third, false).is_empty());
+
+        assert_eq!(
+            translate(b"GTGAAA", GeneticCode::BACTERIAL, Frame::First, true),
+            b"MK"
+        );
+
+        assert_eq!(
+            translate(b"GTGAAA", GeneticCode::STANDARD, Frame::First, true),
+            b"VK"
+        );
+    }
+}
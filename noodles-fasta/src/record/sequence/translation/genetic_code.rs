@@ -0,0 +1,349 @@
+use std::{error, fmt};
+
+/// An NCBI genetic code (translation table).
+///
+/// See <https://www.ncbi.nlm.nih.gov/Taxonomy/Utils/wprintgc.cgi>.
+//
+// Codons are ordered by their bases using `TCAG`, i.e., TTT, TTC, TTA, TTG, TCT, ..., GGG.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GeneticCode {
+    id: u8,
+    name: &'static str,
+    amino_acids: &'static [u8; 64],
+    starts: &'static [u8; 64],
+}
+
+macro_rules! genetic_codes {
+    ($(($constant:ident, $id:expr, $name:expr, $amino_acids:expr, $starts:expr)),+ $(,)?) => {
+        impl GeneticCode {
+            $(
+                #[doc = concat!("Translation table ", stringify!($id), ": ", $name, ".")]
+                pub const $constant: Self = Self {
+                    id: $id,
+                    name: $name,
+                    amino_acids: $amino_acids,
+                    starts: $starts,
+                };
+            )+
+
+            const ALL: &[Self] = &[$(Self::$constant),+];
+        }
+    };
+}
+
+genetic_codes! {
+    (
+        STANDARD, 1, "Standard",
+        b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**--*----M---------------M----------------------------"
+    ),
+    (
+        VERTEBRATE_MITOCHONDRIAL, 2, "Vertebrate Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+        b"----------**--------------------MMMM----------**---M------------"
+    ),
+    (
+        YEAST_MITOCHONDRIAL, 3, "Yeast Mitochondrial",
+        b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**----------------------MM---------------M------------"
+    ),
+    (
+        MOLD_MITOCHONDRIAL, 4,
+        "Mold, Protozoan, and Coelenterate Mitochondrial and Mycoplasma/Spiroplasma",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--MM------**-------M------------MMMM---------------M------------"
+    ),
+    (
+        INVERTEBRATE_MITOCHONDRIAL, 5, "Invertebrate Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+        b"---M------**--------------------MMMM---------------M------------"
+    ),
+    (
+        CILIATE_NUCLEAR, 6, "Ciliate, Dasycladacean and Hexamita Nuclear",
+        b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--------------*--------------------M----------------------------"
+    ),
+    (
+        ECHINODERM_MITOCHONDRIAL, 9, "Echinoderm and Flatworm Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M---------------M------------"
+    ),
+    (
+        EUPLOTID_NUCLEAR, 10, "Euplotid Nuclear",
+        b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M----------------------------"
+    ),
+    (
+        BACTERIAL, 11, "Bacterial, Archaeal and Plant Plastid",
+        b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**--*----M------------MMMM---------------M------------"
+    ),
+    (
+        ALTERNATIVE_YEAST_NUCLEAR, 12, "Alternative Yeast Nuclear",
+        b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**--*----M---------------M----------------------------"
+    ),
+    (
+        ASCIDIAN_MITOCHONDRIAL, 13, "Ascidian Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+        b"---M------**----------------------MM---------------M------------"
+    ),
+    (
+        ALTERNATIVE_FLATWORM_MITOCHONDRIAL, 14, "Alternative Flatworm Mitochondrial",
+        b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"-----------*-----------------------M----------------------------"
+    ),
+    (
+        CHLOROPHYCEAN_MITOCHONDRIAL, 16, "Chlorophycean Mitochondrial",
+        b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------*---*--------------------M----------------------------"
+    ),
+    (
+        TREMATODE_MITOCHONDRIAL, 21, "Trematode Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+        b"----------**-----------------------M---------------M------------"
+    ),
+    (
+        SCENEDESMUS_OBLIQUUS_MITOCHONDRIAL, 22, "Scenedesmus obliquus Mitochondrial",
+        b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"------*---*---*--------------------M----------------------------"
+    ),
+    (
+        THRAUSTOCHYTRIUM_MITOCHONDRIAL, 23, "Thraustochytrium Mitochondrial",
+        b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"--*-------**--*-----------------M--M---------------M------------"
+    ),
+    (
+        RHABDOPLEURIDAE_MITOCHONDRIAL, 24, "Rhabdopleuridae Mitochondrial",
+        b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        b"---M------**-------M---------------M---------------M------------"
+    ),
+    (
+        CANDIDATE_DIVISION_SR1, 25, "Candidate Division SR1 and Gracilibacteria",
+        b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"---M------**-----------------------M---------------M------------"
+    ),
+    (
+        PACHYSOLEN_TANNOPHILUS_NUCLEAR, 26, "Pachysolen tannophilus Nuclear",
+        b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+        b"----------**--*----M---------------M----------------------------"
+    ),
+    (
+        CEPHALODISCIDAE_MITOCHONDRIAL, 33, "Cephalodiscidae Mitochondrial",
+        b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        b"---M-------*-------M---------------M---------------M------------"
+    ),
+}
+
+impl GeneticCode {
+    /// Returns the NCBI translation table ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::sequence::translation::GeneticCode;
+    /// assert_eq!(GeneticCode::STANDARD.id(), 1);
+    /// ```
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Returns the name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::sequence::translation::GeneticCode;
+    /// assert_eq!(GeneticCode::STANDARD.name(), "Standard");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Translates a codon to an amino acid.
+    ///
+    /// Bases are case-insensitive, and `U` is treated as `T`. A codon with IUPAC ambiguity codes
+    /// translates to an amino acid if all of its possible codons do, e.g., `CTN` is `L`;
+    /// otherwise, it is `X`. Stop codons are `*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::sequence::translation::GeneticCode;
+    /// let genetic_code = GeneticCode::STANDARD;
+    /// assert_eq!(genetic_code.translate_codon(b"ATG"), b'M');
+    /// assert_eq!(genetic_code.translate_codon(b"tga"), b'*');
+    /// assert_eq!(genetic_code.translate_codon(b"GGN"), b'G');
+    /// assert_eq!(genetic_code.translate_codon(b"NNN"), b'X');
+    /// ```
+    pub fn translate_codon(&self, codon: &[u8; 3]) -> u8 {
+        self.resolve(codon, self.amino_acids).unwrap_or(b'X')
+    }
+
+    /// Returns whether the codon is a start codon.
+    ///
+    /// This includes alternative start codons, e.g., `TTG` and `CTG` in the standard code. A
+    /// codon with IUPAC ambiguity codes is a start codon if all of its possible codons are.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::record::sequence::translation::GeneticCode;
+    /// let genetic_code = GeneticCode::STANDARD;
+    /// assert!(genetic_code.is_start_codon(b"ATG"));
+    /// assert!(genetic_code.is_start_codon(b"TTG"));
+    /// assert!(!genetic_code.is_start_codon(b"TTT"));
+    /// ```
+    pub fn is_start_codon(&self, codon: &[u8; 3]) -> bool {
+        self.resolve(codon, self.starts) == Some(b'M')
+    }
+
+    // Returns the value shared by all possible codons, if any.
+    fn resolve(&self, codon: &[u8; 3], table: &[u8; 64]) -> Option<u8> {
+        let [a, b, c] = codon.map(bases);
+        let (a, b, c) = (a?, b?, c?);
+
+        let mut value = None;
+
+        for &i in a {
+            for &j in b {
+                for &k in c {
+                    let v = table[16 * i + 4 * j + k];
+
+                    match value {
+                        None => value = Some(v),
+                        Some(w) if w != v => return None,
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        value
+    }
+}
+
+impl Default for GeneticCode {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// An error returned when a genetic code fails to convert.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TryFromIntError(u8);
+
+impl error::Error for TryFromIntError {}
+
+impl fmt::Display for TryFromIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid genetic code ID: {}", self.0)
+    }
+}
+
+impl TryFrom<u8> for GeneticCode {
+    type Error = TryFromIntError;
+
+    fn try_from(n: u8) -> Result<Self, Self::Error> {
+        Self::ALL
+            .iter()
+            .find(|genetic_code| genetic_code.id == n)
+            .copied()
+            .ok_or(TryFromIntError(n))
+    }
+}
+
+// Returns the indices (in `TCAG` order) of the bases an IUPAC nucleotide code represents.
+fn bases(b: u8) -> Option<&'static [usize]> {
+    const T: usize = 0;
+    const C: usize = 1;
+    const A: usize = 2;
+    const G: usize = 3;
+
+    match b.to_ascii_uppercase() {
+        b'T' | b'U' => Some(&[T]),
+        b'C' => Some(&[C]),
+        b'A' => Some(&[A]),
+        b'G' => Some(&[G]),
+        b'W' => Some(&[A, T]),
+        b'S' => Some(&[C, G]),
+        b'M' => Some(&[A, C]),
+        b'K' => Some(&[G, T]),
+        b'R' => Some(&[A, G]),
+        b'Y' => Some(&[C, T]),
+        b'B' => Some(&[C, G, T]),
+        b'D' => Some(&[A, G, T]),
+        b'H' => Some(&[A, C, T]),
+        b'V' => Some(&[A, C, G]),
+        b'N' => Some(&[A, C, G, T]),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables() {
+        for genetic_code in GeneticCode::ALL {
+            assert!(
+                genetic_code
+                    .starts
+                    .iter()
+                    .all(|&b| b == b'-' || b == b'*' || b == b'M'),
+                "{}",
+                genetic_code.id
+            );
+
+            for (i, (&aa, &start)) in genetic_code
+                .amino_acids
+                .iter()
+                .zip(genetic_code.starts)
+                .enumerate()
+            {
+                // Stops in the start table are stops in the amino acid table.
+                if start == b'*' {
+                    assert_eq!(aa, b'*', "{}: {i}", genetic_code.id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_codon() {
+        let genetic_code = GeneticCode::STANDARD;
+
+        assert_eq!(genetic_code.translate_codon(b"TTT"), b'F');
+        assert_eq!(genetic_code.translate_codon(b"GGG"), b'G');
+        assert_eq!(genetic_code.translate_codon(b"AUG"), b'M');
+        assert_eq!(genetic_code.translate_codon(b"TAA"), b'*');
+        assert_eq!(genetic_code.translate_codon(b"TAR"), b'*');
+        assert_eq!(genetic_code.translate_codon(b"TRA"), b'*');
+        assert_eq!(genetic_code.translate_codon(b"TNA"), b'X');
+        assert_eq!(genetic_code.translate_codon(b"AC-"), b'X');
+
+        let genetic_code = GeneticCode::VERTEBRATE_MITOCHONDRIAL;
+        assert_eq!(genetic_code.translate_codon(b"TGA"), b'W');
+        assert_eq!(genetic_code.translate_codon(b"AGA"), b'*');
+        assert_eq!(genetic_code.translate_codon(b"ATA"), b'M');
+    }
+
+    #[test]
+    fn test_is_start_codon() {
+        let genetic_code = GeneticCode::STANDARD;
+        assert!(genetic_code.is_start_codon(b"CTG"));
+        assert!(!genetic_code.is_start_codon(b"ATA"));
+
+        let genetic_code = GeneticCode::BACTERIAL;
+        assert!(genetic_code.is_start_codon(b"GTG"));
+        assert!(genetic_code.is_start_codon(b"ATH"));
+    }
+
+    #[test]
+    fn test_try_from_u8_for_genetic_code() {
+        assert_eq!(GeneticCode::try_from(1), Ok(GeneticCode::STANDARD));
+        assert_eq!(GeneticCode::try_from(11), Ok(GeneticCode::BACTERIAL));
+        assert_eq!(GeneticCode::try_from(7), Err(TryFromIntError(7)));
+    }
+}
@@ -2,6 +2,7 @@
 
 mod adapter;
 pub mod adapters;
+pub mod ref_cache;
 
 pub use self::adapter::Adapter;
 
@@ -0,0 +1,70 @@
+use std::io::{self, Read, Seek};
+
+use noodles_core::Region;
+
+use crate::{Record, repository::Adapter, two_bit};
+
+/// A 2bit reader adapter.
+pub struct TwoBit<R> {
+    reader: two_bit::Reader<R>,
+}
+
+impl<R> TwoBit<R>
+where
+    R: Read + Seek,
+{
+    /// Creates a 2bit reader adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    /// use noodles_fasta::{repository::adapters::TwoBit, two_bit};
+    /// let reader = File::open("reference.2bit").and_then(two_bit::Reader::new)?;
+    /// let adapter = TwoBit::new(reader);
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn new(reader: two_bit::Reader<R>) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R> Adapter for TwoBit<R>
+where
+    R: Read + Seek + Send + Sync,
+{
+    fn get(&mut self, name: &[u8]) -> Option<io::Result<Record>> {
+        self.reader.index().query(name).ok()?;
+
+        let region = Region::new(name, ..);
+        Some(self.reader.query(&region))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::{Repository, two_bit::reader::build_two_bit};
+
+    #[test]
+    fn test_get() -> io::Result<()> {
+        let data = build_two_bit(0, &[("sq0", b"ACGTnnAC"), ("sq1", b"ttgg")]);
+        let reader = two_bit::Reader::new(Cursor::new(data))?;
+        let repository = Repository::new(TwoBit::new(reader));
+
+        let sequence = repository.get(b"sq0").transpose()?;
+        assert_eq!(
+            sequence.as_ref().map(|s| s.as_ref()),
+            Some(&b"ACGTnnAC"[..])
+        );
+
+        let sequence = repository.get(b"sq1").transpose()?;
+        assert_eq!(sequence.as_ref().map(|s| s.as_ref()), Some(&b"ttgg"[..]));
+
+        assert!(repository.get(b"sq2").is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,176 @@
+//! htslib-compatible `REF_PATH`/`REF_CACHE` layout.
+//!
+//! Sequences are stored by ID, e.g., an MD5 checksum, at paths given by a directory template. Each
+//! file holds a raw sequence, i.e., without a FASTA definition or line breaks.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Expands a `REF_PATH` or `REF_CACHE` template using the given sequence ID.
+///
+/// `%Ns` is replaced by the next `N` characters of the ID, and `%s`, by the remaining characters.
+/// If the template does not consume the entire ID, the remaining characters are appended as the
+/// final path component.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use noodles_fasta::repository::ref_cache;
+///
+/// assert_eq!(
+///     ref_cache::expand_path("/tmp/hts-ref/%2s/%2s/%s", "d7eba311421bbc9d3ada44709dd61534"),
+///     Path::new("/tmp/hts-ref/d7/eb/a311421bbc9d3ada44709dd61534"),
+/// );
+/// ```
+pub fn expand_path(template: &str, id: &str) -> PathBuf {
+    let mut dst = String::new();
+    let mut rest = id;
+
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            dst.push(c);
+            continue;
+        }
+
+        let mut digits = String::new();
+
+        while let Some(d) = chars.next_if(char::is_ascii_digit) {
+            digits.push(d);
+        }
+
+        if chars.next_if_eq(&'s').is_some() {
+            let len = if digits.is_empty() {
+                rest.len()
+            } else {
+                digits.parse().unwrap_or(usize::MAX).min(rest.len())
+            };
+
+            let (head, tail) = rest.split_at(len);
+            dst.push_str(head);
+            rest = tail;
+        } else {
+            dst.push(c);
+            dst.push_str(&digits);
+        }
+    }
+
+    if !rest.is_empty() {
+        if !dst.ends_with('/') {
+            dst.push('/');
+        }
+
+        dst.push_str(rest);
+    }
+
+    PathBuf::from(dst)
+}
+
+/// Reads a stored sequence.
+///
+/// This returns `None` if the file does not exist.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fasta::repository::ref_cache;
+/// let sequence = ref_cache::read("/tmp/hts-ref/d7/eb/a311421bbc9d3ada44709dd61534")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn read<P>(src: P) -> io::Result<Option<Vec<u8>>>
+where
+    P: AsRef<Path>,
+{
+    match fs::read(src) {
+        Ok(buf) => Ok(Some(buf)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes a sequence.
+///
+/// Missing parent directories are created. The sequence is first written to a temporary file in
+/// the same directory and then renamed, so concurrent readers never observe a partially written
+/// entry.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_fasta::repository::ref_cache;
+/// ref_cache::write("/tmp/hts-ref/f1/f8/f4bf413b16ad135722aa4591043e", b"ACGT")?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub fn write<P>(dst: P, sequence: &[u8]) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let dst = dst.as_ref();
+
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut tmp_dst = dst.as_os_str().to_owned();
+    tmp_dst.push(format!(".tmp.{}", process::id()));
+
+    fs::write(&tmp_dst, sequence)?;
+    fs::rename(&tmp_dst, dst)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    const ID: &str = "d7eba311421bbc9d3ada44709dd61534";
+
+    #[test]
+    fn test_expand_path() {
+        assert_eq!(
+            expand_path("/tmp/hts-ref/%2s/%2s/%s", ID),
+            PathBuf::from("/tmp/hts-ref/d7/eb/a311421bbc9d3ada44709dd61534")
+        );
+
+        assert_eq!(
+            expand_path("/tmp/hts-ref/%s", ID),
+            PathBuf::from(format!("/tmp/hts-ref/{ID}"))
+        );
+
+        assert_eq!(
+            expand_path("/tmp/hts-ref/%2s", ID),
+            PathBuf::from("/tmp/hts-ref/d7/eba311421bbc9d3ada44709dd61534")
+        );
+
+        assert_eq!(
+            expand_path("/tmp/hts-ref", ID),
+            PathBuf::from(format!("/tmp/hts-ref/{ID}"))
+        );
+
+        assert_eq!(
+            expand_path("/tmp/%x/%64s", ID),
+            PathBuf::from(format!("/tmp/%x/{ID}"))
+        );
+    }
+
+    #[test]
+    fn test_read_and_write() -> io::Result<()> {
+        let root = env::temp_dir().join(format!("noodles-fasta-ref-cache-{}", process::id()));
+        let path = expand_path(&format!("{}/%2s/%2s/%s", root.display()), ID);
+
+        assert!(read(&path)?.is_none());
+
+        write(&path, b"ACGT")?;
+        assert_eq!(read(&path)?, Some(b"ACGT".to_vec()));
+
+        fs::remove_dir_all(&root)?;
+
+        Ok(())
+    }
+}
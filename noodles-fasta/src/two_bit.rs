@@ -0,0 +1,10 @@
+//! UCSC 2bit format.
+//!
+//! 2bit is a binary format that packs nucleotide sequences as 2 bits per base (`T`, `C`, `A`,
+//! `G`). Runs of unknown bases (`N`) and soft-masked (lowercase) regions are stored separately as
+//! blocks. An index of sequence names to file offsets allows random access.
+
+pub mod index;
+pub(crate) mod reader;
+
+pub use self::{index::Index, reader::Reader};
@@ -0,0 +1,50 @@
+//! 2bit index and record.
+
+mod record;
+
+use std::io;
+
+pub use self::record::Record;
+
+/// A 2bit index.
+///
+/// This is the list of sequence names and their record offsets in the file.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Index(Vec<Record>);
+
+impl Index {
+    /// Returns the record offset of the sequence with the given name.
+    pub fn query(&self, name: &[u8]) -> io::Result<u64> {
+        self.get(name).map(|record| record.offset()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid reference sequence name: {}",
+                    String::from_utf8_lossy(name)
+                ),
+            )
+        })
+    }
+
+    pub(crate) fn get(&self, name: &[u8]) -> Option<&Record> {
+        self.0.iter().find(|record| record.name() == name)
+    }
+}
+
+impl AsRef<[Record]> for Index {
+    fn as_ref(&self) -> &[Record] {
+        &self.0
+    }
+}
+
+impl From<Vec<Record>> for Index {
+    fn from(records: Vec<Record>) -> Self {
+        Self(records)
+    }
+}
+
+impl From<Index> for Vec<Record> {
+    fn from(index: Index) -> Self {
+        index.0
+    }
+}
@@ -0,0 +1,54 @@
+use bstr::{BStr, BString};
+
+/// A 2bit index record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Record {
+    name: BString,
+    offset: u64,
+}
+
+impl Record {
+    /// Creates a 2bit index record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::two_bit::index::Record;
+    /// let record = Record::new("sq0", 34);
+    /// ```
+    pub fn new<N>(name: N, offset: u64) -> Self
+    where
+        N: Into<BString>,
+    {
+        Self {
+            name: name.into(),
+            offset,
+        }
+    }
+
+    /// Returns the sequence name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::two_bit::index::Record;
+    /// let record = Record::new("sq0", 34);
+    /// assert_eq!(record.name(), "sq0");
+    /// ```
+    pub fn name(&self) -> &BStr {
+        self.name.as_ref()
+    }
+
+    /// Returns the offset of the sequence record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fasta::two_bit::index::Record;
+    /// let record = Record::new("sq0", 34);
+    /// assert_eq!(record.offset(), 34);
+    /// ```
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
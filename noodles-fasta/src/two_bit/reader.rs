@@ -0,0 +1,285 @@
+mod header;
+mod sequence;
+
+use std::io::{self, Read, Seek, SeekFrom};
+
+use noodles_core::{Position, Region};
+
+use self::{header::read_header, sequence::read_sequence};
+use super::Index;
+use crate::{
+    Record,
+    record::{Definition, Sequence},
+};
+
+/// A 2bit reader.
+///
+/// The header and index are read when the reader is created. Sequences are then randomly
+/// accessed using [`Self::query`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use noodles_core::Region;
+/// use noodles_fasta::two_bit;
+///
+/// let mut reader = File::open("reference.2bit").and_then(two_bit::Reader::new)?;
+///
+/// let region = "sq0:8-13".parse()?;
+/// let record = reader.query(&region)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Reader<R> {
+    inner: R,
+    byte_order: ByteOrder,
+    index: Index,
+}
+
+impl<R> Reader<R> {
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &Index {
+        &self.index
+    }
+}
+
+impl<R> Reader<R>
+where
+    R: Read + Seek,
+{
+    /// Creates a 2bit reader.
+    ///
+    /// This reads the header and index from the start of the stream.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        inner.seek(SeekFrom::Start(0))?;
+        let (byte_order, index) = read_header(&mut inner)?;
+
+        Ok(Self {
+            inner,
+            byte_order,
+            index,
+        })
+    }
+
+    /// Returns the length of the sequence with the given name.
+    pub fn sequence_length(&mut self, name: &[u8]) -> io::Result<usize> {
+        let offset = self.index.query(name)?;
+        self.inner.seek(SeekFrom::Start(offset))?;
+        sequence::read_usize(&mut self.inner, self.byte_order)
+    }
+
+    /// Returns a record of the given region.
+    ///
+    /// Unknown bases are `N`, and soft-masked bases are lowercase.
+    pub fn query(&mut self, region: &Region) -> io::Result<Record> {
+        let offset = self.index.query(region.name())?;
+        self.inner.seek(SeekFrom::Start(offset))?;
+
+        let interval = region.interval();
+        let start = interval.start().unwrap_or(Position::MIN);
+        let end = interval.end();
+
+        let sequence = read_sequence(&mut self.inner, self.byte_order, start, end)?;
+
+        let definition = Definition::new(region.to_string(), None);
+        Ok(Record::new(definition, Sequence::from(sequence)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ByteOrder {
+    LittleEndian,
+    BigEndian,
+}
+
+impl ByteOrder {
+    fn read_u32<R>(self, reader: &mut R) -> io::Result<u32>
+    where
+        R: Read,
+    {
+        let mut buf = [0; 4];
+        reader.read_exact(&mut buf)?;
+
+        Ok(match self {
+            Self::LittleEndian => u32::from_le_bytes(buf),
+            Self::BigEndian => u32::from_be_bytes(buf),
+        })
+    }
+
+    fn read_u64<R>(self, reader: &mut R) -> io::Result<u64>
+    where
+        R: Read,
+    {
+        let mut buf = [0; 8];
+        reader.read_exact(&mut buf)?;
+
+        Ok(match self {
+            Self::LittleEndian => u64::from_le_bytes(buf),
+            Self::BigEndian => u64::from_be_bytes(buf),
+        })
+    }
+}
+
+// Builds a little-endian 2bit file from the given sequences.
+//
+// `N`/`n` runs are encoded as N blocks and lowercase runs as mask blocks.
+#[cfg(test)]
+pub(crate) fn build_two_bit(version: u32, records: &[(&str, &[u8])]) -> Vec<u8> {
+    fn push_u32(dst: &mut Vec<u8>, n: usize) {
+        dst.extend_from_slice(&(n as u32).to_le_bytes());
+    }
+
+    fn blocks<F>(sequence: &[u8], f: F) -> Vec<(usize, usize)>
+    where
+        F: Fn(u8) -> bool,
+    {
+        let mut blocks: Vec<(usize, usize)> = Vec::new();
+
+        for (i, &b) in sequence.iter().enumerate() {
+            if f(b) {
+                match blocks.last_mut() {
+                    Some((start, len)) if *start + *len == i => *len += 1,
+                    _ => blocks.push((i, 1)),
+                }
+            }
+        }
+
+        blocks
+    }
+
+    fn push_blocks(dst: &mut Vec<u8>, blocks: &[(usize, usize)]) {
+        push_u32(dst, blocks.len());
+
+        for &(start, _) in blocks {
+            push_u32(dst, start);
+        }
+
+        for &(_, len) in blocks {
+            push_u32(dst, len);
+        }
+    }
+
+    let mut dst = Vec::new();
+
+    dst.extend_from_slice(&0x1a412743u32.to_le_bytes());
+    push_u32(&mut dst, version as usize);
+    push_u32(&mut dst, records.len());
+    push_u32(&mut dst, 0);
+
+    let offset_size = if version == 0 { 4 } else { 8 };
+    let index_len: usize = records
+        .iter()
+        .map(|(name, _)| 1 + name.len() + offset_size)
+        .sum();
+
+    let mut bodies = Vec::new();
+    let mut offset = dst.len() + index_len;
+
+    for (name, sequence) in records {
+        dst.push(name.len() as u8);
+        dst.extend_from_slice(name.as_bytes());
+
+        if version == 0 {
+            push_u32(&mut dst, offset);
+        } else {
+            dst.extend_from_slice(&(offset as u64).to_le_bytes());
+        }
+
+        let mut body = Vec::new();
+        push_u32(&mut body, sequence.len());
+        push_blocks(
+            &mut body,
+            &blocks(sequence, |b| b.eq_ignore_ascii_case(&b'N')),
+        );
+        push_blocks(&mut body, &blocks(sequence, |b| b.is_ascii_lowercase()));
+        push_u32(&mut body, 0);
+
+        for chunk in sequence.chunks(4) {
+            let mut b = 0;
+
+            for (i, base) in chunk.iter().enumerate() {
+                let code = match base.to_ascii_uppercase() {
+                    b'C' => 1,
+                    b'A' => 2,
+                    b'G' => 3,
+                    _ => 0,
+                };
+
+                b |= code << (6 - 2 * i);
+            }
+
+            body.push(b);
+        }
+
+        offset += body.len();
+        bodies.push(body);
+    }
+
+    for body in bodies {
+        dst.extend(body);
+    }
+
+    dst
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const SQ0: &[u8] = b"ACGTNNNNacgtTTGCAn";
+    const SQ1: &[u8] = b"GATTACA";
+
+    #[test]
+    fn test_query() -> Result<(), Box<dyn std::error::Error>> {
+        for version in [0, 1] {
+            let data = build_two_bit(version, &[("sq0", SQ0), ("sq1", SQ1)]);
+            let mut reader = Reader::new(Cursor::new(data))?;
+
+            let record = reader.query(&"sq0".parse()?)?;
+            assert_eq!(record.name(), b"sq0");
+            assert_eq!(record.sequence().as_ref(), SQ0);
+
+            let record = reader.query(&"sq0:3-10".parse()?)?;
+            assert_eq!(record.name(), b"sq0:3-10");
+            assert_eq!(record.sequence().as_ref(), b"GTNNNNac");
+
+            let record = reader.query(&"sq0:17-18".parse()?)?;
+            assert_eq!(record.sequence().as_ref(), b"An");
+
+            let record = reader.query(&"sq1:2".parse()?)?;
+            assert_eq!(record.sequence().as_ref(), b"ATTACA");
+
+            assert_eq!(reader.sequence_length(b"sq1")?, SQ1.len());
+
+            assert!(matches!(
+                reader.query(&"sq1:5-8".parse()?),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+
+            assert!(matches!(
+                reader.query(&"sq2".parse()?),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+        }
+
+        Ok(())
+    }
+}
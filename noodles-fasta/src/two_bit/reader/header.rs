@@ -0,0 +1,127 @@
+use std::io::{self, Read};
+
+use bstr::BString;
+
+use super::ByteOrder;
+use crate::two_bit::{Index, index::Record};
+
+const SIGNATURE: u32 = 0x1a412743;
+
+pub(super) fn read_header<R>(reader: &mut R) -> io::Result<(ByteOrder, Index)>
+where
+    R: Read,
+{
+    let byte_order = read_signature(reader)?;
+
+    let version = byte_order.read_u32(reader)?;
+
+    let is_64_bit = match version {
+        0 => false,
+        1 => true,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported version: {version}"),
+            ));
+        }
+    };
+
+    let sequence_count = byte_order.read_u32(reader)?;
+    let _reserved = byte_order.read_u32(reader)?;
+
+    let mut records = Vec::new();
+
+    for _ in 0..sequence_count {
+        let record = read_index_record(reader, byte_order, is_64_bit)?;
+        records.push(record);
+    }
+
+    Ok((byte_order, Index::from(records)))
+}
+
+fn read_signature<R>(reader: &mut R) -> io::Result<ByteOrder>
+where
+    R: Read,
+{
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf)?;
+
+    if u32::from_le_bytes(buf) == SIGNATURE {
+        Ok(ByteOrder::LittleEndian)
+    } else if u32::from_be_bytes(buf) == SIGNATURE {
+        Ok(ByteOrder::BigEndian)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid 2bit signature",
+        ))
+    }
+}
+
+fn read_index_record<R>(
+    reader: &mut R,
+    byte_order: ByteOrder,
+    is_64_bit: bool,
+) -> io::Result<Record>
+where
+    R: Read,
+{
+    let mut buf = [0; 1];
+    reader.read_exact(&mut buf)?;
+    let name_len = usize::from(buf[0]);
+
+    let mut name = vec![0; name_len];
+    reader.read_exact(&mut name)?;
+
+    let offset = if is_64_bit {
+        byte_order.read_u64(reader)?
+    } else {
+        byte_order.read_u32(reader).map(u64::from)?
+    };
+
+    Ok(Record::new(BString::from(name), offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_header() -> io::Result<()> {
+        let src = [
+            0x1a, 0x41, 0x27, 0x43, // signature (big-endian)
+            0x00, 0x00, 0x00, 0x00, // version = 0
+            0x00, 0x00, 0x00, 0x01, // sequence count = 1
+            0x00, 0x00, 0x00, 0x00, // reserved
+            0x03, b's', b'q', b'0', // name = "sq0"
+            0x00, 0x00, 0x00, 0x18, // offset = 24
+        ];
+
+        let (byte_order, index) = read_header(&mut &src[..])?;
+
+        assert_eq!(byte_order, ByteOrder::BigEndian);
+        assert_eq!(index, Index::from(vec![Record::new("sq0", 24)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_header_with_invalid_input() {
+        let src = [0x00, 0x00, 0x00, 0x00];
+
+        assert!(matches!(
+            read_header(&mut &src[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        let src = [
+            0x43, 0x27, 0x41, 0x1a, // signature (little-endian)
+            0x02, 0x00, 0x00, 0x00, // version = 2
+        ];
+
+        assert!(matches!(
+            read_header(&mut &src[..]),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    io::{self, Read, Seek, SeekFrom},
+    ops::Range,
+};
+
+use noodles_core::Position;
+
+use super::ByteOrder;
+
+const BASES: [u8; 4] = *b"TCAG";
+
+// Reads the sequence record at the current stream position and decodes the bases in the interval
+// [`start`, `end`].
+//
+// When `end` is `None`, the interval ends at the end of the sequence.
+pub(super) fn read_sequence<R>(
+    reader: &mut R,
+    byte_order: ByteOrder,
+    start: Position,
+    end: Option<Position>,
+) -> io::Result<Vec<u8>>
+where
+    R: Read + Seek,
+{
+    let len = read_usize(reader, byte_order)?;
+    let n_blocks = read_blocks(reader, byte_order)?;
+    let mask_blocks = read_blocks(reader, byte_order)?;
+    let _reserved = byte_order.read_u32(reader)?;
+
+    let start = usize::from(start) - 1;
+    let end = end.map(usize::from).unwrap_or(len);
+
+    if start > end || end > len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid interval",
+        ));
+    }
+
+    let packed_start = start / 4;
+    let packed_end = end.div_ceil(4);
+
+    reader.seek(SeekFrom::Current(packed_start as i64))?;
+
+    let mut packed_bases = vec![0; packed_end - packed_start];
+    reader.read_exact(&mut packed_bases)?;
+
+    let mut sequence: Vec<_> = (start..end)
+        .map(|i| {
+            let b = packed_bases[i / 4 - packed_start];
+            let shift = 6 - 2 * (i % 4);
+            BASES[usize::from((b >> shift) & 0x03)]
+        })
+        .collect();
+
+    for range in clip_blocks(&n_blocks, start, end) {
+        sequence[range].fill(b'N');
+    }
+
+    for range in clip_blocks(&mask_blocks, start, end) {
+        sequence[range].make_ascii_lowercase();
+    }
+
+    Ok(sequence)
+}
+
+pub(super) fn read_usize<R>(reader: &mut R, byte_order: ByteOrder) -> io::Result<usize>
+where
+    R: Read,
+{
+    byte_order
+        .read_u32(reader)
+        .and_then(|n| usize::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+}
+
+fn read_blocks<R>(reader: &mut R, byte_order: ByteOrder) -> io::Result<Vec<Range<usize>>>
+where
+    R: Read,
+{
+    let count = read_usize(reader, byte_order)?;
+
+    // The count is untrusted, so the starts are not preallocated.
+    let mut starts = Vec::new();
+
+    for _ in 0..count {
+        starts.push(read_usize(reader, byte_order)?);
+    }
+
+    let mut blocks = Vec::with_capacity(starts.len());
+
+    for start in starts {
+        let len = read_usize(reader, byte_order)?;
+
+        let end = start
+            .checked_add(len)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid block"))?;
+
+        blocks.push(start..end);
+    }
+
+    Ok(blocks)
+}
+
+// Returns the parts of the blocks that overlap [`start`, `end`), relative to `start`.
+fn clip_blocks(
+    blocks: &[Range<usize>],
+    start: usize,
+    end: usize,
+) -> impl Iterator<Item = Range<usize>> + '_ {
+    blocks.iter().filter_map(move |block| {
+        let block_start = block.start.max(start);
+        let block_end = block.end.min(end);
+        (block_start < block_end).then(|| (block_start - start)..(block_end - start))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_blocks() {
+        let blocks = [0..2, 4..8, 10..12];
+        let actual: Vec<_> = clip_blocks(&blocks, 1, 6).collect();
+        assert_eq!(actual, [0..1, 3..5]);
+    }
+
+    #[test]
+    fn test_read_blocks_with_truncated_input() {
+        let data = [0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00];
+        let mut reader = &data[..];
+
+        assert!(matches!(
+            read_blocks(&mut reader, ByteOrder::LittleEndian),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 use std::io::{self, BufRead};
 
+use crate::fai::Index;
+
 /// A FASTQ index reader.
 #[derive(Debug)]
 pub struct Reader<R> {
@@ -96,6 +98,44 @@ where
     pub fn read_record(&mut self, buf: &mut String) -> io::Result<usize> {
         read_line(&mut self.inner, buf)
     }
+
+    /// Reads a FASTQ index.
+    ///
+    /// The position of the stream is expected to be at the start or at the start of a record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::fai;
+    ///
+    /// let data = b"r0\t4\t4\t4\t5\t11\n";
+    /// let mut reader = fai::io::Reader::new(&data[..]);
+    /// let index = reader.read_index()?;
+    ///
+    /// assert_eq!(index, [fai::Record::new("r0", 4, 4, 4, 5, 11)]);
+    /// # Ok::<(), io::Error>(())
+    /// ```
+    pub fn read_index(&mut self) -> io::Result<Index> {
+        let mut buf = String::new();
+        let mut records = Vec::new();
+
+        loop {
+            buf.clear();
+
+            if self.read_record(&mut buf)? == 0 {
+                break;
+            }
+
+            let record = buf
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+            records.push(record);
+        }
+
+        Ok(records)
+    }
 }
 
 fn read_line<R>(reader: &mut R, buf: &mut String) -> io::Result<usize>
@@ -150,4 +190,30 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_read_index() -> io::Result<()> {
+        use crate::fai::Record;
+
+        let data = b"r0\t4\t4\t4\t5\t11\nr1\t2\t21\t2\t3\t26\n";
+        let mut reader = Reader::new(&data[..]);
+        let index = reader.read_index()?;
+
+        assert_eq!(
+            index,
+            [
+                Record::new("r0", 4, 4, 4, 5, 11),
+                Record::new("r1", 2, 21, 2, 3, 26),
+            ]
+        );
+
+        let data = b"r0\tndls\n";
+        let mut reader = Reader::new(&data[..]);
+        assert!(matches!(
+            reader.read_index(),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,99 @@
+use std::io::{self, BufRead};
+
+use super::Reader;
+use crate::{Record, quality_encoding::QualityEncoding};
+
+/// A FASTQ reader that converts quality scores to Phred+33.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::{self as fastq, io::ConvertingReader, quality_encoding::QualityEncoding};
+///
+/// let data = b"@r0\nACGT\n+\nhhhB\n";
+/// let mut reader = ConvertingReader::new(fastq::io::Reader::new(&data[..]), QualityEncoding::Phred64);
+///
+/// let mut record = fastq::Record::default();
+/// reader.read_record(&mut record)?;
+/// assert_eq!(record.quality_scores(), b"III#");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct ConvertingReader<R> {
+    inner: Reader<R>,
+    encoding: QualityEncoding,
+}
+
+impl<R> ConvertingReader<R> {
+    /// Creates a FASTQ reader that converts quality scores from the given encoding.
+    pub fn new(inner: Reader<R>, encoding: QualityEncoding) -> Self {
+        Self { inner, encoding }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &Reader<R> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut Reader<R> {
+        &mut self.inner
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> Reader<R> {
+        self.inner
+    }
+
+    /// Returns the source quality score encoding.
+    pub fn encoding(&self) -> QualityEncoding {
+        self.encoding
+    }
+}
+
+impl<R> ConvertingReader<R>
+where
+    R: BufRead,
+{
+    /// Reads a FASTQ record and converts its quality scores to Phred+33.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0, the
+    /// stream reached EOF.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` with the kind [`io::ErrorKind::InvalidData`] if a quality score
+    /// is out of range for the source encoding.
+    pub fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        let n = self.inner.read_record(record)?;
+
+        self.encoding
+            .convert_to_phred33(record.quality_scores_mut())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_record() -> io::Result<()> {
+        let data = b"@r0\nACGT\n+\n;@Jh\n@r1\nA\n+\n!\n";
+        let mut reader = ConvertingReader::new(Reader::new(&data[..]), QualityEncoding::Solexa64);
+
+        let mut record = Record::default();
+
+        reader.read_record(&mut record)?;
+        assert_eq!(record.quality_scores(), b"\"$+I");
+
+        let e = reader.read_record(&mut record).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+
+        assert_eq!(reader.read_record(&mut record)?, 0);
+
+        Ok(())
+    }
+}
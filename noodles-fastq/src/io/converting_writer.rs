@@ -0,0 +1,103 @@
+use std::io::{self, Write};
+
+use super::Writer;
+use crate::{Record, quality_encoding::QualityEncoding};
+
+/// A FASTQ writer that converts quality scores to Phred+33.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::{
+///     self as fastq, io::ConvertingWriter, quality_encoding::QualityEncoding,
+///     record::Definition,
+/// };
+///
+/// let mut writer = ConvertingWriter::new(fastq::io::Writer::new(Vec::new()), QualityEncoding::Phred64);
+///
+/// let record = fastq::Record::new(Definition::new("r0", ""), "ACGT", "hhhB");
+/// writer.write_record(&record)?;
+///
+/// assert_eq!(writer.get_ref().get_ref(), b"@r0\nACGT\n+\nIII#\n");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct ConvertingWriter<W> {
+    inner: Writer<W>,
+    encoding: QualityEncoding,
+    record: Record,
+}
+
+impl<W> ConvertingWriter<W> {
+    /// Creates a FASTQ writer that converts quality scores from the given encoding.
+    pub fn new(inner: Writer<W>, encoding: QualityEncoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            record: Record::default(),
+        }
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &Writer<W> {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut Writer<W> {
+        &mut self.inner
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> Writer<W> {
+        self.inner
+    }
+
+    /// Returns the source quality score encoding.
+    pub fn encoding(&self) -> QualityEncoding {
+        self.encoding
+    }
+}
+
+impl<W> ConvertingWriter<W>
+where
+    W: Write,
+{
+    /// Writes a FASTQ record with its quality scores converted to Phred+33.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` with the kind [`io::ErrorKind::InvalidInput`] if a quality
+    /// score is out of range for the source encoding. The record is not written.
+    pub fn write_record(&mut self, record: &Record) -> io::Result<()> {
+        self.record.clone_from(record);
+
+        self.encoding
+            .convert_to_phred33(self.record.quality_scores_mut())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        self.inner.write_record(&self.record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::Definition;
+
+    #[test]
+    fn test_write_record() -> io::Result<()> {
+        let mut writer = ConvertingWriter::new(Writer::new(Vec::new()), QualityEncoding::Phred64);
+
+        let record = Record::new(Definition::new("r0", ""), "A", "5");
+        let e = writer.write_record(&record).unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidInput);
+
+        let record = Record::new(Definition::new("r1", ""), "AC", "@h");
+        writer.write_record(&record)?;
+
+        assert_eq!(writer.get_ref().get_ref(), b"@r1\nAC\n+\n!I\n");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+//! Indexed FASTQ reader.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Seek, SeekFrom},
+};
+
+use bstr::{BString, ByteSlice};
+
+use super::Reader;
+use crate::{Record, fai, record::Definition};
+
+/// An indexed FASTQ reader.
+///
+/// This fetches records by read name, similar to `samtools fqidx`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{self, Cursor};
+/// use noodles_fastq::{self as fastq, fai};
+///
+/// let data = b"@r0\nACGT\n+\nNDLS\n@r1\nTGCA\n+\nSLDN\n";
+/// let index = vec![
+///     fai::Record::new("r0", 4, 4, 4, 5, 11),
+///     fai::Record::new("r1", 4, 20, 4, 5, 27),
+/// ];
+///
+/// let mut reader = fastq::io::IndexedReader::new(Cursor::new(data), index);
+/// let record = reader.query("r1")?;
+///
+/// assert_eq!(record.name(), "r1");
+/// assert_eq!(record.sequence(), b"TGCA");
+/// assert_eq!(record.quality_scores(), b"SLDN");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct IndexedReader<R> {
+    inner: Reader<R>,
+    index: fai::Index,
+    names: HashMap<BString, usize>,
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead,
+{
+    /// Creates an indexed FASTQ reader.
+    pub fn new(inner: R, index: fai::Index) -> Self {
+        let names = index
+            .iter()
+            .enumerate()
+            .map(|(i, record)| (BString::from(record.name()), i))
+            .collect();
+
+        Self {
+            inner: Reader::new(inner),
+            index,
+            names,
+        }
+    }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        self.inner.get_ref()
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner.get_mut()
+    }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    /// Reads a FASTQ record from the current stream position.
+    pub fn read_record(&mut self, record: &mut Record) -> io::Result<usize> {
+        self.inner.read_record(record)
+    }
+
+    /// Returns the associated index.
+    pub fn index(&self) -> &fai::Index {
+        &self.index
+    }
+}
+
+impl<R> IndexedReader<R>
+where
+    R: BufRead + Seek,
+{
+    /// Returns the record with the given read name.
+    ///
+    /// The returned record includes the read name, sequence, and quality scores. Descriptions
+    /// are not indexed and are left empty.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` with the kind [`io::ErrorKind::InvalidInput`] if the name is
+    /// not in the index.
+    pub fn query<N>(&mut self, name: N) -> io::Result<Record>
+    where
+        N: AsRef<[u8]>,
+    {
+        let name = name.as_ref().as_bstr();
+
+        let i = self.names.get(name).copied().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid record name: {name}"),
+            )
+        })?;
+
+        let index_record = &self.index[i];
+        let len = usize::try_from(index_record.length())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let reader = self.inner.get_mut();
+
+        // The buffers grow as lines are read instead of trusting the length in the index.
+        let mut sequence = Vec::new();
+        reader.seek(SeekFrom::Start(index_record.sequence_offset()))?;
+        read_lines_limit(reader, len, &mut sequence)?;
+
+        let mut quality_scores = Vec::new();
+        reader.seek(SeekFrom::Start(index_record.quality_scores_offset()))?;
+        read_lines_limit(reader, len, &mut quality_scores)?;
+
+        let definition = Definition::new(index_record.name(), "");
+
+        Ok(Record::new(definition, sequence, quality_scores))
+    }
+
+    /// Returns the records with the given read names, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io::{self, Cursor};
+    /// use noodles_fastq::{self as fastq, fai};
+    ///
+    /// let data = b"@r0\nACGT\n+\nNDLS\n@r1\nTGCA\n+\nSLDN\n";
+    /// let index = vec![
+    ///     fai::Record::new("r0", 4, 4, 4, 5, 11),
+    ///     fai::Record::new("r1", 4, 20, 4, 5, 27),
+    /// ];
+    ///
+    /// let mut reader = fastq::io::IndexedReader::new(Cursor::new(data), index);
+    /// let records = reader.query_all(["r1", "r0"])?;
+    ///
+    /// assert_eq!(records.len(), 2);
+    /// assert_eq!(records[0].name(), "r1");
+    /// assert_eq!(records[1].name(), "r0");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn query_all<I, N>(&mut self, names: I) -> io::Result<Vec<Record>>
+    where
+        I: IntoIterator<Item = N>,
+        N: AsRef<[u8]>,
+    {
+        names.into_iter().map(|name| self.query(name)).collect()
+    }
+}
+
+// Reads lines until `len` bytes, excluding newlines, are read.
+fn read_lines_limit<R>(reader: &mut R, len: usize, dst: &mut Vec<u8>) -> io::Result<()>
+where
+    R: BufRead,
+{
+    const LINE_FEED: u8 = b'\n';
+    const CARRIAGE_RETURN: u8 = b'\r';
+
+    while dst.len() < len {
+        if reader.read_until(LINE_FEED, dst)? == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+
+        if dst.ends_with(&[LINE_FEED]) {
+            dst.pop();
+
+            if dst.ends_with(&[CARRIAGE_RETURN]) {
+                dst.pop();
+            }
+        }
+    }
+
+    if dst.len() > len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "record length mismatch",
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, Write};
+
+    use noodles_bgzf as bgzf;
+
+    use super::*;
+    use crate::io::Indexer;
+
+    const DATA: &[u8] = b"@r0 LN:4\nACGT\n+\nNDLS\n@r1\nAC\n+r1\nND\n";
+
+    fn build_index(src: &[u8]) -> io::Result<fai::Index> {
+        let mut indexer = Indexer::new(src);
+        let mut index = Vec::new();
+
+        while let Some(record) = indexer.index_record()? {
+            index.push(record);
+        }
+
+        Ok(index)
+    }
+
+    #[test]
+    fn test_query() -> io::Result<()> {
+        let index = build_index(DATA)?;
+        let mut reader = IndexedReader::new(Cursor::new(DATA), index);
+
+        let record = reader.query("r1")?;
+        assert_eq!(record, Record::new(Definition::new("r1", ""), "AC", "ND"));
+
+        let record = reader.query(b"r0")?;
+        assert_eq!(
+            record,
+            Record::new(Definition::new("r0", ""), "ACGT", "NDLS")
+        );
+
+        assert!(matches!(
+            reader.query("r2"),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let index = build_index(DATA)?;
+        let mut reader = IndexedReader::new(Cursor::new(&DATA[..DATA.len() - 2]), index);
+
+        assert!(matches!(
+            reader.query("r1"),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_invalid_index_length() {
+        let index = vec![fai::Record::new("r0", u64::from(u32::MAX), 4, 4, 5, 11)];
+        let mut reader = IndexedReader::new(Cursor::new(DATA), index);
+
+        assert!(matches!(
+            reader.query("r0"),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof
+        ));
+    }
+
+    #[test]
+    fn test_query_with_multiline_record() -> io::Result<()> {
+        let data = b"@r0\nGGTT\r\nCC\r\n+\r\nSLDN\r\nNN\r\n";
+        let index = vec![fai::Record::new("r0", 6, 4, 4, 6, 17)];
+        let mut reader = IndexedReader::new(Cursor::new(data), index);
+
+        let record = reader.query("r0")?;
+        assert_eq!(
+            record,
+            Record::new(Definition::new("r0", ""), "GGTTCC", "SLDNNN")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_all_with_bgzf_reader() -> io::Result<()> {
+        let mut writer = bgzf::io::Writer::new(Vec::new());
+        writer.write_all(DATA)?;
+        let compressed_data = writer.finish()?;
+
+        let index = build_index(DATA)?;
+        let inner = bgzf::io::IndexedReader::new(Cursor::new(compressed_data), Default::default());
+        let mut reader = IndexedReader::new(inner, index);
+
+        let records = reader.query_all(["r1", "r0"])?;
+
+        assert_eq!(
+            records,
+            [
+                Record::new(Definition::new("r1", ""), "AC", "ND"),
+                Record::new(Definition::new("r0", ""), "ACGT", "NDLS"),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_lines_limit() -> io::Result<()> {
+        let mut dst = Vec::new();
+        read_lines_limit(&mut &b"AC\nGT\n"[..], 4, &mut dst)?;
+        assert_eq!(dst, b"ACGT");
+
+        let mut dst = Vec::new();
+        assert!(matches!(
+            read_lines_limit(&mut &b"ACG\nT\n"[..], 2, &mut dst),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use noodles_bgzf as bgzf;
+
+use super::IndexedReader;
+use crate::fai;
+
+/// An indexed FASTQ reader builder.
+#[derive(Default)]
+pub struct Builder {
+    index: Option<fai::Index>,
+}
+
+impl Builder {
+    /// Sets an index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::{fai, io::indexed_reader::Builder};
+    /// let index = fai::Index::default();
+    /// let builder = Builder::default().set_index(index);
+    /// ```
+    pub fn set_index(mut self, index: fai::Index) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Builds an indexed FASTQ reader from a path.
+    ///
+    /// If no index is set, the associated index is read from `<src>.fai`. bgzip-compressed
+    /// inputs, i.e., those with a `.gz` or `.bgz` extension, also read the associated gzip index
+    /// from `<src>.gzi`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use noodles_fastq::io::indexed_reader::Builder;
+    /// let reader = Builder::default().build_from_path("sample.fq.gz")?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_path<P>(self, src: P) -> io::Result<IndexedReader<crate::io::BufReader<File>>>
+    where
+        P: AsRef<Path>,
+    {
+        let src = src.as_ref();
+
+        let index = match self.index {
+            Some(index) => index,
+            None => {
+                let index_src = build_index_src(src);
+                read_index(index_src)?
+            }
+        };
+
+        let reader = match src.extension().and_then(|ext| ext.to_str()) {
+            Some("gz" | "bgz") => bgzf::io::indexed_reader::Builder::default()
+                .build_from_path(src)
+                .map(crate::io::BufReader::Bgzf)?,
+            _ => File::open(src)
+                .map(BufReader::new)
+                .map(crate::io::BufReader::Uncompressed)?,
+        };
+
+        Ok(IndexedReader::new(reader, index))
+    }
+
+    /// Builds an indexed FASTQ reader from a reader.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::{fai, io::indexed_reader::Builder};
+    ///
+    /// let index = fai::Index::default();
+    /// let data = [];
+    /// let builder = Builder::default()
+    ///     .set_index(index)
+    ///     .build_from_reader(&data[..])?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn build_from_reader<R>(self, reader: R) -> io::Result<IndexedReader<R>>
+    where
+        R: BufRead,
+    {
+        let index = self
+            .index
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing index"))?;
+
+        Ok(IndexedReader::new(reader, index))
+    }
+}
+
+fn read_index<P>(src: P) -> io::Result<fai::Index>
+where
+    P: AsRef<Path>,
+{
+    let mut reader = File::open(src)
+        .map(BufReader::new)
+        .map(fai::io::Reader::new)?;
+
+    reader.read_index()
+}
+
+fn build_index_src<P>(src: P) -> PathBuf
+where
+    P: AsRef<Path>,
+{
+    const EXT: &str = "fai";
+    push_ext(src.as_ref().into(), EXT)
+}
+
+fn push_ext<S>(path: PathBuf, ext: S) -> PathBuf
+where
+    S: AsRef<OsStr>,
+{
+    let mut s = OsString::from(path);
+    s.push(".");
+    s.push(ext);
+    PathBuf::from(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+
+    #[test]
+    fn test_build_from_path() -> io::Result<()> {
+        let dir = env::temp_dir().join(format!("noodles-fastq-indexed-reader-{}", process::id()));
+        fs::create_dir_all(&dir)?;
+
+        let src = dir.join("sample.fq");
+        fs::write(&src, b"@r0\nACGT\n+\nNDLS\n@r1\nTGCA\n+\nSLDN\n")?;
+        fs::write(
+            build_index_src(&src),
+            b"r0\t4\t4\t4\t5\t11\nr1\t4\t20\t4\t5\t27\n",
+        )?;
+
+        let mut reader = Builder::default().build_from_path(&src)?;
+        let record = reader.query("r1")?;
+
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(record.sequence(), b"TGCA");
+        assert_eq!(record.quality_scores(), b"SLDN");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_index_src() {
+        assert_eq!(build_index_src("sample.fq"), PathBuf::from("sample.fq.fai"));
+    }
+}
@@ -0,0 +1,306 @@
+//! Paired-end FASTQ reader.
+
+mod pairs;
+
+pub use self::pairs::Pairs;
+
+use std::{
+    error, fmt,
+    io::{self, BufRead},
+};
+
+use bstr::{BStr, BString};
+
+use super::Reader;
+use crate::Record;
+
+/// A paired-end FASTQ reader.
+///
+/// This reads mates either in lockstep from two streams (e.g., `R1` and `R2` files) or
+/// alternately from a single interleaved stream. The read names of each pair must match,
+/// ignoring a `/1` or `/2` suffix. Descriptions, e.g., Illumina comments, are not compared.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::{self as fastq, io::PairedReader};
+///
+/// let r1 = fastq::io::Reader::new(&b"@r0/1\nACGT\n+\nNDLS\n"[..]);
+/// let r2 = fastq::io::Reader::new(&b"@r0/2\nTGCA\n+\nSLDN\n"[..]);
+/// let mut reader = PairedReader::new(r1, r2);
+///
+/// let mut read_1 = fastq::Record::default();
+/// let mut read_2 = fastq::Record::default();
+/// reader.read_pair(&mut read_1, &mut read_2)?;
+///
+/// assert_eq!(read_1.sequence(), b"ACGT");
+/// assert_eq!(read_2.sequence(), b"TGCA");
+/// # Ok::<_, io::Error>(())
+/// ```
+pub struct PairedReader<R, S = R> {
+    source: Source<R, S>,
+    pair_count: u64,
+}
+
+enum Source<R, S> {
+    Split(Reader<R>, Reader<S>),
+    Interleaved(Reader<R>),
+}
+
+impl<R, S> PairedReader<R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    /// Creates a paired-end FASTQ reader from two streams.
+    pub fn new(reader_1: Reader<R>, reader_2: Reader<S>) -> Self {
+        Self {
+            source: Source::Split(reader_1, reader_2),
+            pair_count: 0,
+        }
+    }
+
+    /// Returns the number of pairs read.
+    pub fn pair_count(&self) -> u64 {
+        self.pair_count
+    }
+
+    /// Reads a pair of records.
+    ///
+    /// If successful, the number of bytes read is returned. If the number of bytes read is 0,
+    /// all streams reached EOF.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` with the kind [`io::ErrorKind::InvalidData`] and a
+    /// [`PairError`] source if the mates are desynchronized, i.e., one stream ends before the
+    /// other or the read names differ.
+    pub fn read_pair(&mut self, read_1: &mut Record, read_2: &mut Record) -> io::Result<usize> {
+        let record_number = self.pair_count + 1;
+
+        let (n1, n2) = match &mut self.source {
+            Source::Split(reader_1, reader_2) => {
+                (reader_1.read_record(read_1)?, reader_2.read_record(read_2)?)
+            }
+            Source::Interleaved(reader) => {
+                let n1 = reader.read_record(read_1)?;
+
+                let n2 = if n1 > 0 {
+                    reader.read_record(read_2)?
+                } else {
+                    0
+                };
+
+                (n1, n2)
+            }
+        };
+
+        let result = match (n1, n2) {
+            (0, 0) => return Ok(0),
+            (0, _) => Err(PairError::MissingRead1(record_number)),
+            (_, 0) => Err(PairError::MissingRead2(record_number)),
+            _ if !names_eq(read_1.name(), read_2.name()) => Err(PairError::NameMismatch {
+                record_number,
+                read_1_name: read_1.name().into(),
+                read_2_name: read_2.name().into(),
+            }),
+            _ => Ok(n1 + n2),
+        };
+
+        let n = result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        self.pair_count += 1;
+
+        Ok(n)
+    }
+
+    /// Returns an iterator over pairs of records starting from the current stream positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::io;
+    /// use noodles_fastq::{self as fastq, io::PairedReader};
+    ///
+    /// let data = b"@r0/1\nACGT\n+\nNDLS\n@r0/2\nTGCA\n+\nSLDN\n";
+    /// let mut reader = PairedReader::interleaved(fastq::io::Reader::new(&data[..]));
+    ///
+    /// let mut pairs = reader.pairs();
+    /// assert!(pairs.next().transpose()?.is_some());
+    /// assert!(pairs.next().is_none());
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    pub fn pairs(&mut self) -> Pairs<'_, R, S> {
+        Pairs::new(self)
+    }
+}
+
+impl<R> PairedReader<R, R>
+where
+    R: BufRead,
+{
+    /// Creates a paired-end FASTQ reader from an interleaved stream.
+    ///
+    /// Mates are expected to be adjacent, with read 1 first.
+    pub fn interleaved(reader: Reader<R>) -> Self {
+        Self {
+            source: Source::Interleaved(reader),
+            pair_count: 0,
+        }
+    }
+}
+
+/// An error returned when paired-end reads are desynchronized.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PairError {
+    /// Read 1 is missing from the pair with the given 1-based record number.
+    MissingRead1(u64),
+    /// Read 2 is missing from the pair with the given 1-based record number.
+    MissingRead2(u64),
+    /// The read names of the pair with the given 1-based record number differ.
+    NameMismatch {
+        /// The 1-based record number.
+        record_number: u64,
+        /// The name of read 1.
+        read_1_name: BString,
+        /// The name of read 2.
+        read_2_name: BString,
+    },
+}
+
+impl PairError {
+    /// Returns the 1-based record number of the pair.
+    pub fn record_number(&self) -> u64 {
+        match self {
+            Self::MissingRead1(n) | Self::MissingRead2(n) => *n,
+            Self::NameMismatch { record_number, .. } => *record_number,
+        }
+    }
+}
+
+impl error::Error for PairError {}
+
+impl fmt::Display for PairError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRead1(n) => write!(f, "missing read 1 at record {n}"),
+            Self::MissingRead2(n) => write!(f, "missing read 2 at record {n}"),
+            Self::NameMismatch {
+                record_number,
+                read_1_name,
+                read_2_name,
+            } => write!(
+                f,
+                "read name mismatch at record {record_number}: {read_1_name} != {read_2_name}"
+            ),
+        }
+    }
+}
+
+fn names_eq(a: &BStr, b: &BStr) -> bool {
+    strip_mate_suffix(a) == strip_mate_suffix(b)
+}
+
+fn strip_mate_suffix(name: &[u8]) -> &[u8] {
+    name.strip_suffix(b"/1")
+        .or_else(|| name.strip_suffix(b"/2"))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unwrap_pair_error(e: io::Error) -> PairError {
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        e.into_inner()
+            .and_then(|e| e.downcast::<PairError>().ok())
+            .map(|e| *e)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_read_pair() -> io::Result<()> {
+        let r1 = Reader::new(&b"@r0/1 1:N:0:1\nACGT\n+\nNDLS\n@r1/1\nA\n+\nN\n"[..]);
+        let r2 = Reader::new(&b"@r0/2 2:N:0:1\nTGCA\n+\nSLDN\n@r1\nC\n+\nN\n"[..]);
+        let mut reader = PairedReader::new(r1, r2);
+
+        let mut read_1 = Record::default();
+        let mut read_2 = Record::default();
+
+        assert!(reader.read_pair(&mut read_1, &mut read_2)? > 0);
+        assert_eq!(read_1.name(), "r0/1");
+        assert_eq!(read_2.name(), "r0/2");
+
+        assert!(reader.read_pair(&mut read_1, &mut read_2)? > 0);
+        assert_eq!(reader.read_pair(&mut read_1, &mut read_2)?, 0);
+        assert_eq!(reader.pair_count(), 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_pair_with_desynchronized_streams() -> io::Result<()> {
+        let mut read_1 = Record::default();
+        let mut read_2 = Record::default();
+
+        let r1 = Reader::new(&b"@r0/1\nA\n+\nN\n@r1/1\nA\n+\nN\n"[..]);
+        let r2 = Reader::new(&b"@r0/2\nC\n+\nN\n"[..]);
+        let mut reader = PairedReader::new(r1, r2);
+        reader.read_pair(&mut read_1, &mut read_2)?;
+
+        let e = reader.read_pair(&mut read_1, &mut read_2).unwrap_err();
+        assert_eq!(unwrap_pair_error(e), PairError::MissingRead2(2));
+
+        let r1 = Reader::new(&b""[..]);
+        let r2 = Reader::new(&b"@r0/2\nC\n+\nN\n"[..]);
+        let mut reader = PairedReader::new(r1, r2);
+
+        let e = reader.read_pair(&mut read_1, &mut read_2).unwrap_err();
+        assert_eq!(unwrap_pair_error(e), PairError::MissingRead1(1));
+
+        let r1 = Reader::new(&b"@r0/1\nA\n+\nN\n"[..]);
+        let r2 = Reader::new(&b"@r1/2\nC\n+\nN\n"[..]);
+        let mut reader = PairedReader::new(r1, r2);
+
+        let e = reader.read_pair(&mut read_1, &mut read_2).unwrap_err();
+        let e = unwrap_pair_error(e);
+        assert_eq!(e.record_number(), 1);
+        assert_eq!(
+            e,
+            PairError::NameMismatch {
+                record_number: 1,
+                read_1_name: BString::from("r0/1"),
+                read_2_name: BString::from("r1/2"),
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_pair_from_interleaved_stream() -> io::Result<()> {
+        let data = b"@r0/1\nA\n+\nN\n@r0/2\nC\n+\nN\n@r1/1\nG\n+\nN\n";
+        let mut reader = PairedReader::interleaved(Reader::new(&data[..]));
+
+        let mut read_1 = Record::default();
+        let mut read_2 = Record::default();
+
+        assert!(reader.read_pair(&mut read_1, &mut read_2)? > 0);
+        assert_eq!(read_1.sequence(), b"A");
+        assert_eq!(read_2.sequence(), b"C");
+
+        let e = reader.read_pair(&mut read_1, &mut read_2).unwrap_err();
+        assert_eq!(unwrap_pair_error(e), PairError::MissingRead2(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_mate_suffix() {
+        assert_eq!(strip_mate_suffix(b"r0/1"), b"r0");
+        assert_eq!(strip_mate_suffix(b"r0/2"), b"r0");
+        assert_eq!(strip_mate_suffix(b"r0/3"), b"r0/3");
+        assert_eq!(strip_mate_suffix(b"r0"), b"r0");
+    }
+}
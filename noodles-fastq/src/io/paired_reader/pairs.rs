@@ -0,0 +1,46 @@
+use std::io::{self, BufRead};
+
+use super::PairedReader;
+use crate::Record;
+
+/// An iterator over pairs of records of a paired-end FASTQ reader.
+///
+/// This is created by calling [`PairedReader::pairs`].
+pub struct Pairs<'a, R, S> {
+    inner: &'a mut PairedReader<R, S>,
+    read_1: Record,
+    read_2: Record,
+}
+
+impl<'a, R, S> Pairs<'a, R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    pub(super) fn new(inner: &'a mut PairedReader<R, S>) -> Self {
+        Self {
+            inner,
+            read_1: Record::default(),
+            read_2: Record::default(),
+        }
+    }
+}
+
+impl<R, S> Iterator for Pairs<'_, R, S>
+where
+    R: BufRead,
+    S: BufRead,
+{
+    type Item = io::Result<(Record, Record)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_1.clear();
+        self.read_2.clear();
+
+        match self.inner.read_pair(&mut self.read_1, &mut self.read_2) {
+            Ok(0) => None,
+            Ok(_) => Some(Ok((self.read_1.clone(), self.read_2.clone()))),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! FASTQ quality score encoding.
+
+mod detector;
+
+pub use self::detector::{Detector, detect};
+
+use std::{error, fmt};
+
+const PHRED_33_OFFSET: u8 = b'!';
+const PHRED_64_OFFSET: u8 = b'@';
+const MAX_VALUE: u8 = b'~';
+
+/// A FASTQ quality score encoding.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum QualityEncoding {
+    /// Phred quality scores offset by 33 (Sanger, Illumina 1.8+).
+    Phred33,
+    /// Phred quality scores offset by 64 (Illumina 1.3–1.7).
+    Phred64,
+    /// Solexa quality scores offset by 64 (Solexa, Illumina 1.0).
+    Solexa64,
+}
+
+impl QualityEncoding {
+    /// Returns the smallest valid encoded value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::quality_encoding::QualityEncoding;
+    /// assert_eq!(QualityEncoding::Phred33.min_value(), b'!');
+    /// assert_eq!(QualityEncoding::Phred64.min_value(), b'@');
+    /// assert_eq!(QualityEncoding::Solexa64.min_value(), b';');
+    /// ```
+    pub fn min_value(&self) -> u8 {
+        match self {
+            Self::Phred33 => PHRED_33_OFFSET,
+            Self::Phred64 => PHRED_64_OFFSET,
+            // Solexa scores start at -5.
+            Self::Solexa64 => PHRED_64_OFFSET - 5,
+        }
+    }
+
+    /// Converts encoded quality scores to Phred+33 in place.
+    ///
+    /// Solexa scores are mapped to the nearest Phred score.
+    ///
+    /// # Errors
+    ///
+    /// This returns an error if a value is out of range for the encoding.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::quality_encoding::QualityEncoding;
+    ///
+    /// let mut buf = b"@Jh".to_vec();
+    /// QualityEncoding::Phred64.convert_to_phred33(&mut buf)?;
+    /// assert_eq!(buf, b"!+I");
+    ///
+    /// let mut buf = b";@J".to_vec();
+    /// QualityEncoding::Solexa64.convert_to_phred33(&mut buf)?;
+    /// assert_eq!(buf, b"\"$+");
+    /// # Ok::<_, noodles_fastq::quality_encoding::ConvertError>(())
+    /// ```
+    pub fn convert_to_phred33(&self, buf: &mut [u8]) -> Result<(), ConvertError> {
+        let min_value = self.min_value();
+
+        for value in buf.iter_mut() {
+            if !(min_value..=MAX_VALUE).contains(value) {
+                return Err(ConvertError::InvalidValue(*value));
+            }
+
+            *value = match self {
+                Self::Phred33 => *value,
+                Self::Phred64 => *value - PHRED_64_OFFSET + PHRED_33_OFFSET,
+                Self::Solexa64 => {
+                    let score = i32::from(*value) - i32::from(PHRED_64_OFFSET);
+                    solexa_to_phred(score) + PHRED_33_OFFSET
+                }
+            };
+        }
+
+        Ok(())
+    }
+}
+
+/// An error returned when quality scores fail to convert.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConvertError {
+    /// A value is out of range for the encoding.
+    InvalidValue(u8),
+}
+
+impl error::Error for ConvertError {}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidValue(value) => {
+                write!(f, "invalid quality score value: {:?}", char::from(*value))
+            }
+        }
+    }
+}
+
+// Q_phred = 10 * log10(10^(Q_solexa / 10) + 1)
+fn solexa_to_phred(score: i32) -> u8 {
+    let q = 10.0 * (10f64.powf(f64::from(score) / 10.0) + 1.0).log10();
+    q.round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_to_phred33() {
+        let mut buf = b"!5I~".to_vec();
+        assert!(
+            QualityEncoding::Phred33
+                .convert_to_phred33(&mut buf)
+                .is_ok()
+        );
+        assert_eq!(buf, b"!5I~");
+
+        let mut buf = b"@Th~".to_vec();
+        assert!(
+            QualityEncoding::Phred64
+                .convert_to_phred33(&mut buf)
+                .is_ok()
+        );
+        assert_eq!(buf, b"!5I_");
+
+        let mut buf = b";@Jh".to_vec();
+        assert!(
+            QualityEncoding::Solexa64
+                .convert_to_phred33(&mut buf)
+                .is_ok()
+        );
+        assert_eq!(buf, b"\"$+I");
+
+        let mut buf = b"@5".to_vec();
+        assert_eq!(
+            QualityEncoding::Phred64.convert_to_phred33(&mut buf),
+            Err(ConvertError::InvalidValue(b'5'))
+        );
+
+        let mut buf = b":".to_vec();
+        assert_eq!(
+            QualityEncoding::Solexa64.convert_to_phred33(&mut buf),
+            Err(ConvertError::InvalidValue(b':'))
+        );
+
+        let mut buf = b" ".to_vec();
+        assert_eq!(
+            QualityEncoding::Phred33.convert_to_phred33(&mut buf),
+            Err(ConvertError::InvalidValue(b' '))
+        );
+    }
+
+    #[test]
+    fn test_solexa_to_phred() {
+        assert_eq!(solexa_to_phred(-5), 1);
+        assert_eq!(solexa_to_phred(0), 3);
+        assert_eq!(solexa_to_phred(10), 10);
+        assert_eq!(solexa_to_phred(40), 40);
+    }
+}
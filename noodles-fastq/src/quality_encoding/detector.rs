@@ -0,0 +1,163 @@
+use std::io::{self, BufRead};
+
+use super::{MAX_VALUE, PHRED_33_OFFSET, PHRED_64_OFFSET, QualityEncoding};
+use crate::{Record, io::Reader};
+
+const SOLEXA_64_MIN_VALUE: u8 = PHRED_64_OFFSET - 5;
+// Q42, the largest score commonly emitted in Phred+33.
+const PHRED_33_MAX_VALUE: u8 = b'K';
+
+/// A FASTQ quality score encoding detector.
+///
+/// This infers the encoding from the range of observed quality score values. Values below `;` are
+/// only valid as Phred+33. Otherwise, a +64 encoding is only inferred when a value exceeds the
+/// Phred+33 ceiling (`K`): Solexa+64 when a value is below `@`, and Phred+64 otherwise. A sample
+/// that fits both Phred+33 and a +64 encoding, e.g., `;;;;` or `FFFF`, is ambiguous.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::quality_encoding::{Detector, QualityEncoding};
+///
+/// let mut detector = Detector::default();
+/// assert!(detector.encoding().is_none());
+///
+/// detector.add(b"hhhhhhhB");
+/// assert_eq!(detector.encoding(), Some(QualityEncoding::Phred64));
+///
+/// detector.add(b"#,5?");
+/// assert_eq!(detector.encoding(), Some(QualityEncoding::Phred33));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Detector {
+    range: Option<(u8, u8)>,
+}
+
+impl Detector {
+    /// Adds quality scores to the sample.
+    pub fn add(&mut self, quality_scores: &[u8]) {
+        for &value in quality_scores {
+            self.range = Some(match self.range {
+                Some((min, max)) => (min.min(value), max.max(value)),
+                None => (value, value),
+            });
+        }
+    }
+
+    /// Returns the inferred encoding.
+    ///
+    /// This returns `None` if no quality scores were sampled, if any sampled value is not a valid
+    /// quality score in any encoding, or if the sample is ambiguous.
+    pub fn encoding(&self) -> Option<QualityEncoding> {
+        let (min, max) = self.range?;
+
+        if min < PHRED_33_OFFSET || max > MAX_VALUE {
+            None
+        } else if min < SOLEXA_64_MIN_VALUE {
+            Some(QualityEncoding::Phred33)
+        } else if max <= PHRED_33_MAX_VALUE {
+            None
+        } else if min < PHRED_64_OFFSET {
+            Some(QualityEncoding::Solexa64)
+        } else {
+            Some(QualityEncoding::Phred64)
+        }
+    }
+}
+
+/// Samples up to the given number of records to infer the quality score encoding.
+///
+/// This consumes the sampled records from the reader.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io;
+/// use noodles_fastq::{self as fastq, quality_encoding::{self, QualityEncoding}};
+///
+/// let data = b"@r0\nACGT\n+\nhhhB\n@r1\nACGT\n+\nNDLS\n";
+/// let mut reader = fastq::io::Reader::new(&data[..]);
+///
+/// assert_eq!(quality_encoding::detect(&mut reader, 1)?, Some(QualityEncoding::Phred64));
+/// # Ok::<_, io::Error>(())
+/// ```
+pub fn detect<R>(
+    reader: &mut Reader<R>,
+    max_record_count: usize,
+) -> io::Result<Option<QualityEncoding>>
+where
+    R: BufRead,
+{
+    let mut detector = Detector::default();
+    let mut record = Record::default();
+
+    for _ in 0..max_record_count {
+        if reader.read_record(&mut record)? == 0 {
+            break;
+        }
+
+        detector.add(record.quality_scores());
+    }
+
+    Ok(detector.encoding())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding() {
+        let mut detector = Detector::default();
+        assert!(detector.encoding().is_none());
+
+        detector.add(b"");
+        assert!(detector.encoding().is_none());
+
+        detector.add(b"@F");
+        assert!(detector.encoding().is_none());
+
+        detector.add(b"h");
+        assert_eq!(detector.encoding(), Some(QualityEncoding::Phred64));
+
+        detector.add(b";");
+        assert_eq!(detector.encoding(), Some(QualityEncoding::Solexa64));
+
+        detector.add(b":");
+        assert_eq!(detector.encoding(), Some(QualityEncoding::Phred33));
+
+        detector.add(b" ");
+        assert!(detector.encoding().is_none());
+    }
+
+    #[test]
+    fn test_encoding_with_ambiguous_sample() {
+        let mut detector = Detector::default();
+        detector.add(b";;;;");
+        assert!(detector.encoding().is_none());
+
+        let mut detector = Detector::default();
+        detector.add(b"FFFF");
+        assert!(detector.encoding().is_none());
+
+        let mut detector = Detector::default();
+        detector.add(b"KKKK");
+        assert!(detector.encoding().is_none());
+
+        detector.add(b"L");
+        assert_eq!(detector.encoding(), Some(QualityEncoding::Phred64));
+    }
+
+    #[test]
+    fn test_detect() -> io::Result<()> {
+        let data = b"@r0\nACGT\n+\nhhhB\n@r1\nACGT\n+\n;;;;\n";
+
+        let mut reader = Reader::new(&data[..]);
+        assert_eq!(detect(&mut reader, 2)?, Some(QualityEncoding::Solexa64));
+
+        let mut reader = Reader::new(&b""[..]);
+        assert_eq!(detect(&mut reader, 2)?, None);
+
+        Ok(())
+    }
+}
@@ -1,3 +1,13 @@
+//! FASTQ record definition.
+
+pub mod illumina;
+pub mod legacy;
+pub mod sra;
+
+pub use self::{illumina::Illumina, legacy::Legacy, sra::Sra};
+
+use std::str::{self, FromStr};
+
 use bstr::{BStr, BString};
 
 /// A FASTQ record definition.
@@ -47,3 +57,14 @@ impl Definition {
         self.description.clear();
     }
 }
+
+fn parse_int<N>(buf: &[u8]) -> Option<N>
+where
+    N: FromStr,
+{
+    if buf.is_empty() || !buf.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+
+    str::from_utf8(buf).ok().and_then(|s| s.parse().ok())
+}
@@ -0,0 +1,376 @@
+//! Illumina (CASAVA 1.8+) read name.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{error, fmt};
+
+use bstr::{BStr, BString, ByteSlice};
+
+use super::{Definition, parse_int};
+
+const DELIMITER: u8 = b':';
+
+/// An Illumina (CASAVA 1.8+) read name.
+///
+/// This is parsed from a definition with the form
+///
+/// ```text
+/// @<instrument>:<run number>:<flowcell ID>:<lane>:<tile>:<x>:<y>[:<UMI>] <read>:<is filtered>:<control number>:<index sequence>
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::{Definition, definition::Illumina};
+///
+/// let definition = Definition::new("EAS139:136:FC706VJ:2:2104:15343:197393", "1:Y:18:ATCACG");
+/// let name = Illumina::try_from(&definition)?;
+///
+/// assert_eq!(name.lane(), 2);
+/// assert_eq!(name.tile(), 2104);
+/// assert!(name.is_filtered());
+/// assert_eq!(name.index_sequence(), "ATCACG");
+///
+/// assert_eq!(Definition::from(&name), definition);
+/// # Ok::<_, noodles_fastq::record::definition::illumina::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Illumina {
+    instrument: BString,
+    run_number: u32,
+    flowcell_id: BString,
+    lane: u32,
+    tile: u32,
+    x: u32,
+    y: u32,
+    umi: Option<BString>,
+    read_number: u8,
+    is_filtered: bool,
+    control_number: u32,
+    index_sequence: BString,
+}
+
+impl Illumina {
+    /// Returns a builder to create an Illumina read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Illumina;
+    /// let builder = Illumina::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the instrument ID.
+    pub fn instrument(&self) -> &BStr {
+        self.instrument.as_ref()
+    }
+
+    /// Returns the run number on the instrument.
+    pub fn run_number(&self) -> u32 {
+        self.run_number
+    }
+
+    /// Returns the flowcell ID.
+    pub fn flowcell_id(&self) -> &BStr {
+        self.flowcell_id.as_ref()
+    }
+
+    /// Returns the lane number.
+    pub fn lane(&self) -> u32 {
+        self.lane
+    }
+
+    /// Returns the tile number.
+    pub fn tile(&self) -> u32 {
+        self.tile
+    }
+
+    /// Returns the x-coordinate of the cluster.
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the cluster.
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Returns the unique molecular identifier (UMI), if any.
+    pub fn umi(&self) -> Option<&BStr> {
+        self.umi.as_ref().map(|umi| umi.as_ref())
+    }
+
+    /// Returns the read number of the pair, e.g., 1 or 2.
+    pub fn read_number(&self) -> u8 {
+        self.read_number
+    }
+
+    /// Returns whether the read was filtered, i.e., did not pass filter.
+    pub fn is_filtered(&self) -> bool {
+        self.is_filtered
+    }
+
+    /// Returns the control number.
+    ///
+    /// This is 0 when none of the control bits are on.
+    pub fn control_number(&self) -> u32 {
+        self.control_number
+    }
+
+    /// Returns the index sequence or sample number.
+    pub fn index_sequence(&self) -> &BStr {
+        self.index_sequence.as_ref()
+    }
+}
+
+/// An Illumina read name field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// The instrument ID.
+    Instrument,
+    /// The run number.
+    RunNumber,
+    /// The flowcell ID.
+    FlowcellId,
+    /// The lane number.
+    Lane,
+    /// The tile number.
+    Tile,
+    /// The x-coordinate.
+    X,
+    /// The y-coordinate.
+    Y,
+    /// The read number.
+    ReadNumber,
+    /// The filtered flag.
+    IsFiltered,
+    /// The control number.
+    ControlNumber,
+    /// The index sequence.
+    IndexSequence,
+}
+
+/// An error returned when an Illumina read name fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// A field is missing.
+    MissingField(Field),
+    /// A field is invalid.
+    InvalidField(Field),
+    /// The name or description has unexpected trailing fields.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field: {field:?}"),
+            Self::InvalidField(field) => write!(f, "invalid field: {field:?}"),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl TryFrom<&Definition> for Illumina {
+    type Error = ParseError;
+
+    fn try_from(definition: &Definition) -> Result<Self, Self::Error> {
+        let mut fields = definition.name().split_str(&[DELIMITER]);
+
+        let instrument = parse_string(&mut fields, Field::Instrument)?;
+        let run_number = parse_number(&mut fields, Field::RunNumber)?;
+        let flowcell_id = parse_string(&mut fields, Field::FlowcellId)?;
+        let lane = parse_number(&mut fields, Field::Lane)?;
+        let tile = parse_number(&mut fields, Field::Tile)?;
+        let x = parse_number(&mut fields, Field::X)?;
+        let y = parse_number(&mut fields, Field::Y)?;
+        let umi = fields.next().map(BString::from);
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        let mut fields = definition.description().split_str(&[DELIMITER]);
+
+        let read_number = parse_number(&mut fields, Field::ReadNumber)?;
+
+        let is_filtered = match fields.next() {
+            Some(b"Y") => true,
+            Some(b"N") => false,
+            Some(_) => return Err(ParseError::InvalidField(Field::IsFiltered)),
+            None => return Err(ParseError::MissingField(Field::IsFiltered)),
+        };
+
+        let control_number = parse_number(&mut fields, Field::ControlNumber)?;
+
+        let index_sequence = fields
+            .next()
+            .map(BString::from)
+            .ok_or(ParseError::MissingField(Field::IndexSequence))?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self {
+            instrument,
+            run_number,
+            flowcell_id,
+            lane,
+            tile,
+            x,
+            y,
+            umi,
+            read_number,
+            is_filtered,
+            control_number,
+            index_sequence,
+        })
+    }
+}
+
+impl From<&Illumina> for Definition {
+    fn from(illumina: &Illumina) -> Self {
+        let mut name = format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            illumina.instrument,
+            illumina.run_number,
+            illumina.flowcell_id,
+            illumina.lane,
+            illumina.tile,
+            illumina.x,
+            illumina.y
+        );
+
+        if let Some(umi) = &illumina.umi {
+            name.push(char::from(DELIMITER));
+            name.push_str(&umi.to_str_lossy());
+        }
+
+        let description = format!(
+            "{}:{}:{}:{}",
+            illumina.read_number,
+            if illumina.is_filtered { 'Y' } else { 'N' },
+            illumina.control_number,
+            illumina.index_sequence
+        );
+
+        Self::new(name, description)
+    }
+}
+
+fn parse_string<'a, I>(fields: &mut I, field: Field) -> Result<BString, ParseError>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    match fields.next() {
+        Some(buf) if !buf.is_empty() => Ok(buf.into()),
+        Some(_) => Err(ParseError::InvalidField(field)),
+        None => Err(ParseError::MissingField(field)),
+    }
+}
+
+fn parse_number<'a, I, N>(fields: &mut I, field: Field) -> Result<N, ParseError>
+where
+    I: Iterator<Item = &'a [u8]>,
+    N: std::str::FromStr,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingField(field))
+        .and_then(|buf| parse_int(buf).ok_or(ParseError::InvalidField(field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_definition_for_illumina() {
+        let definition = Definition::new(
+            "EAS139:136:FC706VJ:2:2104:15343:197393:ACGTACGT",
+            "2:N:0:ATCACG+TTGACA",
+        );
+
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Ok(Illumina {
+                instrument: BString::from("EAS139"),
+                run_number: 136,
+                flowcell_id: BString::from("FC706VJ"),
+                lane: 2,
+                tile: 2104,
+                x: 15343,
+                y: 197393,
+                umi: Some(BString::from("ACGTACGT")),
+                read_number: 2,
+                is_filtered: false,
+                control_number: 0,
+                index_sequence: BString::from("ATCACG+TTGACA"),
+            })
+        );
+
+        let definition = Definition::new("EAS139:136:FC706VJ:2:2104:15343", "1:Y:18:ATCACG");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::MissingField(Field::Y))
+        );
+
+        let definition = Definition::new("EAS139:136:FC706VJ:2:tile:15343:197393", "1:Y:18:1");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidField(Field::Tile))
+        );
+
+        let definition = Definition::new("EAS139:136:FC706VJ:2:2104:15343:197393", "1:X:18:1");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidField(Field::IsFiltered))
+        );
+
+        let definition = Definition::new("EAS139:136:FC706VJ:2:2104:15343:197393", "");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::InvalidField(Field::ReadNumber))
+        );
+
+        let definition = Definition::new("r0/1", "");
+        assert_eq!(
+            Illumina::try_from(&definition),
+            Err(ParseError::MissingField(Field::RunNumber))
+        );
+    }
+
+    #[test]
+    fn test_from_illumina_for_definition() {
+        let illumina = Illumina::builder()
+            .set_instrument("EAS139")
+            .set_run_number(136)
+            .set_flowcell_id("FC706VJ")
+            .set_lane(2)
+            .set_tile(2104)
+            .set_x(15343)
+            .set_y(197393)
+            .set_umi("ACGTACGT")
+            .set_read_number(1)
+            .set_is_filtered(true)
+            .set_control_number(18)
+            .set_index_sequence("ATCACG")
+            .build();
+
+        assert_eq!(
+            Definition::from(&illumina),
+            Definition::new(
+                "EAS139:136:FC706VJ:2:2104:15343:197393:ACGTACGT",
+                "1:Y:18:ATCACG"
+            )
+        );
+    }
+}
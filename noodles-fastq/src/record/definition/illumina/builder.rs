@@ -0,0 +1,132 @@
+use bstr::BString;
+
+use super::Illumina;
+
+/// An Illumina read name builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    instrument: BString,
+    run_number: u32,
+    flowcell_id: BString,
+    lane: u32,
+    tile: u32,
+    x: u32,
+    y: u32,
+    umi: Option<BString>,
+    read_number: u8,
+    is_filtered: bool,
+    control_number: u32,
+    index_sequence: BString,
+}
+
+impl Builder {
+    /// Sets the instrument ID.
+    pub fn set_instrument<S>(mut self, instrument: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.instrument = instrument.into();
+        self
+    }
+
+    /// Sets the run number.
+    pub fn set_run_number(mut self, run_number: u32) -> Self {
+        self.run_number = run_number;
+        self
+    }
+
+    /// Sets the flowcell ID.
+    pub fn set_flowcell_id<S>(mut self, flowcell_id: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.flowcell_id = flowcell_id.into();
+        self
+    }
+
+    /// Sets the lane number.
+    pub fn set_lane(mut self, lane: u32) -> Self {
+        self.lane = lane;
+        self
+    }
+
+    /// Sets the tile number.
+    pub fn set_tile(mut self, tile: u32) -> Self {
+        self.tile = tile;
+        self
+    }
+
+    /// Sets the x-coordinate of the cluster.
+    pub fn set_x(mut self, x: u32) -> Self {
+        self.x = x;
+        self
+    }
+
+    /// Sets the y-coordinate of the cluster.
+    pub fn set_y(mut self, y: u32) -> Self {
+        self.y = y;
+        self
+    }
+
+    /// Sets the unique molecular identifier (UMI).
+    pub fn set_umi<S>(mut self, umi: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.umi = Some(umi.into());
+        self
+    }
+
+    /// Sets the read number.
+    pub fn set_read_number(mut self, read_number: u8) -> Self {
+        self.read_number = read_number;
+        self
+    }
+
+    /// Sets whether the read was filtered.
+    pub fn set_is_filtered(mut self, is_filtered: bool) -> Self {
+        self.is_filtered = is_filtered;
+        self
+    }
+
+    /// Sets the control number.
+    pub fn set_control_number(mut self, control_number: u32) -> Self {
+        self.control_number = control_number;
+        self
+    }
+
+    /// Sets the index sequence or sample number.
+    pub fn set_index_sequence<S>(mut self, index_sequence: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.index_sequence = index_sequence.into();
+        self
+    }
+
+    /// Builds an Illumina read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Illumina;
+    /// let name = Illumina::builder().set_lane(2).build();
+    /// assert_eq!(name.lane(), 2);
+    /// ```
+    pub fn build(self) -> Illumina {
+        Illumina {
+            instrument: self.instrument,
+            run_number: self.run_number,
+            flowcell_id: self.flowcell_id,
+            lane: self.lane,
+            tile: self.tile,
+            x: self.x,
+            y: self.y,
+            umi: self.umi,
+            read_number: self.read_number,
+            is_filtered: self.is_filtered,
+            control_number: self.control_number,
+            index_sequence: self.index_sequence,
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Legacy Illumina (pre-CASAVA 1.8) read name.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{error, fmt};
+
+use bstr::{BStr, BString, ByteSlice};
+
+use super::{Definition, parse_int};
+
+const DELIMITER: u8 = b':';
+const INDEX_PREFIX: u8 = b'#';
+const READ_NUMBER_PREFIX: u8 = b'/';
+
+/// A legacy Illumina (pre-CASAVA 1.8) read name.
+///
+/// This is parsed from a read name with the form
+///
+/// ```text
+/// @<instrument>:<lane>:<tile>:<x>:<y>[#<index>][/<read>]
+/// ```
+///
+/// The description, if any, is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::{Definition, definition::Legacy};
+///
+/// let definition = Definition::new("HWUSI-EAS100R:6:73:941:1973#0/1", "");
+/// let name = Legacy::try_from(&definition)?;
+///
+/// assert_eq!(name.instrument(), "HWUSI-EAS100R");
+/// assert_eq!(name.lane(), 6);
+/// assert_eq!(name.index(), Some(b"0".as_ref().into()));
+/// assert_eq!(name.read_number(), Some(1));
+///
+/// assert_eq!(Definition::from(&name), definition);
+/// # Ok::<_, noodles_fastq::record::definition::legacy::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Legacy {
+    instrument: BString,
+    lane: u32,
+    tile: u32,
+    x: u32,
+    y: u32,
+    index: Option<BString>,
+    read_number: Option<u8>,
+}
+
+impl Legacy {
+    /// Returns a builder to create a legacy Illumina read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Legacy;
+    /// let builder = Legacy::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the instrument ID.
+    pub fn instrument(&self) -> &BStr {
+        self.instrument.as_ref()
+    }
+
+    /// Returns the lane number.
+    pub fn lane(&self) -> u32 {
+        self.lane
+    }
+
+    /// Returns the tile number.
+    pub fn tile(&self) -> u32 {
+        self.tile
+    }
+
+    /// Returns the x-coordinate of the cluster.
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    /// Returns the y-coordinate of the cluster.
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    /// Returns the index sequence or sample number, if any.
+    pub fn index(&self) -> Option<&BStr> {
+        self.index.as_ref().map(|index| index.as_ref())
+    }
+
+    /// Returns the read number of the pair, if any.
+    pub fn read_number(&self) -> Option<u8> {
+        self.read_number
+    }
+}
+
+/// A legacy Illumina read name field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// The instrument ID.
+    Instrument,
+    /// The lane number.
+    Lane,
+    /// The tile number.
+    Tile,
+    /// The x-coordinate.
+    X,
+    /// The y-coordinate.
+    Y,
+    /// The index sequence.
+    Index,
+    /// The read number.
+    ReadNumber,
+}
+
+/// An error returned when a legacy Illumina read name fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// A field is missing.
+    MissingField(Field),
+    /// A field is invalid.
+    InvalidField(Field),
+    /// The name has unexpected trailing fields.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field: {field:?}"),
+            Self::InvalidField(field) => write!(f, "invalid field: {field:?}"),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl TryFrom<&Definition> for Legacy {
+    type Error = ParseError;
+
+    fn try_from(definition: &Definition) -> Result<Self, Self::Error> {
+        let mut src: &[u8] = definition.name();
+
+        let read_number = match src.rfind_byte(READ_NUMBER_PREFIX) {
+            Some(i) => {
+                let n =
+                    parse_int(&src[i + 1..]).ok_or(ParseError::InvalidField(Field::ReadNumber))?;
+                src = &src[..i];
+                Some(n)
+            }
+            None => None,
+        };
+
+        let index = match src.rfind_byte(INDEX_PREFIX) {
+            Some(i) => {
+                let buf = &src[i + 1..];
+
+                if buf.is_empty() {
+                    return Err(ParseError::InvalidField(Field::Index));
+                }
+
+                src = &src[..i];
+                Some(buf.into())
+            }
+            None => None,
+        };
+
+        let mut fields = src.split_str(&[DELIMITER]);
+
+        let instrument = match fields.next() {
+            Some(buf) if !buf.is_empty() => buf.into(),
+            _ => return Err(ParseError::InvalidField(Field::Instrument)),
+        };
+
+        let lane = parse_number(&mut fields, Field::Lane)?;
+        let tile = parse_number(&mut fields, Field::Tile)?;
+        let x = parse_number(&mut fields, Field::X)?;
+        let y = parse_number(&mut fields, Field::Y)?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        Ok(Self {
+            instrument,
+            lane,
+            tile,
+            x,
+            y,
+            index,
+            read_number,
+        })
+    }
+}
+
+impl From<&Legacy> for Definition {
+    fn from(legacy: &Legacy) -> Self {
+        let mut name = format!(
+            "{}:{}:{}:{}:{}",
+            legacy.instrument, legacy.lane, legacy.tile, legacy.x, legacy.y
+        );
+
+        if let Some(index) = &legacy.index {
+            name.push(char::from(INDEX_PREFIX));
+            name.push_str(&index.to_str_lossy());
+        }
+
+        if let Some(read_number) = legacy.read_number {
+            name.push(char::from(READ_NUMBER_PREFIX));
+            name.push_str(&read_number.to_string());
+        }
+
+        Self::new(name, "")
+    }
+}
+
+fn parse_number<'a, I>(fields: &mut I, field: Field) -> Result<u32, ParseError>
+where
+    I: Iterator<Item = &'a [u8]>,
+{
+    fields
+        .next()
+        .ok_or(ParseError::MissingField(field))
+        .and_then(|buf| parse_int(buf).ok_or(ParseError::InvalidField(field)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_definition_for_legacy() {
+        let definition = Definition::new("HWUSI-EAS100R:6:73:941:1973#ACGT/2", "");
+
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Ok(Legacy {
+                instrument: BString::from("HWUSI-EAS100R"),
+                lane: 6,
+                tile: 73,
+                x: 941,
+                y: 1973,
+                index: Some(BString::from("ACGT")),
+                read_number: Some(2),
+            })
+        );
+
+        let definition = Definition::new("HWUSI-EAS100R:6:73:941:1973", "");
+
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Ok(Legacy {
+                instrument: BString::from("HWUSI-EAS100R"),
+                lane: 6,
+                tile: 73,
+                x: 941,
+                y: 1973,
+                index: None,
+                read_number: None,
+            })
+        );
+
+        let definition = Definition::new("HWUSI-EAS100R:6:73:941", "");
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Err(ParseError::MissingField(Field::Y))
+        );
+
+        let definition = Definition::new("HWUSI-EAS100R:6:73:941:1973/x", "");
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Err(ParseError::InvalidField(Field::ReadNumber))
+        );
+
+        let definition = Definition::new("HWUSI-EAS100R:6:73:941:1973#/1", "");
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Err(ParseError::InvalidField(Field::Index))
+        );
+
+        let definition = Definition::new("EAS139:136:FC706VJ:2:2104:15343:197393", "");
+        assert_eq!(
+            Legacy::try_from(&definition),
+            Err(ParseError::InvalidField(Field::Tile))
+        );
+    }
+
+    #[test]
+    fn test_from_legacy_for_definition() {
+        let legacy = Legacy::builder()
+            .set_instrument("HWUSI-EAS100R")
+            .set_lane(6)
+            .set_tile(73)
+            .set_x(941)
+            .set_y(1973)
+            .set_read_number(1)
+            .build();
+
+        assert_eq!(
+            Definition::from(&legacy),
+            Definition::new("HWUSI-EAS100R:6:73:941:1973/1", "")
+        );
+    }
+}
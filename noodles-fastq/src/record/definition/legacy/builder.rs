@@ -0,0 +1,86 @@
+use bstr::BString;
+
+use super::Legacy;
+
+/// A legacy Illumina read name builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    instrument: BString,
+    lane: u32,
+    tile: u32,
+    x: u32,
+    y: u32,
+    index: Option<BString>,
+    read_number: Option<u8>,
+}
+
+impl Builder {
+    /// Sets the instrument ID.
+    pub fn set_instrument<S>(mut self, instrument: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.instrument = instrument.into();
+        self
+    }
+
+    /// Sets the lane number.
+    pub fn set_lane(mut self, lane: u32) -> Self {
+        self.lane = lane;
+        self
+    }
+
+    /// Sets the tile number.
+    pub fn set_tile(mut self, tile: u32) -> Self {
+        self.tile = tile;
+        self
+    }
+
+    /// Sets the x-coordinate of the cluster.
+    pub fn set_x(mut self, x: u32) -> Self {
+        self.x = x;
+        self
+    }
+
+    /// Sets the y-coordinate of the cluster.
+    pub fn set_y(mut self, y: u32) -> Self {
+        self.y = y;
+        self
+    }
+
+    /// Sets the index sequence or sample number.
+    pub fn set_index<S>(mut self, index: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.index = Some(index.into());
+        self
+    }
+
+    /// Sets the read number.
+    pub fn set_read_number(mut self, read_number: u8) -> Self {
+        self.read_number = Some(read_number);
+        self
+    }
+
+    /// Builds a legacy Illumina read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Legacy;
+    /// let name = Legacy::builder().set_read_number(2).build();
+    /// assert_eq!(name.read_number(), Some(2));
+    /// ```
+    pub fn build(self) -> Legacy {
+        Legacy {
+            instrument: self.instrument,
+            lane: self.lane,
+            tile: self.tile,
+            x: self.x,
+            y: self.y,
+            index: self.index,
+            read_number: self.read_number,
+        }
+    }
+}
@@ -0,0 +1,304 @@
+//! Sequence Read Archive (SRA) read name.
+
+mod builder;
+
+pub use self::builder::Builder;
+
+use std::{error, fmt};
+
+use bstr::{BStr, BString, ByteSlice};
+
+use super::{Definition, parse_int};
+
+const DELIMITER: u8 = b'.';
+const LENGTH_PREFIX: &[u8] = b"length=";
+
+/// A Sequence Read Archive (SRA) read name.
+///
+/// This is parsed from a definition with the form
+///
+/// ```text
+/// @<accession>.<spot number>[.<read number>] [<original name>] [length=<length>]
+/// ```
+///
+/// as written by, e.g., `fasterq-dump` and `fastq-dump`.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_fastq::record::{Definition, definition::Sra};
+///
+/// let definition = Definition::new("SRR001666.1", "071112_SLXA-EAS1_s_7:5:1:817:345 length=36");
+/// let name = Sra::try_from(&definition)?;
+///
+/// assert_eq!(name.accession(), "SRR001666");
+/// assert_eq!(name.spot_number(), 1);
+/// assert_eq!(name.original_name(), Some(b"071112_SLXA-EAS1_s_7:5:1:817:345".as_ref().into()));
+/// assert_eq!(name.length(), Some(36));
+///
+/// assert_eq!(Definition::from(&name), definition);
+/// # Ok::<_, noodles_fastq::record::definition::sra::ParseError>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sra {
+    accession: BString,
+    spot_number: u64,
+    read_number: Option<u8>,
+    original_name: Option<BString>,
+    length: Option<usize>,
+}
+
+impl Sra {
+    /// Returns a builder to create an SRA read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Sra;
+    /// let builder = Sra::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the run accession, e.g., `SRR001666`.
+    pub fn accession(&self) -> &BStr {
+        self.accession.as_ref()
+    }
+
+    /// Returns the 1-based spot number in the run.
+    pub fn spot_number(&self) -> u64 {
+        self.spot_number
+    }
+
+    /// Returns the read number of the spot, if any.
+    pub fn read_number(&self) -> Option<u8> {
+        self.read_number
+    }
+
+    /// Returns the original read name, if any.
+    pub fn original_name(&self) -> Option<&BStr> {
+        self.original_name.as_ref().map(|name| name.as_ref())
+    }
+
+    /// Returns the read length, if any.
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+}
+
+/// An SRA read name field.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Field {
+    /// The run accession.
+    Accession,
+    /// The spot number.
+    SpotNumber,
+    /// The read number.
+    ReadNumber,
+    /// The original read name.
+    OriginalName,
+    /// The read length.
+    Length,
+}
+
+/// An error returned when an SRA read name fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// A field is missing.
+    MissingField(Field),
+    /// A field is invalid.
+    InvalidField(Field),
+    /// The name or description has unexpected trailing fields.
+    UnexpectedField,
+}
+
+impl error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field: {field:?}"),
+            Self::InvalidField(field) => write!(f, "invalid field: {field:?}"),
+            Self::UnexpectedField => f.write_str("unexpected field"),
+        }
+    }
+}
+
+impl TryFrom<&Definition> for Sra {
+    type Error = ParseError;
+
+    fn try_from(definition: &Definition) -> Result<Self, Self::Error> {
+        let mut fields = definition.name().split_str(&[DELIMITER]);
+
+        let accession = match fields.next() {
+            Some(buf) if is_valid_accession(buf) => buf.into(),
+            _ => return Err(ParseError::InvalidField(Field::Accession)),
+        };
+
+        let spot_number = fields
+            .next()
+            .ok_or(ParseError::MissingField(Field::SpotNumber))
+            .and_then(|buf| parse_int(buf).ok_or(ParseError::InvalidField(Field::SpotNumber)))?;
+
+        let read_number = fields
+            .next()
+            .map(|buf| parse_int(buf).ok_or(ParseError::InvalidField(Field::ReadNumber)))
+            .transpose()?;
+
+        if fields.next().is_some() {
+            return Err(ParseError::UnexpectedField);
+        }
+
+        let mut original_name = None;
+        let mut length = None;
+
+        let description_fields = definition
+            .description()
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|buf| !buf.is_empty());
+
+        for field in description_fields {
+            if let Some(buf) = field.strip_prefix(LENGTH_PREFIX) {
+                if length.is_some() {
+                    return Err(ParseError::UnexpectedField);
+                }
+
+                let n = parse_int(buf).ok_or(ParseError::InvalidField(Field::Length))?;
+                length = Some(n);
+            } else if original_name.is_none() && length.is_none() {
+                original_name = Some(field.into());
+            } else {
+                return Err(ParseError::UnexpectedField);
+            }
+        }
+
+        Ok(Self {
+            accession,
+            spot_number,
+            read_number,
+            original_name,
+            length,
+        })
+    }
+}
+
+impl From<&Sra> for Definition {
+    fn from(sra: &Sra) -> Self {
+        let mut name = format!("{}.{}", sra.accession, sra.spot_number);
+
+        if let Some(read_number) = sra.read_number {
+            name.push(char::from(DELIMITER));
+            name.push_str(&read_number.to_string());
+        }
+
+        let mut description = Vec::new();
+
+        if let Some(original_name) = &sra.original_name {
+            description.push(original_name.to_string());
+        }
+
+        if let Some(length) = sra.length {
+            description.push(format!("length={length}"));
+        }
+
+        Self::new(name, description.join(" "))
+    }
+}
+
+// An accession is a run prefix (e.g., `SRR`, `ERR`, or `DRR`) followed by digits.
+fn is_valid_accession(buf: &[u8]) -> bool {
+    let i = buf
+        .iter()
+        .position(|b| !b.is_ascii_uppercase())
+        .unwrap_or(buf.len());
+
+    let (prefix, id) = buf.split_at(i);
+    !prefix.is_empty() && !id.is_empty() && id.iter().all(u8::is_ascii_digit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_from_definition_for_sra() {
+        let definition = Definition::new("SRR001666.1.2", "071112_SLXA-EAS1_s_7:5:1:817:345");
+
+        assert_eq!(
+            Sra::try_from(&definition),
+            Ok(Sra {
+                accession: BString::from("SRR001666"),
+                spot_number: 1,
+                read_number: Some(2),
+                original_name: Some(BString::from("071112_SLXA-EAS1_s_7:5:1:817:345")),
+                length: None,
+            })
+        );
+
+        let definition = Definition::new("ERR000001.8", "length=101");
+
+        assert_eq!(
+            Sra::try_from(&definition),
+            Ok(Sra {
+                accession: BString::from("ERR000001"),
+                spot_number: 8,
+                read_number: None,
+                original_name: None,
+                length: Some(101),
+            })
+        );
+
+        let definition = Definition::new("SRR001666", "");
+        assert_eq!(
+            Sra::try_from(&definition),
+            Err(ParseError::MissingField(Field::SpotNumber))
+        );
+
+        let definition = Definition::new("r0.1", "");
+        assert_eq!(
+            Sra::try_from(&definition),
+            Err(ParseError::InvalidField(Field::Accession))
+        );
+
+        let definition = Definition::new("SRR001666.1.x", "");
+        assert_eq!(
+            Sra::try_from(&definition),
+            Err(ParseError::InvalidField(Field::ReadNumber))
+        );
+
+        let definition = Definition::new("SRR001666.1", "length=n");
+        assert_eq!(
+            Sra::try_from(&definition),
+            Err(ParseError::InvalidField(Field::Length))
+        );
+
+        let definition = Definition::new("SRR001666.1", "length=36 r0");
+        assert_eq!(Sra::try_from(&definition), Err(ParseError::UnexpectedField));
+    }
+
+    #[test]
+    fn test_from_sra_for_definition() {
+        let sra = Sra::builder()
+            .set_accession("SRR001666")
+            .set_spot_number(1)
+            .set_read_number(1)
+            .set_length(36)
+            .build();
+
+        assert_eq!(
+            Definition::from(&sra),
+            Definition::new("SRR001666.1.1", "length=36")
+        );
+    }
+
+    #[test]
+    fn test_is_valid_accession() {
+        assert!(is_valid_accession(b"SRR001666"));
+        assert!(is_valid_accession(b"DRR000001"));
+        assert!(!is_valid_accession(b""));
+        assert!(!is_valid_accession(b"SRR"));
+        assert!(!is_valid_accession(b"001666"));
+        assert!(!is_valid_accession(b"SRR0016x6"));
+    }
+}
@@ -0,0 +1,70 @@
+use bstr::BString;
+
+use super::Sra;
+
+/// An SRA read name builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    accession: BString,
+    spot_number: u64,
+    read_number: Option<u8>,
+    original_name: Option<BString>,
+    length: Option<usize>,
+}
+
+impl Builder {
+    /// Sets the run accession.
+    pub fn set_accession<S>(mut self, accession: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.accession = accession.into();
+        self
+    }
+
+    /// Sets the spot number.
+    pub fn set_spot_number(mut self, spot_number: u64) -> Self {
+        self.spot_number = spot_number;
+        self
+    }
+
+    /// Sets the read number.
+    pub fn set_read_number(mut self, read_number: u8) -> Self {
+        self.read_number = Some(read_number);
+        self
+    }
+
+    /// Sets the original read name.
+    pub fn set_original_name<S>(mut self, original_name: S) -> Self
+    where
+        S: Into<BString>,
+    {
+        self.original_name = Some(original_name.into());
+        self
+    }
+
+    /// Sets the read length.
+    pub fn set_length(mut self, length: usize) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Builds an SRA read name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_fastq::record::definition::Sra;
+    /// let name = Sra::builder().set_spot_number(8).build();
+    /// assert_eq!(name.spot_number(), 8);
+    /// ```
+    pub fn build(self) -> Sra {
+        Sra {
+            accession: self.accession,
+            spot_number: self.spot_number,
+            read_number: self.read_number,
+            original_name: self.original_name,
+            length: self.length,
+        }
+    }
+}
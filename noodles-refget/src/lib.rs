@@ -1,6 +1,8 @@
 //! **noodles-refget** is a refget 2.0 client.
 
 mod client;
+#[cfg(feature = "fasta")]
+pub mod repository;
 pub mod sequence;
 
 pub use self::{client::Client, sequence::Sequence};
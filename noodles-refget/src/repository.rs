@@ -0,0 +1,217 @@
+//! Refget-backed FASTA sequence repository adapter.
+
+mod builder;
+
+use std::{collections::HashMap, io, path::PathBuf};
+
+use noodles_fasta::{
+    self as fasta,
+    record::{Definition, Sequence},
+    repository::ref_cache,
+};
+use tokio::runtime::Runtime;
+
+pub use self::builder::Builder;
+use crate::Client;
+
+/// A refget-backed FASTA sequence repository adapter.
+///
+/// Reference sequence names are resolved to refget sequence IDs, e.g., MD5 checksums, which are
+/// then fetched using a refget client. When a cache is set, fetched sequences are stored using an
+/// htslib-compatible `REF_CACHE` layout, and subsequent lookups are read from disk without making
+/// a request.
+///
+/// Requests are sent using a dedicated runtime, so this must not be used from within an
+/// asynchronous context.
+///
+/// # Examples
+///
+/// ```no_run
+/// use noodles_fasta as fasta;
+/// use noodles_refget::{self as refget, repository::Adapter};
+///
+/// let client = refget::Client::new("https://www.ebi.ac.uk/ena/cram/".parse()?);
+///
+/// let adapter = Adapter::builder()
+///     .add_sequence_id("sq0", "d7eba311421bbc9d3ada44709dd61534")
+///     .set_cache("/tmp/hts-ref/%2s/%2s/%s")
+///     .build_from_client(client)?;
+///
+/// let repository = fasta::Repository::new(adapter);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Adapter {
+    client: Client,
+    runtime: Runtime,
+    sequence_ids: HashMap<Vec<u8>, String>,
+    cache: Option<String>,
+}
+
+impl Adapter {
+    /// Returns a builder to create an adapter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Adapter;
+    /// let builder = Adapter::builder();
+    /// ```
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    fn cache_path(&self, id: &str) -> Option<PathBuf> {
+        self.cache
+            .as_deref()
+            .map(|template| ref_cache::expand_path(template, id))
+    }
+
+    fn fetch(&self, id: &str) -> io::Result<Vec<u8>> {
+        let sequence = self
+            .runtime
+            .block_on(self.client.sequence(id).send())
+            .map_err(io::Error::other)?;
+
+        Ok(sequence.sequence().to_vec())
+    }
+}
+
+impl fasta::repository::Adapter for Adapter {
+    fn get(&mut self, name: &[u8]) -> Option<io::Result<fasta::Record>> {
+        let id = self.sequence_ids.get(name)?;
+
+        let result = match self.cache_path(id) {
+            Some(path) => {
+                ref_cache::read(&path).and_then(|cached_sequence| match cached_sequence {
+                    Some(sequence) => Ok(sequence),
+                    None => {
+                        let sequence = self.fetch(id)?;
+                        ref_cache::write(&path, &sequence)?;
+                        Ok(sequence)
+                    }
+                })
+            }
+            None => self.fetch(id),
+        };
+
+        Some(result.map(|sequence| {
+            fasta::Record::new(Definition::new(name, None), Sequence::from(sequence))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        process, thread,
+    };
+
+    use noodles_fasta::repository::Adapter as _;
+
+    use super::*;
+
+    const ID: &str = "f1f8f4bf413b16ad135722aa4591043e";
+
+    // Serves the given number of requests, responding with `ACGT` for the sequence `ID`.
+    fn spawn_server(request_count: usize) -> io::Result<(url::Url, thread::JoinHandle<()>)> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming().take(request_count) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+
+                let response = if request_line.starts_with(&format!("GET /sequence/{ID} ")) {
+                    "HTTP/1.1 200 OK\r\ncontent-length: 4\r\nconnection: close\r\n\r\nACGT"
+                } else {
+                    "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                };
+
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let base_url = format!("http://{addr}/")
+            .parse()
+            .map_err(io::Error::other)?;
+
+        Ok((base_url, handle))
+    }
+
+    #[test]
+    fn test_get() -> Result<(), Box<dyn std::error::Error>> {
+        let (base_url, handle) = spawn_server(2)?;
+
+        let mut adapter = Adapter::builder()
+            .add_sequence_id("sq0", ID)
+            .add_sequence_id("sq1", "00000000000000000000000000000000")
+            .build_from_client(Client::new(base_url))?;
+
+        let record = adapter.get(b"sq0").transpose()?;
+        assert_eq!(
+            record.map(|r| r.sequence().as_ref().to_vec()),
+            Some(b"ACGT".to_vec())
+        );
+
+        assert!(adapter.get(b"sq1").is_some_and(|result| result.is_err()));
+        assert!(adapter.get(b"sq2").is_none());
+
+        handle.join().unwrap();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_with_cache() -> Result<(), Box<dyn std::error::Error>> {
+        let cache_dir = env::temp_dir().join(format!("noodles-refget-{}", process::id()));
+        let template = format!("{}/%2s/%2s/%s", cache_dir.display());
+
+        let (base_url, handle) = spawn_server(1)?;
+
+        let mut adapter = Adapter::builder()
+            .add_sequence_id("sq0", ID)
+            .set_cache(template.clone())
+            .build_from_client(Client::new(base_url))?;
+
+        assert!(adapter.get(b"sq0").transpose()?.is_some());
+        handle.join().unwrap();
+
+        let cache_path = cache_dir.join("f1").join("f8").join(&ID[4..]);
+        assert_eq!(fs::read(&cache_path)?, b"ACGT");
+
+        // The server is no longer running, so this must be read from the cache.
+        let (base_url, handle) = spawn_server(0)?;
+        handle.join().unwrap();
+
+        let mut adapter = Adapter::builder()
+            .add_sequence_id("sq0", ID)
+            .set_cache(template)
+            .build_from_client(Client::new(base_url))?;
+
+        let record = adapter.get(b"sq0").transpose()?;
+        assert_eq!(
+            record.map(|r| r.sequence().as_ref().to_vec()),
+            Some(b"ACGT".to_vec())
+        );
+
+        fs::remove_dir_all(&cache_dir)?;
+
+        Ok(())
+    }
+}
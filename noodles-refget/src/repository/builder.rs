@@ -0,0 +1,156 @@
+use std::{collections::HashMap, io};
+
+use noodles_sam::{self as sam, header::record::value::map::reference_sequence::tag};
+
+use super::Adapter;
+use crate::Client;
+
+/// A refget-backed FASTA sequence repository adapter builder.
+#[derive(Debug, Default)]
+pub struct Builder {
+    sequence_ids: HashMap<Vec<u8>, String>,
+    cache: Option<String>,
+}
+
+impl Builder {
+    /// Adds a mapping from a reference sequence name to a refget sequence ID.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Adapter;
+    /// let builder = Adapter::builder().add_sequence_id("sq0", "d7eba311421bbc9d3ada44709dd61534");
+    /// ```
+    pub fn add_sequence_id<N, I>(mut self, name: N, id: I) -> Self
+    where
+        N: Into<Vec<u8>>,
+        I: Into<String>,
+    {
+        self.sequence_ids.insert(name.into(), id.into());
+        self
+    }
+
+    /// Adds the reference sequences in a SAM header using their MD5 checksums (`M5`) as refget
+    /// sequence IDs.
+    ///
+    /// Reference sequences without an MD5 checksum are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::num::NonZeroUsize;
+    ///
+    /// use noodles_refget::repository::Adapter;
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{
+    ///         map::{reference_sequence::tag::MD5_CHECKSUM, ReferenceSequence},
+    ///         Map,
+    ///     },
+    /// };
+    ///
+    /// let reference_sequence = Map::<ReferenceSequence>::builder()
+    ///     .set_length(NonZeroUsize::try_from(8)?)
+    ///     .insert(MD5_CHECKSUM, "d7eba311421bbc9d3ada44709dd61534")
+    ///     .build()?;
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_reference_sequence("sq0", reference_sequence)
+    ///     .build();
+    ///
+    /// let builder = Adapter::builder().add_sequence_ids_from_header(&header);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn add_sequence_ids_from_header(mut self, header: &sam::Header) -> Self {
+        for (name, reference_sequence) in header.reference_sequences() {
+            if let Some(md5_checksum) = reference_sequence.other_fields().get(&tag::MD5_CHECKSUM) {
+                let id = md5_checksum.to_string().to_ascii_lowercase();
+                self.sequence_ids.insert(name.to_vec(), id);
+            }
+        }
+
+        self
+    }
+
+    /// Sets the cache path template.
+    ///
+    /// This uses the same format as htslib's `REF_CACHE`, where `%Ns` is replaced by the next `N`
+    /// characters of the sequence ID and `%s`, by the remaining characters, e.g.,
+    /// `$HOME/.cache/hts-ref/%2s/%2s/%s`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::repository::Adapter;
+    /// let builder = Adapter::builder().set_cache("/tmp/hts-ref/%2s/%2s/%s");
+    /// ```
+    pub fn set_cache<S>(mut self, template: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.cache = Some(template.into());
+        self
+    }
+
+    /// Builds an adapter from a refget client.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_refget::{self as refget, repository::Adapter};
+    /// let client = refget::Client::new("https://localhost/".parse()?);
+    /// let adapter = Adapter::builder().build_from_client(client)?;
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn build_from_client(self, client: Client) -> io::Result<Adapter> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+
+        Ok(Adapter {
+            client,
+            runtime,
+            sequence_ids: self.sequence_ids,
+            cache: self.cache,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use sam::header::record::value::{Map, map::ReferenceSequence};
+
+    use super::*;
+
+    #[test]
+    fn test_add_sequence_ids_from_header() -> Result<(), Box<dyn std::error::Error>> {
+        let length = NonZeroUsize::try_from(8)?;
+
+        let header = sam::Header::builder()
+            .add_reference_sequence(
+                "sq0",
+                Map::<ReferenceSequence>::builder()
+                    .set_length(length)
+                    .insert(tag::MD5_CHECKSUM, "D7EBA311421BBC9D3ADA44709DD61534")
+                    .build()?,
+            )
+            .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(length))
+            .build();
+
+        let builder = Builder::default().add_sequence_ids_from_header(&header);
+
+        assert_eq!(
+            builder.sequence_ids,
+            [(
+                b"sq0".to_vec(),
+                String::from("d7eba311421bbc9d3ada44709dd61534")
+            )]
+            .into_iter()
+            .collect()
+        );
+
+        Ok(())
+    }
+}
@@ -3,5 +3,6 @@
 pub mod io;
 pub mod record;
 pub mod record_buf;
+pub mod validation;
 
 pub use self::{record::Record, record_buf::RecordBuf};
@@ -1,5 +1,6 @@
 //! Alignment record CIGAR operations.
 
+pub mod aligned_pairs;
 pub mod op;
 
 #[doc(hidden)]
@@ -7,7 +8,10 @@ pub mod iter;
 
 use std::io;
 
-pub use self::op::Op;
+use noodles_core::Position;
+
+use self::op::Kind;
+pub use self::{aligned_pairs::AlignedPairs, op::Op};
 
 /// Alignment record CIGAR operations.
 pub trait Cigar {
@@ -49,6 +53,198 @@ pub trait Cigar {
 
         Ok(length)
     }
+
+    /// Calculates the query alignment span.
+    ///
+    /// This is the number of read bases that are part of the alignment, i.e., the read length
+    /// excluding soft clips.
+    fn query_alignment_span(&self) -> io::Result<usize> {
+        let mut span = 0;
+
+        for result in self.iter() {
+            let op = result?;
+
+            if op.kind().consumes_read() && op.kind() != Kind::SoftClip {
+                span += op.len();
+            }
+        }
+
+        Ok(span)
+    }
+
+    /// Calculates the leading and trailing soft clip lengths.
+    ///
+    /// Hard clips are skipped when finding soft clips at either end.
+    fn soft_clip_lengths(&self) -> io::Result<(usize, usize)> {
+        let ops: Vec<_> = self.iter().collect::<io::Result<_>>()?;
+
+        let leading = clip_length(ops.iter(), |kind| kind == Kind::SoftClip);
+        let trailing = clip_length(ops.iter().rev(), |kind| kind == Kind::SoftClip);
+
+        Ok((leading, trailing))
+    }
+
+    /// Calculates the unclipped start position.
+    ///
+    /// This is the alignment start moved left by the lengths of the leading soft and hard clips.
+    /// This returns `None` if the unclipped start is before the start of the reference sequence.
+    fn unclipped_start(&self, alignment_start: Position) -> io::Result<Option<Position>> {
+        let ops: Vec<_> = self.iter().collect::<io::Result<_>>()?;
+        let len = clip_length(ops.iter(), is_clip);
+
+        Ok(usize::from(alignment_start)
+            .checked_sub(len)
+            .and_then(Position::new))
+    }
+
+    /// Calculates the unclipped end position.
+    ///
+    /// This is the alignment end moved right by the lengths of the trailing soft and hard clips,
+    /// i.e., the alignment start plus the reference span and trailing clip lengths minus 1.
+    fn unclipped_end(&self, alignment_start: Position) -> io::Result<Position> {
+        let ops: Vec<_> = self.iter().collect::<io::Result<_>>()?;
+
+        let span: usize = ops
+            .iter()
+            .filter(|op| op.kind().consumes_reference())
+            .map(|op| op.len())
+            .sum();
+
+        let len = clip_length(ops.iter().rev(), is_clip);
+
+        usize::from(alignment_start)
+            .checked_add(span)
+            .and_then(|n| n.checked_add(len))
+            .and_then(|n| n.checked_sub(1))
+            .and_then(Position::new)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid unclipped end"))
+    }
+
+    /// Returns an iterator over aligned pairs of read and reference positions.
+    ///
+    /// Read positions are 0-based, and reference positions are 1-based, starting at the given
+    /// alignment start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{op::Kind, Op}, Cigar as _},
+    ///     record_buf::Cigar,
+    /// };
+    ///
+    /// let cigar: Cigar = [Op::new(Kind::Match, 1), Op::new(Kind::Deletion, 1)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let pairs: Vec<_> = cigar
+    ///     .aligned_pairs(Position::try_from(8)?)
+    ///     .map(|result| result.map(|pair| (pair.read_position(), pair.reference_position())))
+    ///     .collect::<std::io::Result<_>>()?;
+    ///
+    /// assert_eq!(pairs, [(Some(0), Position::new(8)), (None, Position::new(9))]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn aligned_pairs(&self, alignment_start: Position) -> AlignedPairs<'_> {
+        AlignedPairs::new(self.iter(), alignment_start)
+    }
+
+    /// Maps a read position to a reference position.
+    ///
+    /// The read position is 0-based. This returns `None` if the read position is not aligned to
+    /// the reference sequence, e.g., it is in an insertion or soft clip, or it is out of range.
+    fn reference_position(
+        &self,
+        alignment_start: Position,
+        read_position: usize,
+    ) -> io::Result<Option<Position>> {
+        let mut read_start = 0;
+        let mut reference_start = usize::from(alignment_start);
+
+        for result in self.iter() {
+            let op = result?;
+            let kind = op.kind();
+
+            let read_end = if kind.consumes_read() {
+                read_start + op.len()
+            } else {
+                read_start
+            };
+
+            if (read_start..read_end).contains(&read_position) {
+                return Ok(if kind.consumes_reference() {
+                    Position::new(reference_start + (read_position - read_start))
+                } else {
+                    None
+                });
+            }
+
+            read_start = read_end;
+
+            if kind.consumes_reference() {
+                reference_start += op.len();
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Maps a reference position to a read position.
+    ///
+    /// The returned read position is 0-based. This returns `None` if the reference position is
+    /// not covered by a read base, e.g., it is in a deletion or skipped region, or it is outside
+    /// the alignment.
+    fn read_position(
+        &self,
+        alignment_start: Position,
+        reference_position: Position,
+    ) -> io::Result<Option<usize>> {
+        let reference_position = usize::from(reference_position);
+
+        let mut read_start = 0;
+        let mut reference_start = usize::from(alignment_start);
+
+        for result in self.iter() {
+            let op = result?;
+            let kind = op.kind();
+
+            let reference_end = if kind.consumes_reference() {
+                reference_start + op.len()
+            } else {
+                reference_start
+            };
+
+            if (reference_start..reference_end).contains(&reference_position) {
+                return Ok(kind
+                    .consumes_read()
+                    .then(|| read_start + (reference_position - reference_start)));
+            }
+
+            reference_start = reference_end;
+
+            if kind.consumes_read() {
+                read_start += op.len();
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+fn is_clip(kind: Kind) -> bool {
+    matches!(kind, Kind::SoftClip | Kind::HardClip)
+}
+
+fn clip_length<'a, I, F>(ops: I, f: F) -> usize
+where
+    I: Iterator<Item = &'a Op>,
+    F: Fn(Kind) -> bool,
+{
+    ops.take_while(|op| is_clip(op.kind()))
+        .filter(|op| f(op.kind()))
+        .map(|op| op.len())
+        .sum()
 }
 
 impl<'a> IntoIterator for &'a dyn Cigar {
@@ -77,7 +273,6 @@ impl Cigar for Box<dyn Cigar + '_> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::alignment::record::cigar::op::Kind;
 
     struct T(Vec<Op>);
 
@@ -132,4 +327,172 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_query_alignment_span() -> io::Result<()> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 8),
+            Op::new(Kind::Insertion, 2),
+            Op::new(Kind::Deletion, 4),
+            Op::new(Kind::Match, 5),
+            Op::new(Kind::SoftClip, 1),
+        ]);
+
+        assert_eq!(cigar.query_alignment_span()?, 15);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_soft_clip_lengths() -> io::Result<()> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 8),
+            Op::new(Kind::SoftClip, 1),
+        ]);
+
+        assert_eq!(cigar.soft_clip_lengths()?, (3, 1));
+
+        let cigar: &dyn Cigar = &T(vec![Op::new(Kind::Match, 8)]);
+        assert_eq!(cigar.soft_clip_lengths()?, (0, 0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclipped_start() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 8),
+            Op::new(Kind::SoftClip, 1),
+        ]);
+
+        let alignment_start = Position::try_from(8)?;
+        assert_eq!(cigar.unclipped_start(alignment_start)?, Position::new(3));
+
+        let alignment_start = Position::try_from(5)?;
+        assert!(cigar.unclipped_start(alignment_start)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclipped_end() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::SoftClip, 3),
+            Op::new(Kind::Match, 8),
+            Op::new(Kind::Deletion, 2),
+            Op::new(Kind::SoftClip, 1),
+            Op::new(Kind::HardClip, 4),
+        ]);
+
+        let alignment_start = Position::try_from(8)?;
+        assert_eq!(
+            cigar.unclipped_end(alignment_start)?,
+            Position::try_from(22)?
+        );
+
+        // A CIGAR that consumes no reference.
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::Insertion, 5),
+            Op::new(Kind::SoftClip, 5),
+        ]);
+        assert_eq!(
+            cigar.unclipped_end(alignment_start)?,
+            Position::try_from(12)?
+        );
+
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::SoftClip, 5),
+            Op::new(Kind::Insertion, 5),
+        ]);
+        assert!(matches!(
+            cigar.unclipped_end(Position::MIN),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reference_position() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::SoftClip, 1),
+            Op::new(Kind::Match, 2),
+            Op::new(Kind::Insertion, 1),
+            Op::new(Kind::Deletion, 2),
+            Op::new(Kind::Match, 2),
+        ]);
+
+        let alignment_start = Position::try_from(8)?;
+
+        assert!(cigar.reference_position(alignment_start, 0)?.is_none());
+        assert_eq!(
+            cigar.reference_position(alignment_start, 1)?,
+            Position::new(8)
+        );
+        assert_eq!(
+            cigar.reference_position(alignment_start, 2)?,
+            Position::new(9)
+        );
+        assert!(cigar.reference_position(alignment_start, 3)?.is_none());
+        assert_eq!(
+            cigar.reference_position(alignment_start, 4)?,
+            Position::new(12)
+        );
+        assert_eq!(
+            cigar.reference_position(alignment_start, 5)?,
+            Position::new(13)
+        );
+        assert!(cigar.reference_position(alignment_start, 6)?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_position() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: &dyn Cigar = &T(vec![
+            Op::new(Kind::SoftClip, 1),
+            Op::new(Kind::Match, 2),
+            Op::new(Kind::Insertion, 1),
+            Op::new(Kind::Deletion, 2),
+            Op::new(Kind::Match, 2),
+        ]);
+
+        let alignment_start = Position::try_from(8)?;
+
+        assert!(
+            cigar
+                .read_position(alignment_start, Position::try_from(7)?)?
+                .is_none()
+        );
+        assert_eq!(
+            cigar.read_position(alignment_start, Position::try_from(8)?)?,
+            Some(1)
+        );
+        assert_eq!(
+            cigar.read_position(alignment_start, Position::try_from(9)?)?,
+            Some(2)
+        );
+        assert!(
+            cigar
+                .read_position(alignment_start, Position::try_from(10)?)?
+                .is_none()
+        );
+        assert_eq!(
+            cigar.read_position(alignment_start, Position::try_from(12)?)?,
+            Some(4)
+        );
+        assert!(
+            cigar
+                .read_position(alignment_start, Position::try_from(14)?)?
+                .is_none()
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,333 @@
+//! Alignment record CIGAR aligned pairs.
+
+use std::io;
+
+use noodles_core::Position;
+
+use super::{Op, op::Kind};
+use crate::alignment::record::Sequence;
+
+/// A read position paired with a reference position.
+///
+/// Read positions are 0-based indices into the read sequence. Reference positions are 1-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AlignedPair {
+    kind: Kind,
+    read_position: Option<usize>,
+    reference_position: Option<Position>,
+}
+
+impl AlignedPair {
+    /// Creates an aligned pair.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::record::cigar::{aligned_pairs::AlignedPair, op::Kind};
+    /// let pair = AlignedPair::new(Kind::Match, Some(0), Some(Position::MIN));
+    /// ```
+    pub const fn new(
+        kind: Kind,
+        read_position: Option<usize>,
+        reference_position: Option<Position>,
+    ) -> Self {
+        Self {
+            kind,
+            read_position,
+            reference_position,
+        }
+    }
+
+    /// Returns the kind of the CIGAR operation the pair is part of.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns the read position.
+    ///
+    /// This is 0-based and `None` for deletions and skipped regions.
+    pub fn read_position(&self) -> Option<usize> {
+        self.read_position
+    }
+
+    /// Returns the reference position.
+    ///
+    /// This is 1-based and `None` for insertions and soft clips.
+    pub fn reference_position(&self) -> Option<Position> {
+        self.reference_position
+    }
+}
+
+/// An iterator over aligned pairs of a CIGAR.
+///
+/// This is created by calling [`super::Cigar::aligned_pairs`]. Hard clips and padding do not
+/// produce any pairs.
+pub struct AlignedPairs<'a> {
+    ops: Box<dyn Iterator<Item = io::Result<Op>> + 'a>,
+    op: Option<(Kind, usize)>,
+    read_position: usize,
+    reference_position: Position,
+}
+
+impl<'a> AlignedPairs<'a> {
+    pub(super) fn new(
+        ops: Box<dyn Iterator<Item = io::Result<Op>> + 'a>,
+        alignment_start: Position,
+    ) -> Self {
+        Self {
+            ops,
+            op: None,
+            read_position: 0,
+            reference_position: alignment_start,
+        }
+    }
+
+    /// Compares read bases to reference bases for each aligned pair.
+    ///
+    /// `reference_sequence` is the entire reference sequence, i.e., reference position 1 is at
+    /// index 0. Pairs that do not have both a read and reference position do not have a
+    /// comparison.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::alignment::{
+    ///     record::{cigar::{aligned_pairs::BaseComparison, op::Kind, Op}, Cigar as _},
+    ///     record_buf::{Cigar, Sequence},
+    /// };
+    ///
+    /// let cigar: Cigar = [Op::new(Kind::Match, 2)].into_iter().collect();
+    /// let sequence = Sequence::from(b"AT");
+    ///
+    /// let comparisons: Vec<_> = cigar
+    ///     .aligned_pairs(Position::MIN)
+    ///     .with_reference_sequence(&sequence, b"AC")
+    ///     .map(|result| result.map(|(_, comparison)| comparison))
+    ///     .collect::<std::io::Result<_>>()?;
+    ///
+    /// assert_eq!(
+    ///     comparisons,
+    ///     [Some(BaseComparison::Match), Some(BaseComparison::Mismatch)]
+    /// );
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn with_reference_sequence<S>(
+        self,
+        sequence: S,
+        reference_sequence: &'a [u8],
+    ) -> WithReferenceSequence<'a, S>
+    where
+        S: Sequence,
+    {
+        WithReferenceSequence {
+            pairs: self,
+            sequence,
+            reference_sequence,
+        }
+    }
+
+    fn next_op(&mut self) -> io::Result<Option<(Kind, usize)>> {
+        loop {
+            match self.op.take() {
+                Some((_, 0)) | None => {}
+                Some(op) => return Ok(Some(op)),
+            }
+
+            match self.ops.next().transpose()? {
+                Some(op) => self.op = Some((op.kind(), op.len())),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Iterator for AlignedPairs<'_> {
+    type Item = io::Result<AlignedPair>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (kind, len) = match self.next_op() {
+                Ok(Some(op)) => op,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+
+            if matches!(kind, Kind::HardClip | Kind::Pad) {
+                continue;
+            }
+
+            self.op = Some((kind, len - 1));
+
+            let read_position = kind.consumes_read().then_some(self.read_position);
+
+            let reference_position = kind.consumes_reference().then_some(self.reference_position);
+
+            if read_position.is_some() {
+                self.read_position += 1;
+            }
+
+            if let Some(position) = reference_position {
+                match position.checked_add(1) {
+                    Some(next_position) => self.reference_position = next_position,
+                    None => {
+                        return Some(Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "reference position overflow",
+                        )));
+                    }
+                }
+            }
+
+            return Some(Ok(AlignedPair::new(
+                kind,
+                read_position,
+                reference_position,
+            )));
+        }
+    }
+}
+
+/// The result of comparing a read base to a reference base.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BaseComparison {
+    /// The bases are equal.
+    Match,
+    /// The bases differ.
+    Mismatch,
+}
+
+/// An iterator over aligned pairs with read and reference base comparisons.
+///
+/// This is created by calling [`AlignedPairs::with_reference_sequence`].
+pub struct WithReferenceSequence<'a, S> {
+    pairs: AlignedPairs<'a>,
+    sequence: S,
+    reference_sequence: &'a [u8],
+}
+
+impl<S> Iterator for WithReferenceSequence<'_, S>
+where
+    S: Sequence,
+{
+    type Item = io::Result<(AlignedPair, Option<BaseComparison>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pair = match self.pairs.next()? {
+            Ok(pair) => pair,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let (Some(read_position), Some(reference_position)) =
+            (pair.read_position(), pair.reference_position())
+        else {
+            return Some(Ok((pair, None)));
+        };
+
+        let Some(read_base) = self.sequence.get(read_position) else {
+            return Some(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "read position out of bounds",
+            )));
+        };
+
+        let Some(&reference_base) = self
+            .reference_sequence
+            .get(usize::from(reference_position) - 1)
+        else {
+            return Some(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "reference position out of bounds",
+            )));
+        };
+
+        let comparison = if read_base == b'=' || read_base.eq_ignore_ascii_case(&reference_base) {
+            BaseComparison::Match
+        } else {
+            BaseComparison::Mismatch
+        };
+
+        Some(Ok((pair, Some(comparison))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::{record::Cigar as _, record_buf::Cigar};
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        let cigar: Cigar = [
+            Op::new(Kind::HardClip, 2),
+            Op::new(Kind::SoftClip, 1),
+            Op::new(Kind::Match, 2),
+            Op::new(Kind::Insertion, 1),
+            Op::new(Kind::Deletion, 1),
+            Op::new(Kind::Skip, 1),
+            Op::new(Kind::SequenceMatch, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let start = Position::try_from(8)?;
+        let actual: Vec<_> = cigar.aligned_pairs(start).collect::<io::Result<_>>()?;
+
+        let expected = [
+            AlignedPair::new(Kind::SoftClip, Some(0), None),
+            AlignedPair::new(Kind::Match, Some(1), Position::new(8)),
+            AlignedPair::new(Kind::Match, Some(2), Position::new(9)),
+            AlignedPair::new(Kind::Insertion, Some(3), None),
+            AlignedPair::new(Kind::Deletion, None, Position::new(10)),
+            AlignedPair::new(Kind::Skip, None, Position::new(11)),
+            AlignedPair::new(Kind::SequenceMatch, Some(4), Position::new(12)),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_reference_sequence() -> io::Result<()> {
+        use crate::alignment::record_buf::Sequence;
+
+        let cigar: Cigar = [
+            Op::new(Kind::SoftClip, 1),
+            Op::new(Kind::Match, 3),
+            Op::new(Kind::Deletion, 1),
+        ]
+        .into_iter()
+        .collect();
+
+        let sequence = Sequence::from(b"TAcG");
+
+        let actual: Vec<_> = cigar
+            .aligned_pairs(Position::MIN)
+            .with_reference_sequence(&sequence, b"ACTTA")
+            .map(|result| result.map(|(_, comparison)| comparison))
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            None,
+            Some(BaseComparison::Match),
+            Some(BaseComparison::Match),
+            Some(BaseComparison::Mismatch),
+            None,
+        ];
+
+        assert_eq!(actual, expected);
+
+        let mut iter = cigar
+            .aligned_pairs(Position::MIN)
+            .with_reference_sequence(&sequence, b"AC");
+
+        assert!(matches!(
+            iter.nth(3),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+}
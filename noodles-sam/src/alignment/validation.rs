@@ -0,0 +1,614 @@
+//! Alignment record validation.
+//!
+//! A [`Validator`] checks alignment records against a SAM header and the constraints of the SAM
+//! specification, e.g., reference sequence IDs and positions are in range, the sequence and
+//! quality scores lengths are consistent with the CIGAR, and read groups and programs referenced
+//! in the data are defined in the header.
+
+mod issue;
+mod report;
+
+pub use self::{
+    issue::{Issue, Severity},
+    report::Report,
+};
+
+use std::io;
+
+use bstr::BString;
+
+use super::{
+    Record,
+    record::{
+        Flags,
+        data::field::{Tag, Type, Value},
+    },
+};
+use crate::Header;
+
+/// The strictness of a validator.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strictness {
+    /// Only errors are reported.
+    Lenient,
+    /// Both errors and warnings are reported.
+    #[default]
+    Strict,
+}
+
+/// An alignment record validator.
+pub struct Validator<'h> {
+    header: &'h Header,
+    strictness: Strictness,
+}
+
+impl<'h> Validator<'h> {
+    /// Creates an alignment record validator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::validation::Validator};
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header);
+    /// ```
+    pub fn new(header: &'h Header) -> Self {
+        Self {
+            header,
+            strictness: Strictness::default(),
+        }
+    }
+
+    /// Sets the strictness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::validation::{Strictness, Validator},
+    /// };
+    ///
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header).set_strictness(Strictness::Lenient);
+    /// ```
+    pub fn set_strictness(mut self, strictness: Strictness) -> Self {
+        self.strictness = strictness;
+        self
+    }
+
+    /// Validates an alignment record.
+    ///
+    /// This returns the list of issues found. An empty list means the record is valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{
+    ///         record::data::field::Tag,
+    ///         record_buf::data::field::Value,
+    ///         validation::{Issue, Validator},
+    ///         RecordBuf,
+    ///     },
+    /// };
+    ///
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header);
+    ///
+    /// let record = RecordBuf::default();
+    /// assert!(validator.validate(&record)?.is_empty());
+    ///
+    /// let record = RecordBuf::builder()
+    ///     .set_data([(Tag::READ_GROUP, Value::from("rg0"))].into_iter().collect())
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     validator.validate(&record)?,
+    ///     [Issue::MissingReadGroup("rg0".into())]
+    /// );
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn validate<R>(&self, record: &R) -> io::Result<Vec<Issue>>
+    where
+        R: Record + ?Sized,
+    {
+        let mut issues = Vec::new();
+
+        let flags = record.flags()?;
+
+        validate_positions(self.header, record, flags, &mut issues)?;
+        validate_mate_positions(self.header, record, &mut issues)?;
+        validate_flags(record, flags, &mut issues);
+        validate_lengths(record, &mut issues)?;
+        validate_data(self.header, record, &mut issues)?;
+
+        if self.strictness == Strictness::Lenient {
+            issues.retain(|issue| issue.severity() == Severity::Error);
+        }
+
+        Ok(issues)
+    }
+
+    /// Validates a list of alignment records and aggregates the issues into a report.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     alignment::{validation::Validator, RecordBuf},
+    /// };
+    ///
+    /// let header = sam::Header::default();
+    /// let validator = Validator::new(&header);
+    ///
+    /// let records = [Ok(RecordBuf::default()), Ok(RecordBuf::default())];
+    /// let report = validator.validate_all(records)?;
+    ///
+    /// assert_eq!(report.record_count(), 2);
+    /// assert!(report.is_valid());
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn validate_all<I, R>(&self, records: I) -> io::Result<Report>
+    where
+        I: IntoIterator<Item = io::Result<R>>,
+        R: Record,
+    {
+        let mut report = Report::default();
+
+        for result in records {
+            let record = result?;
+            let issues = self.validate(&record)?;
+            report.add(issues);
+        }
+
+        Ok(report)
+    }
+}
+
+fn validate_positions<R>(
+    header: &Header,
+    record: &R,
+    flags: Flags,
+    issues: &mut Vec<Issue>,
+) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    // A reference sequence name that is not in the header fails to resolve to an ID.
+    let Ok(reference_sequence_id) = record.reference_sequence_id(header).transpose() else {
+        issues.push(Issue::MissingReferenceSequence);
+        return Ok(());
+    };
+
+    let alignment_start = record.alignment_start().transpose()?;
+
+    if !flags.is_unmapped() && (reference_sequence_id.is_none() || alignment_start.is_none()) {
+        issues.push(Issue::MissingPosition);
+    }
+
+    let Some(id) = reference_sequence_id else {
+        return Ok(());
+    };
+
+    let Some(length) = reference_sequence_length(header, id) else {
+        issues.push(Issue::InvalidReferenceSequenceId(id));
+        return Ok(());
+    };
+
+    let Some(position) = alignment_start else {
+        return Ok(());
+    };
+
+    if usize::from(position) > length {
+        issues.push(Issue::AlignmentStartOutOfRange { position, length });
+    } else if !flags.is_unmapped() {
+        if let Some(position) = record.alignment_end().transpose()? {
+            if usize::from(position) > length {
+                issues.push(Issue::AlignmentEndOutOfRange { position, length });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_mate_positions<R>(
+    header: &Header,
+    record: &R,
+    issues: &mut Vec<Issue>,
+) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    let id = match record.mate_reference_sequence_id(header).transpose() {
+        Ok(Some(id)) => id,
+        Ok(None) => return Ok(()),
+        Err(_) => {
+            issues.push(Issue::MissingMateReferenceSequence);
+            return Ok(());
+        }
+    };
+
+    let Some(length) = reference_sequence_length(header, id) else {
+        issues.push(Issue::InvalidMateReferenceSequenceId(id));
+        return Ok(());
+    };
+
+    if let Some(position) = record.mate_alignment_start().transpose()? {
+        if usize::from(position) > length {
+            issues.push(Issue::MateAlignmentStartOutOfRange { position, length });
+        }
+    }
+
+    Ok(())
+}
+
+fn reference_sequence_length(header: &Header, id: usize) -> Option<usize> {
+    header
+        .reference_sequences()
+        .get_index(id)
+        .map(|(_, reference_sequence)| reference_sequence.length().get())
+}
+
+fn validate_flags<R>(record: &R, flags: Flags, issues: &mut Vec<Issue>)
+where
+    R: Record + ?Sized,
+{
+    // § 1.4.2 "_FLAG_" (2024-11-06): "If 0x1 is unset, no assumptions can be made about 0x2,
+    // 0x8, 0x20, 0x40 and 0x80."
+    const SEGMENT_FLAGS: Flags = Flags::PROPERLY_SEGMENTED
+        .union(Flags::MATE_UNMAPPED)
+        .union(Flags::MATE_REVERSE_COMPLEMENTED)
+        .union(Flags::FIRST_SEGMENT)
+        .union(Flags::LAST_SEGMENT);
+
+    if flags.is_unmapped() && !record.cigar().is_empty() {
+        issues.push(Issue::UnmappedWithCigar);
+    }
+
+    if !flags.is_segmented() {
+        let segment_flags = flags.intersection(SEGMENT_FLAGS);
+
+        if !segment_flags.is_empty() {
+            issues.push(Issue::UnexpectedSegmentFlags(segment_flags));
+        }
+    }
+}
+
+fn validate_lengths<R>(record: &R, issues: &mut Vec<Issue>) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    let sequence_length = record.sequence().len();
+    let cigar = record.cigar();
+
+    if sequence_length > 0 && !cigar.is_empty() {
+        let cigar_read_length = cigar.read_length()?;
+
+        if sequence_length != cigar_read_length {
+            issues.push(Issue::SequenceLengthMismatch {
+                sequence_length,
+                cigar_read_length,
+            });
+        }
+    }
+
+    let quality_scores_length = record.quality_scores().len();
+
+    if quality_scores_length > 0 && quality_scores_length != sequence_length {
+        issues.push(Issue::QualityScoresLengthMismatch {
+            quality_scores_length,
+            sequence_length,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_data<R>(header: &Header, record: &R, issues: &mut Vec<Issue>) -> io::Result<()>
+where
+    R: Record + ?Sized,
+{
+    for result in record.data().iter() {
+        let (tag, value) = result?;
+
+        if let Some(expected_type) = ExpectedType::for_tag(tag) {
+            if !expected_type.matches(value.ty()) {
+                issues.push(Issue::InvalidDataFieldType {
+                    tag,
+                    ty: value.ty(),
+                });
+
+                continue;
+            }
+        }
+
+        match (tag, value) {
+            (Tag::READ_GROUP, Value::String(id)) if !header.read_groups().contains_key(id) => {
+                issues.push(Issue::MissingReadGroup(BString::from(id)));
+            }
+            (Tag::PROGRAM, Value::String(id)) if !header.programs().as_ref().contains_key(id) => {
+                issues.push(Issue::MissingProgram(BString::from(id)));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExpectedType {
+    Character,
+    Integer,
+    String,
+    Array,
+}
+
+impl ExpectedType {
+    // These are the types of the predefined standard tags defined in SAMtags.
+    fn for_tag(tag: Tag) -> Option<Self> {
+        match tag {
+            Tag::TRANSCRIPT_STRAND => Some(Self::Character),
+            Tag::MIN_MAPPING_QUALITY
+            | Tag::ALIGNMENT_SCORE
+            | Tag::COLOR_EDIT_DISTANCE
+            | Tag::NEXT_HIT_POSITION
+            | Tag::SEGMENT_INDEX
+            | Tag::PERFECT_HIT_COUNT
+            | Tag::ONE_DIFFERENCE_HIT_COUNT
+            | Tag::TWO_DIFFERENCE_HIT_COUNT
+            | Tag::HIT_INDEX
+            | Tag::TOTAL_HIT_COUNT
+            | Tag::BASE_MODIFICATION_SEQUENCE_LENGTH
+            | Tag::MATE_MAPPING_QUALITY
+            | Tag::ALIGNMENT_HIT_COUNT
+            | Tag::EDIT_DISTANCE
+            | Tag::ORIGINAL_POSITION
+            | Tag::TEMPLATE_LIKELIHOOD
+            | Tag::TEMPLATE_MAPPING_QUALITY
+            | Tag::SEGMENT_COUNT
+            | Tag::SEGMENT_LIKELIHOOD => Some(Self::Integer),
+            Tag::SAMPLE_BARCODE_SEQUENCE
+            | Tag::BASE_ALIGNMENT_QUALITY_OFFSETS
+            | Tag::ORIGINAL_UMI_QUALITY_SCORES
+            | Tag::CELL_BARCODE_ID
+            | Tag::NEXT_HIT_REFERENCE_SEQUENCE_NAME
+            | Tag::COMMENT
+            | Tag::COLOR_QUALITY_SCORES
+            | Tag::CELL_BARCODE_SEQUENCE
+            | Tag::COLOR_SEQUENCE
+            | Tag::COMPLETE_READ_ANNOTATIONS
+            | Tag::CELL_BARCODE_QUALITY_SCORES
+            | Tag::NEXT_HIT_SEQUENCE
+            | Tag::SEGMENT_SUFFIX
+            | Tag::LIBRARY
+            | Tag::MATE_CIGAR
+            | Tag::MISMATCHED_POSITIONS
+            | Tag::UMI_ID
+            | Tag::BASE_MODIFICATIONS
+            | Tag::ORIGINAL_ALIGNMENT
+            | Tag::ORIGINAL_CIGAR
+            | Tag::ORIGINAL_QUALITY_SCORES
+            | Tag::ORIGINAL_UMI_BARCODE_SEQUENCE
+            | Tag::PROGRAM
+            | Tag::PADDED_READ_ANNOTATIONS
+            | Tag::PLATFORM_UNIT
+            | Tag::MATE_QUALITY_SCORES
+            | Tag::SAMPLE_BARCODE_QUALITY_SCORES
+            | Tag::UMI_QUALITY_SCORES
+            | Tag::MATE_SEQUENCE
+            | Tag::READ_GROUP
+            | Tag::UMI_SEQUENCE
+            | Tag::OTHER_ALIGNMENTS
+            | Tag::NEXT_HIT_QUALITY_SCORES => Some(Self::String),
+            Tag::CIGAR | Tag::ALTERNATIVE_SEQUENCE | Tag::BASE_MODIFICATION_PROBABILITIES => {
+                Some(Self::Array)
+            }
+            _ => None,
+        }
+    }
+
+    fn matches(self, ty: Type) -> bool {
+        match self {
+            Self::Character => ty == Type::Character,
+            Self::Integer => matches!(
+                ty,
+                Type::Int8 | Type::UInt8 | Type::Int16 | Type::UInt16 | Type::Int32 | Type::UInt32
+            ),
+            Self::String => ty == Type::String,
+            Self::Array => ty == Type::Array,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::{
+        alignment::{
+            RecordBuf,
+            record::cigar::{Op, op::Kind},
+            record_buf::{QualityScores, Sequence, data::field::Value as ValueBuf},
+        },
+        header::record::value::{
+            Map,
+            map::{ReadGroup, ReferenceSequence},
+        },
+    };
+
+    const SQ0_LN: NonZeroUsize = match NonZeroUsize::new(8) {
+        Some(n) => n,
+        None => unreachable!(),
+    };
+
+    fn build_header() -> Header {
+        Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(SQ0_LN))
+            .add_read_group("rg0", Map::<ReadGroup>::default())
+            .build()
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header();
+        let validator = Validator::new(&header);
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(5)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_sequence(Sequence::from(b"ACGT"))
+            .set_quality_scores(QualityScores::from(vec![45, 35, 43, 50]))
+            .set_data(
+                [
+                    (Tag::READ_GROUP, ValueBuf::from("rg0")),
+                    (Tag::EDIT_DISTANCE, ValueBuf::from(0u8)),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .build();
+
+        assert!(validator.validate(&record)?.is_empty());
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(6)?)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .set_sequence(Sequence::from(b"ACG"))
+            .set_quality_scores(QualityScores::from(vec![45, 35]))
+            .set_mate_reference_sequence_id(1)
+            .set_data(
+                [
+                    (Tag::READ_GROUP, ValueBuf::from("rg1")),
+                    (Tag::PROGRAM, ValueBuf::from("pg0")),
+                    (Tag::EDIT_DISTANCE, ValueBuf::from("0")),
+                ]
+                .into_iter()
+                .collect(),
+            )
+            .build();
+
+        assert_eq!(
+            validator.validate(&record)?,
+            [
+                Issue::AlignmentEndOutOfRange {
+                    position: Position::try_from(9)?,
+                    length: 8
+                },
+                Issue::InvalidMateReferenceSequenceId(1),
+                Issue::SequenceLengthMismatch {
+                    sequence_length: 3,
+                    cigar_read_length: 4
+                },
+                Issue::QualityScoresLengthMismatch {
+                    quality_scores_length: 2,
+                    sequence_length: 3
+                },
+                Issue::MissingReadGroup(BString::from("rg1")),
+                Issue::MissingProgram(BString::from("pg0")),
+                Issue::InvalidDataFieldType {
+                    tag: Tag::EDIT_DISTANCE,
+                    ty: Type::String
+                },
+            ]
+        );
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(2)
+            .build();
+
+        assert_eq!(
+            validator.validate(&record)?,
+            [Issue::MissingPosition, Issue::InvalidReferenceSequenceId(2)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_sam_record() -> io::Result<()> {
+        let header = build_header();
+        let validator = Validator::new(&header);
+
+        let record = crate::Record::try_from(&b"r0\t0\tsq0\t5\t60\t4M\t=\t1\t0\tACGT\tNDLS"[..])?;
+        assert!(validator.validate(&record)?.is_empty());
+
+        let record = crate::Record::try_from(&b"r0\t1\tsq1\t5\t60\t4M\tsq2\t1\t0\tACGT\tNDLS"[..])?;
+        assert_eq!(
+            validator.validate(&record)?,
+            [
+                Issue::MissingReferenceSequence,
+                Issue::MissingMateReferenceSequence
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_with_strictness() -> io::Result<()> {
+        let header = build_header();
+
+        let record = RecordBuf::builder()
+            .set_flags(Flags::UNMAPPED | Flags::FIRST_SEGMENT)
+            .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+            .build();
+
+        let validator = Validator::new(&header);
+        assert_eq!(
+            validator.validate(&record)?,
+            [
+                Issue::UnmappedWithCigar,
+                Issue::UnexpectedSegmentFlags(Flags::FIRST_SEGMENT),
+            ]
+        );
+
+        let validator = Validator::new(&header).set_strictness(Strictness::Lenient);
+        assert!(validator.validate(&record)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_all() -> io::Result<()> {
+        let header = build_header();
+        let validator = Validator::new(&header);
+
+        let records = [
+            Ok(RecordBuf::default()),
+            Ok(RecordBuf::builder()
+                .set_flags(Flags::UNMAPPED | Flags::LAST_SEGMENT)
+                .build()),
+            Ok(RecordBuf::builder().set_reference_sequence_id(1).build()),
+        ];
+
+        let report = validator.validate_all(records)?;
+
+        assert_eq!(report.record_count(), 3);
+        assert_eq!(report.invalid_record_count(), 1);
+        assert!(!report.is_valid());
+        assert_eq!(report.count(Severity::Warning), 1);
+        assert_eq!(report.count(Severity::Error), 1);
+        assert_eq!(
+            report.issues(),
+            [
+                (1, Issue::UnexpectedSegmentFlags(Flags::LAST_SEGMENT)),
+                (2, Issue::InvalidReferenceSequenceId(1)),
+            ]
+        );
+
+        Ok(())
+    }
+}
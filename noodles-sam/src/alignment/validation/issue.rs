@@ -0,0 +1,155 @@
+use std::fmt;
+
+use bstr::BString;
+use noodles_core::Position;
+
+use crate::alignment::record::{
+    Flags,
+    data::field::{Tag, Type},
+};
+
+/// The severity of a validation issue.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The record is suspicious but can be processed.
+    Warning,
+    /// The record is invalid.
+    Error,
+}
+
+/// An alignment record validation issue.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Issue {
+    /// The reference sequence ID is not in the header reference sequences.
+    InvalidReferenceSequenceId(usize),
+    /// The reference sequence name is not in the header reference sequences.
+    MissingReferenceSequence,
+    /// A mapped record is missing its reference sequence ID or alignment start.
+    MissingPosition,
+    /// The alignment start is past the end of the reference sequence.
+    AlignmentStartOutOfRange {
+        /// The alignment start.
+        position: Position,
+        /// The reference sequence length (`@SQ LN`).
+        length: usize,
+    },
+    /// The alignment end is past the end of the reference sequence.
+    AlignmentEndOutOfRange {
+        /// The alignment end.
+        position: Position,
+        /// The reference sequence length (`@SQ LN`).
+        length: usize,
+    },
+    /// The mate reference sequence ID is not in the header reference sequences.
+    InvalidMateReferenceSequenceId(usize),
+    /// The mate reference sequence name is not in the header reference sequences.
+    MissingMateReferenceSequence,
+    /// The mate alignment start is past the end of the mate reference sequence.
+    MateAlignmentStartOutOfRange {
+        /// The mate alignment start.
+        position: Position,
+        /// The mate reference sequence length (`@SQ LN`).
+        length: usize,
+    },
+    /// The sequence length does not match the read length calculated from the CIGAR.
+    SequenceLengthMismatch {
+        /// The sequence length.
+        sequence_length: usize,
+        /// The read length calculated from the CIGAR.
+        cigar_read_length: usize,
+    },
+    /// The quality scores length does not match the sequence length.
+    QualityScoresLengthMismatch {
+        /// The quality scores length.
+        quality_scores_length: usize,
+        /// The sequence length.
+        sequence_length: usize,
+    },
+    /// An unmapped record has CIGAR operations.
+    UnmappedWithCigar,
+    /// Segment or mate flags are set on a record that is not segmented.
+    UnexpectedSegmentFlags(Flags),
+    /// The read group (`RG`) is not in the header read groups.
+    MissingReadGroup(BString),
+    /// The program (`PG`) is not in the header programs.
+    MissingProgram(BString),
+    /// A data field value does not have the type defined for its tag.
+    InvalidDataFieldType {
+        /// The data field tag.
+        tag: Tag,
+        /// The actual value type.
+        ty: Type,
+    },
+}
+
+impl Issue {
+    /// Returns the severity of the issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::alignment::validation::{Issue, Severity};
+    /// assert_eq!(Issue::UnmappedWithCigar.severity(), Severity::Warning);
+    /// assert_eq!(Issue::MissingPosition.severity(), Severity::Error);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::UnmappedWithCigar | Self::UnexpectedSegmentFlags(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidReferenceSequenceId(id) => {
+                write!(f, "invalid reference sequence ID: {id}")
+            }
+            Self::MissingReferenceSequence => write!(f, "missing reference sequence"),
+            Self::MissingPosition => write!(f, "missing position for mapped record"),
+            Self::AlignmentStartOutOfRange { position, length } => write!(
+                f,
+                "alignment start ({position}) is out of range of reference sequence length ({length})"
+            ),
+            Self::AlignmentEndOutOfRange { position, length } => write!(
+                f,
+                "alignment end ({position}) is out of range of reference sequence length ({length})"
+            ),
+            Self::InvalidMateReferenceSequenceId(id) => {
+                write!(f, "invalid mate reference sequence ID: {id}")
+            }
+            Self::MissingMateReferenceSequence => write!(f, "missing mate reference sequence"),
+            Self::MateAlignmentStartOutOfRange { position, length } => write!(
+                f,
+                "mate alignment start ({position}) is out of range of reference sequence length ({length})"
+            ),
+            Self::SequenceLengthMismatch {
+                sequence_length,
+                cigar_read_length,
+            } => write!(
+                f,
+                "sequence length mismatch: expected {cigar_read_length} (from CIGAR), got {sequence_length}"
+            ),
+            Self::QualityScoresLengthMismatch {
+                quality_scores_length,
+                sequence_length,
+            } => write!(
+                f,
+                "quality scores length mismatch: expected {sequence_length}, got {quality_scores_length}"
+            ),
+            Self::UnmappedWithCigar => write!(f, "unmapped record has CIGAR operations"),
+            Self::UnexpectedSegmentFlags(flags) => {
+                write!(
+                    f,
+                    "unexpected segment flags for unsegmented record: {flags:?}"
+                )
+            }
+            Self::MissingReadGroup(id) => write!(f, "missing read group: {id}"),
+            Self::MissingProgram(id) => write!(f, "missing program: {id}"),
+            Self::InvalidDataFieldType { tag, ty } => {
+                write!(f, "invalid data field type for {tag:?}: {ty:?}")
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::{Issue, Severity};
+
+/// An aggregated validation report of alignment records.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    record_count: usize,
+    invalid_record_count: usize,
+    issues: Vec<(usize, Issue)>,
+}
+
+impl Report {
+    /// Returns the number of validated records.
+    pub fn record_count(&self) -> usize {
+        self.record_count
+    }
+
+    /// Returns the number of records with at least one error.
+    pub fn invalid_record_count(&self) -> usize {
+        self.invalid_record_count
+    }
+
+    /// Returns the issues with the 0-based index of the record they were found in.
+    pub fn issues(&self) -> &[(usize, Issue)] {
+        &self.issues
+    }
+
+    /// Returns the number of issues with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|(_, issue)| issue.severity() == severity)
+            .count()
+    }
+
+    /// Returns whether no errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.invalid_record_count == 0
+    }
+
+    pub(super) fn add(&mut self, issues: Vec<Issue>) {
+        let i = self.record_count;
+        self.record_count += 1;
+
+        if issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error)
+        {
+            self.invalid_record_count += 1;
+        }
+
+        self.issues
+            .extend(issues.into_iter().map(|issue| (i, issue)));
+    }
+}
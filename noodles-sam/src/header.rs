@@ -71,9 +71,11 @@
 //! ```
 
 mod builder;
+pub mod normalization;
 mod parser;
 mod programs;
 pub mod record;
+pub mod validation;
 
 pub use self::{
     builder::Builder,
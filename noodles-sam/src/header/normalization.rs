@@ -0,0 +1,242 @@
+//! SAM header normalization.
+
+use std::collections::HashMap;
+
+use bstr::{BStr, BString, ByteSlice};
+use indexmap::IndexMap;
+
+use super::{
+    Header,
+    record::value::{
+        Map,
+        map::{
+            Program,
+            header::{group_order, sort_order, tag as header_tag},
+            program::tag as program_tag,
+            read_group::{platform, tag as read_group_tag},
+            reference_sequence::{molecule_topology, tag as reference_sequence_tag},
+        },
+    },
+};
+
+/// Normalizes a SAM header.
+///
+/// This canonicalizes the header so that equivalent headers are written the same:
+///
+///   * A header (`@HD`) record is added if missing.
+///   * Sort orders (`@HD SO`), group orders (`@HD GO`), and molecule topologies (`@SQ TP`) are
+///     lowercased; platforms (`@RG PL`) are uppercased; and MD5 checksums (`@SQ M5`) are
+///     lowercased.
+///   * Read groups are sorted by ID.
+///   * Programs are sorted by chain, from root to leaf.
+///
+/// Reference sequences are not reordered, as their order defines the reference sequence IDs of
+/// alignment records.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, header::normalization};
+///
+/// let mut header: sam::Header = "\
+/// @RG\tID:rg1\tPL:illumina
+/// @RG\tID:rg0\tPL:ILLUMINA
+/// "
+/// .parse()?;
+///
+/// normalization::normalize(&mut header);
+///
+/// let expected: sam::Header = "\
+/// @HD\tVN:1.6
+/// @RG\tID:rg0\tPL:ILLUMINA
+/// @RG\tID:rg1\tPL:ILLUMINA
+/// "
+/// .parse()?;
+///
+/// assert_eq!(header, expected);
+/// # Ok::<_, sam::header::ParseError>(())
+/// ```
+pub fn normalize(header: &mut Header) {
+    normalize_header(header);
+    normalize_reference_sequences(header);
+    normalize_read_groups(header);
+    normalize_programs(header);
+}
+
+fn normalize_header(header: &mut Header) {
+    let map = header.header_mut().get_or_insert_with(Map::default);
+    let other_fields = map.other_fields_mut();
+
+    if let Some(value) = other_fields.get_mut(&header_tag::SORT_ORDER) {
+        canonicalize_lowercase(
+            value,
+            &[
+                sort_order::UNKNOWN,
+                sort_order::UNSORTED,
+                sort_order::QUERY_NAME,
+                sort_order::COORDINATE,
+            ],
+        );
+    }
+
+    if let Some(value) = other_fields.get_mut(&header_tag::GROUP_ORDER) {
+        canonicalize_lowercase(
+            value,
+            &[
+                group_order::NONE,
+                group_order::QUERY,
+                group_order::REFERENCE,
+            ],
+        );
+    }
+}
+
+fn normalize_reference_sequences(header: &mut Header) {
+    for reference_sequence in header.reference_sequences_mut().values_mut() {
+        let other_fields = reference_sequence.other_fields_mut();
+
+        if let Some(value) = other_fields.get_mut(&reference_sequence_tag::MD5_CHECKSUM) {
+            value.make_ascii_lowercase();
+        }
+
+        if let Some(value) = other_fields.get_mut(&reference_sequence_tag::MOLECULE_TOPOLOGY) {
+            canonicalize_lowercase(
+                value,
+                &[molecule_topology::LINEAR, molecule_topology::CIRCULAR],
+            );
+        }
+    }
+}
+
+fn normalize_read_groups(header: &mut Header) {
+    let read_groups = header.read_groups_mut();
+
+    for read_group in read_groups.values_mut() {
+        if let Some(value) = read_group
+            .other_fields_mut()
+            .get_mut(&read_group_tag::PLATFORM)
+        {
+            if let Some(platform) = platform::ALL.iter().find(|p| p.eq_ignore_ascii_case(value)) {
+                *value = BString::from(*platform);
+            }
+        }
+    }
+
+    read_groups.sort_keys();
+}
+
+fn normalize_programs(header: &mut Header) {
+    let programs = header.programs_mut().as_mut();
+
+    let order = chain_order(programs);
+    let mut rank = vec![0; order.len()];
+
+    for (r, &i) in order.iter().enumerate() {
+        rank[i] = r;
+    }
+
+    let mut entries: Vec<_> = programs.drain(..).enumerate().collect();
+    entries.sort_by_key(|(i, _)| rank[*i]);
+    programs.extend(entries.into_iter().map(|(_, entry)| entry));
+}
+
+// Returns the program indices in depth-first order from each root program.
+fn chain_order(programs: &IndexMap<BString, Map<Program>>) -> Vec<usize> {
+    let mut children: HashMap<&BStr, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+
+    for (i, (id, program)) in programs.iter().enumerate() {
+        match previous_program_id(program).filter(|pp| programs.contains_key(*pp) && *pp != id) {
+            Some(previous_id) => children.entry(previous_id).or_default().push(i),
+            None => roots.push(i),
+        }
+    }
+
+    let mut order = Vec::with_capacity(programs.len());
+    let mut visited = vec![false; programs.len()];
+    let mut stack: Vec<usize> = roots.into_iter().rev().collect();
+
+    while let Some(i) = stack.pop() {
+        if visited[i] {
+            continue;
+        }
+
+        visited[i] = true;
+        order.push(i);
+
+        if let Some((id, _)) = programs.get_index(i) {
+            if let Some(js) = children.get(id.as_bstr()) {
+                stack.extend(js.iter().rev());
+            }
+        }
+    }
+
+    // Programs in cycles are not reachable from a root and keep their relative order.
+    order.extend((0..programs.len()).filter(|&i| !visited[i]));
+
+    order
+}
+
+fn previous_program_id(program: &Map<Program>) -> Option<&BStr> {
+    program
+        .other_fields()
+        .get(&program_tag::PREVIOUS_PROGRAM_ID)
+        .map(|id| id.as_bstr())
+}
+
+fn canonicalize_lowercase(value: &mut BString, canonical_values: &[&[u8]]) {
+    if let Some(canonical_value) = canonical_values
+        .iter()
+        .find(|v| v.eq_ignore_ascii_case(value))
+    {
+        *value = BString::from(*canonical_value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() -> Result<(), Box<dyn std::error::Error>> {
+        let mut header: Header = "\
+@HD\tVN:1.6\tSO:Coordinate\tGO:QUERY
+@SQ\tSN:sq1\tLN:13\tM5:D7EBA311421BBC9D3ADA44709DD61534\tTP:Circular
+@SQ\tSN:sq0\tLN:8
+@RG\tID:rg1\tPL:PacBio
+@RG\tID:rg0\tPL:unknown
+@PG\tID:pg2\tPP:pg0
+@PG\tID:pg3\tPP:pg1
+@PG\tID:pg1
+@PG\tID:pg0
+@PG\tID:pg4\tPP:pg2
+"
+        .parse()?;
+
+        normalize(&mut header);
+
+        let expected: Header = "\
+@HD\tVN:1.6\tSO:coordinate\tGO:query
+@SQ\tSN:sq1\tLN:13\tM5:d7eba311421bbc9d3ada44709dd61534\tTP:circular
+@SQ\tSN:sq0\tLN:8
+@RG\tID:rg0\tPL:unknown
+@RG\tID:rg1\tPL:PACBIO
+@PG\tID:pg1
+@PG\tID:pg3\tPP:pg1
+@PG\tID:pg0
+@PG\tID:pg2\tPP:pg0
+@PG\tID:pg4\tPP:pg2
+"
+        .parse()?;
+
+        assert_eq!(header, expected);
+
+        let ids: Vec<_> = header.read_groups().keys().cloned().collect();
+        assert_eq!(ids, ["rg0", "rg1"]);
+
+        let ids: Vec<_> = header.programs().as_ref().keys().cloned().collect();
+        assert_eq!(ids, ["pg1", "pg3", "pg0", "pg2", "pg4"]);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use std::io;
+use std::{collections::HashMap, io};
 
-use bstr::{BStr, BString, ByteVec};
+use bstr::{BStr, BString, ByteSlice, ByteVec};
 use indexmap::{IndexMap, IndexSet};
 
 use super::record::value::map::{Map, Program, program::tag};
@@ -190,6 +190,295 @@ impl Programs {
                 .unwrap()
         }))
     }
+
+    /// Appends a program to the end of every program chain.
+    ///
+    /// A copy of the program is linked to each leaf program using the previous program ID (`PP`)
+    /// field. If the graph is empty, the program is added as a root program.
+    ///
+    /// Unlike [`Self::add`], IDs are always made unique: if an ID is already in use, a numeric
+    /// suffix is appended, e.g., `pg0-1`, `pg0-2`, etc.
+    ///
+    /// This returns the IDs of the added programs.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if any program chain has a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{map::program::tag, Map},
+    /// };
+    ///
+    /// let mut header = sam::Header::builder()
+    ///     .add_program("pg0", Map::default())
+    ///     .add_program("pg1", Map::default())
+    ///     .build();
+    ///
+    /// let programs = header.programs_mut();
+    /// let ids = programs.append("pg0", Map::default())?;
+    /// assert_eq!(ids, [b"pg0-1", b"pg0-2"]);
+    ///
+    /// let program = &programs.as_ref()[&b"pg0-2"[..]];
+    /// assert_eq!(
+    ///     program.other_fields().get(&tag::PREVIOUS_PROGRAM_ID).map(|id| id.as_slice()),
+    ///     Some(&b"pg1"[..])
+    /// );
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    pub fn append<P>(&mut self, id_prefix: P, map: Map<Program>) -> io::Result<Vec<BString>>
+    where
+        P: Into<BString>,
+    {
+        let id_prefix = id_prefix.into();
+
+        let previous_program_ids: Vec<BString> = self.leaves()?.map(|(id, _)| id.into()).collect();
+
+        if previous_program_ids.is_empty() {
+            let id = self.unique_id(&id_prefix);
+            self.0.insert(id.clone(), map);
+            return Ok(vec![id]);
+        }
+
+        let mut ids = Vec::with_capacity(previous_program_ids.len());
+
+        for previous_program_id in previous_program_ids {
+            let id = self.unique_id(&id_prefix);
+
+            let mut map = map.clone();
+            map.other_fields_mut()
+                .insert(tag::PREVIOUS_PROGRAM_ID, previous_program_id);
+
+            self.0.insert(id.clone(), map);
+            ids.push(id);
+        }
+
+        Ok(ids)
+    }
+
+    /// Returns the program chains.
+    ///
+    /// Each chain is the list of programs from a root program to a leaf program. Programs that
+    /// branch into multiple chains are included in each chain.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if any program chain has a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{map::program::tag, Map},
+    /// };
+    ///
+    /// let header = sam::Header::builder()
+    ///     .add_program("pg0", Map::default())
+    ///     .add_program("pg1", Map::builder().insert(tag::PREVIOUS_PROGRAM_ID, "pg0").build()?)
+    ///     .add_program("pg2", Map::default())
+    ///     .build();
+    ///
+    /// let chains: Vec<Vec<_>> = header
+    ///     .programs()
+    ///     .chains()?
+    ///     .map(|chain| chain.into_iter().map(|(id, _)| id).collect())
+    ///     .collect();
+    ///
+    /// assert_eq!(chains, [vec!["pg2"], vec!["pg0", "pg1"]]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn chains(&self) -> io::Result<impl Iterator<Item = Vec<(&BStr, &Map<Program>)>>> {
+        let leaves = self.leaves()?;
+
+        Ok(leaves.map(|(id, map)| {
+            let mut chain = vec![(id, map)];
+            let mut map = map;
+
+            while let Some((id, previous_map)) = map
+                .other_fields()
+                .get(&tag::PREVIOUS_PROGRAM_ID)
+                .and_then(|previous_program_id| self.0.get_key_value(previous_program_id))
+            {
+                chain.push((id.as_ref(), previous_map));
+                map = previous_map;
+            }
+
+            chain.reverse();
+            chain
+        }))
+    }
+
+    /// Removes a program.
+    ///
+    /// Programs linked to the removed program are relinked to its previous program, if any;
+    /// otherwise, they become root programs. The order of the remaining programs is preserved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{map::program::tag, Map},
+    /// };
+    ///
+    /// let mut header = sam::Header::builder()
+    ///     .add_program("pg0", Map::default())
+    ///     .add_program("pg1", Map::builder().insert(tag::PREVIOUS_PROGRAM_ID, "pg0").build()?)
+    ///     .add_program("pg2", Map::builder().insert(tag::PREVIOUS_PROGRAM_ID, "pg1").build()?)
+    ///     .build();
+    ///
+    /// let programs = header.programs_mut();
+    /// assert!(programs.remove(b"pg1").is_some());
+    ///
+    /// let program = &programs.as_ref()[&b"pg2"[..]];
+    /// assert_eq!(
+    ///     program.other_fields().get(&tag::PREVIOUS_PROGRAM_ID).map(|id| id.as_slice()),
+    ///     Some(&b"pg0"[..])
+    /// );
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn remove<I>(&mut self, id: &I) -> Option<Map<Program>>
+    where
+        I: AsRef<[u8]> + ?Sized,
+    {
+        let id = id.as_ref().as_bstr();
+        let map = self.0.shift_remove(id)?;
+        let previous_program_id = map.other_fields().get(&tag::PREVIOUS_PROGRAM_ID);
+
+        for child in self.0.values_mut() {
+            let other_fields = child.other_fields_mut();
+
+            if other_fields
+                .get(&tag::PREVIOUS_PROGRAM_ID)
+                .is_some_and(|pp| pp == id)
+            {
+                match previous_program_id {
+                    Some(previous_program_id) => {
+                        other_fields.insert(tag::PREVIOUS_PROGRAM_ID, previous_program_id.clone());
+                    }
+                    None => {
+                        other_fields.shift_remove(&tag::PREVIOUS_PROGRAM_ID);
+                    }
+                }
+            }
+        }
+
+        Some(map)
+    }
+
+    /// Collapses duplicate programs.
+    ///
+    /// Two programs are duplicates if they have the same fields, ignoring their IDs, and the same
+    /// previous program, after its own duplicates are collapsed. This is typical when merging the
+    /// headers of files that share processing history.
+    ///
+    /// The first program of a set of duplicates is kept. This returns a map of the IDs of the
+    /// removed programs to the IDs of the kept programs, e.g., to update the program (`PG`) data
+    /// field of alignment records.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if any program chain has a cycle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     self as sam,
+    ///     header::record::value::{map::program::tag, Map},
+    /// };
+    ///
+    /// let mut header = sam::Header::builder()
+    ///     .add_program("bwa", Map::builder().insert(tag::NAME, "bwa").build()?)
+    ///     .add_program("bwa-1", Map::builder().insert(tag::NAME, "bwa").build()?)
+    ///     .build();
+    ///
+    /// let ids = header.programs_mut().collapse()?;
+    ///
+    /// assert_eq!(header.programs().as_ref().len(), 1);
+    /// assert_eq!(ids.get(&b"bwa-1"[..]).map(|id| id.as_slice()), Some(&b"bwa"[..]));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn collapse(&mut self) -> io::Result<HashMap<BString, BString>> {
+        // Checks that there are no cycles.
+        let _ = self.leaves()?;
+
+        let mut ids: HashMap<BString, BString> = HashMap::new();
+        let mut entries: Vec<_> = self.0.drain(..).collect();
+
+        // Rewriting a previous program ID can make the programs linked to it duplicates, so this
+        // repeats until no more duplicates are found.
+        loop {
+            let mut retained: Vec<(BString, Map<Program>)> = Vec::with_capacity(entries.len());
+            let mut is_changed = false;
+
+            for (id, mut map) in entries {
+                if let Some(previous_program_id) =
+                    map.other_fields_mut().get_mut(&tag::PREVIOUS_PROGRAM_ID)
+                {
+                    if let Some(kept_id) = ids.get(previous_program_id) {
+                        *previous_program_id = kept_id.clone();
+                    }
+                }
+
+                match retained.iter().find(|(_, m)| *m == map) {
+                    Some((kept_id, _)) => {
+                        ids.insert(id, kept_id.clone());
+                        is_changed = true;
+                    }
+                    None => retained.push((id, map)),
+                }
+            }
+
+            entries = retained;
+
+            if !is_changed {
+                break;
+            }
+        }
+
+        self.0.extend(entries);
+
+        // A kept program can be collapsed in a later pass, so IDs are resolved transitively.
+        Ok(ids
+            .keys()
+            .map(|id| {
+                let mut kept_id = &ids[id];
+
+                while let Some(next_id) = ids.get(kept_id) {
+                    kept_id = next_id;
+                }
+
+                (id.clone(), kept_id.clone())
+            })
+            .collect())
+    }
+
+    fn unique_id(&self, id_prefix: &BString) -> BString {
+        const SEPARATOR: u8 = b'-';
+
+        if !self.0.contains_key(id_prefix) {
+            return id_prefix.clone();
+        }
+
+        let mut n = 1;
+
+        loop {
+            let mut id = id_prefix.clone();
+            id.push_byte(SEPARATOR);
+            id.push_str(n.to_string());
+
+            if !self.0.contains_key(&id) {
+                return id;
+            }
+
+            n += 1;
+        }
+    }
 }
 
 fn has_cycle<'a>(graph: &'a Inner, mut parent_id: &'a BStr, node_id: &'a BStr) -> bool {
@@ -350,4 +639,168 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_append() -> Result<(), Box<dyn std::error::Error>> {
+        let mut programs = Programs::default();
+
+        assert_eq!(programs.append("pg0", Map::default())?, [b"pg0"]);
+        assert_eq!(programs.append("pg0", Map::default())?, [b"pg0-1"]);
+
+        programs
+            .as_mut()
+            .insert(BString::from("pg1"), Map::default());
+
+        assert_eq!(
+            programs.append("pg0", Map::default())?,
+            [b"pg0-2", b"pg0-3"]
+        );
+
+        let previous_program_ids: Vec<_> = ["pg0-2", "pg0-3"]
+            .into_iter()
+            .map(|id| {
+                programs.as_ref()[id.as_bytes()]
+                    .other_fields()
+                    .get(&tag::PREVIOUS_PROGRAM_ID)
+                    .cloned()
+            })
+            .collect();
+
+        assert_eq!(
+            previous_program_ids,
+            [Some(BString::from("pg1")), Some(BString::from("pg0-1"))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_chains() -> Result<(), Box<dyn std::error::Error>> {
+        let header = Header::builder()
+            .add_program("pg0", Map::default())
+            .add_program(
+                "pg1",
+                Map::builder()
+                    .insert(tag::PREVIOUS_PROGRAM_ID, "pg0")
+                    .build()?,
+            )
+            .add_program(
+                "pg2",
+                Map::builder()
+                    .insert(tag::PREVIOUS_PROGRAM_ID, "pg0")
+                    .build()?,
+            )
+            .add_program(
+                "pg3",
+                Map::builder()
+                    .insert(tag::PREVIOUS_PROGRAM_ID, "pg2")
+                    .build()?,
+            )
+            .build();
+
+        let actual: Vec<Vec<_>> = header
+            .programs()
+            .chains()?
+            .map(|chain| chain.into_iter().map(|(id, _)| id).collect())
+            .collect();
+
+        assert_eq!(actual, [vec!["pg0", "pg2", "pg3"], vec!["pg0", "pg1"]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove() -> Result<(), Box<dyn std::error::Error>> {
+        let mut programs = Programs(
+            [
+                (BString::from("pg0"), Map::default()),
+                (
+                    BString::from("pg1"),
+                    Map::builder()
+                        .insert(tag::PREVIOUS_PROGRAM_ID, "pg0")
+                        .build()?,
+                ),
+                (
+                    BString::from("pg2"),
+                    Map::builder()
+                        .insert(tag::PREVIOUS_PROGRAM_ID, "pg1")
+                        .build()?,
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert!(programs.remove("pg3").is_none());
+
+        assert!(programs.remove("pg0").is_some());
+        assert!(
+            !programs.as_ref()[&b"pg1"[..]]
+                .other_fields()
+                .contains_key(&tag::PREVIOUS_PROGRAM_ID)
+        );
+
+        assert!(programs.remove("pg1").is_some());
+        let ids: Vec<_> = programs.as_ref().keys().cloned().collect();
+        assert_eq!(ids, [BString::from("pg2")]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_collapse() -> Result<(), Box<dyn std::error::Error>> {
+        let build_map = |name: &str, previous_program_id: Option<&str>| {
+            let mut builder = Map::<Program>::builder().insert(tag::NAME, name);
+
+            if let Some(id) = previous_program_id {
+                builder = builder.insert(tag::PREVIOUS_PROGRAM_ID, id);
+            }
+
+            builder.build()
+        };
+
+        let mut programs = Programs(
+            [
+                (BString::from("bwa"), build_map("bwa", None)?),
+                (
+                    BString::from("samtools"),
+                    build_map("samtools", Some("bwa"))?,
+                ),
+                (
+                    BString::from("samtools-1"),
+                    build_map("samtools", Some("bwa-1"))?,
+                ),
+                (BString::from("bwa-1"), build_map("bwa", None)?),
+                (
+                    BString::from("gatk"),
+                    build_map("gatk", Some("samtools-1"))?,
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let ids = programs.collapse()?;
+
+        let expected: HashMap<_, _> = [
+            (BString::from("bwa-1"), BString::from("bwa")),
+            (BString::from("samtools-1"), BString::from("samtools")),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(ids, expected);
+
+        let actual: Vec<_> = programs.as_ref().keys().cloned().collect();
+        assert_eq!(actual, ["bwa", "samtools", "gatk"]);
+
+        assert_eq!(
+            programs.as_ref()[&b"gatk"[..]]
+                .other_fields()
+                .get(&tag::PREVIOUS_PROGRAM_ID),
+            Some(&BString::from("samtools"))
+        );
+
+        Ok(())
+    }
 }
@@ -35,3 +35,18 @@ pub const SOLID: &[u8] = b"SOLID";
 
 /// Ultima Genomics (`ULTIMA`).
 pub const ULTIMA: &[u8] = b"ULTIMA";
+
+pub(crate) const ALL: [&[u8]; 12] = [
+    CAPILLARY,
+    DNB_SEQ,
+    ELEMENT,
+    HELICOS,
+    ILLUMINA,
+    ION_TORRENT,
+    LS454,
+    ONT,
+    PAC_BIO,
+    SINGULAR,
+    SOLID,
+    ULTIMA,
+];
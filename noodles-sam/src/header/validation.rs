@@ -0,0 +1,446 @@
+//! SAM header validation.
+//!
+//! SAM headers are parsed permissively. [`validate`] checks a header against the constraints of
+//! the SAM specification that are not enforced by the parser, e.g., field value formats,
+//! reference sequence alternative names, and program chains.
+
+mod issue;
+
+pub use self::issue::{Issue, Severity};
+
+use std::collections::{HashMap, HashSet};
+
+use bstr::{BStr, ByteSlice};
+
+use super::{
+    Header,
+    record::value::map::{
+        header::{Version, group_order, sort_order, tag as header_tag},
+        program::tag as program_tag,
+        read_group::{platform, tag as read_group_tag},
+        reference_sequence::{molecule_topology, tag as reference_sequence_tag},
+    },
+};
+
+const SUPPORTED_VERSIONS: [Version; 7] = [
+    Version::new(1, 0),
+    Version::new(1, 1),
+    Version::new(1, 2),
+    Version::new(1, 3),
+    Version::new(1, 4),
+    Version::new(1, 5),
+    Version::new(1, 6),
+];
+
+const SORT_ORDERS: [&[u8]; 4] = [
+    sort_order::UNKNOWN,
+    sort_order::UNSORTED,
+    sort_order::QUERY_NAME,
+    sort_order::COORDINATE,
+];
+
+const GROUP_ORDERS: [&[u8]; 3] = [
+    group_order::NONE,
+    group_order::QUERY,
+    group_order::REFERENCE,
+];
+
+// § 1.3 "The header section" (2024-11-06): "Range: [1, 2^31 - 1]".
+const MAX_REFERENCE_SEQUENCE_LENGTH: usize = (1 << 31) - 1;
+
+/// Validates a SAM header.
+///
+/// This returns the list of issues found. An empty list means the header is valid.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::{self as sam, header::validation::{self, Issue}};
+///
+/// let header: sam::Header = "@HD\tVN:1.6\tSO:coordinate\n".parse()?;
+/// assert!(validation::validate(&header).is_empty());
+///
+/// let header: sam::Header = "@HD\tVN:1.6\tSO:sorted\n".parse()?;
+/// assert_eq!(
+///     validation::validate(&header),
+///     [Issue::InvalidSortOrder("sorted".into())]
+/// );
+/// # Ok::<_, sam::header::ParseError>(())
+/// ```
+pub fn validate(header: &Header) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    validate_header(header, &mut issues);
+    validate_reference_sequences(header, &mut issues);
+    validate_read_groups(header, &mut issues);
+    validate_programs(header, &mut issues);
+
+    issues
+}
+
+fn validate_header(header: &Header, issues: &mut Vec<Issue>) {
+    let Some(map) = header.header() else {
+        return;
+    };
+
+    let version = map.version();
+
+    if !SUPPORTED_VERSIONS.contains(&version) {
+        issues.push(Issue::UnsupportedVersion(version));
+    }
+
+    let other_fields = map.other_fields();
+
+    let sort_order = other_fields.get(&header_tag::SORT_ORDER);
+
+    if let Some(value) = sort_order {
+        if !SORT_ORDERS.contains(&value.as_bytes()) {
+            issues.push(Issue::InvalidSortOrder(value.clone()));
+        }
+    }
+
+    if let Some(value) = other_fields.get(&header_tag::GROUP_ORDER) {
+        if !GROUP_ORDERS.contains(&value.as_bytes()) {
+            issues.push(Issue::InvalidGroupOrder(value.clone()));
+        }
+    }
+
+    if let Some(value) = other_fields.get(&header_tag::SUBSORT_ORDER) {
+        if !is_valid_subsort_order(value.as_ref(), sort_order.map(|s| s.as_ref())) {
+            issues.push(Issue::InvalidSubsortOrder(value.clone()));
+        }
+    }
+}
+
+// The subsort order is of the form `(coordinate|queryname|unsorted)(:[A-Za-z0-9_-]+)+`, where the
+// first component is the same as the sort order.
+fn is_valid_subsort_order(s: &BStr, sort_order: Option<&BStr>) -> bool {
+    const DELIMITER: u8 = b':';
+
+    let mut components = s.split(|&b| b == DELIMITER);
+
+    let Some(order) = components.next() else {
+        return false;
+    };
+
+    if ![
+        sort_order::COORDINATE,
+        sort_order::QUERY_NAME,
+        sort_order::UNSORTED,
+    ]
+    .contains(&order)
+    {
+        return false;
+    }
+
+    if sort_order.is_some_and(|sort_order| sort_order.as_bytes() != order) {
+        return false;
+    }
+
+    let mut has_subsort = false;
+
+    for component in components {
+        if component.is_empty()
+            || !component
+                .iter()
+                .all(|&b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+        {
+            return false;
+        }
+
+        has_subsort = true;
+    }
+
+    has_subsort
+}
+
+fn validate_reference_sequences(header: &Header, issues: &mut Vec<Issue>) {
+    let mut names: HashSet<&BStr> = header
+        .reference_sequences()
+        .keys()
+        .map(|name| name.as_ref())
+        .collect();
+
+    for (name, reference_sequence) in header.reference_sequences() {
+        if !is_valid_reference_sequence_name(name.as_ref()) {
+            issues.push(Issue::InvalidReferenceSequenceName(name.clone()));
+        }
+
+        let length = reference_sequence.length().get();
+
+        if length > MAX_REFERENCE_SEQUENCE_LENGTH {
+            issues.push(Issue::InvalidReferenceSequenceLength {
+                reference_sequence_name: name.clone(),
+                length,
+            });
+        }
+
+        let other_fields = reference_sequence.other_fields();
+
+        if let Some(value) = other_fields.get(&reference_sequence_tag::ALTERNATIVE_NAMES) {
+            // Alternative names are comma-separated and share the namespace of reference
+            // sequence names.
+            for alternative_name in value.split(|&b| b == b',') {
+                let alternative_name = alternative_name.as_bstr();
+
+                if !is_valid_reference_sequence_name(alternative_name) {
+                    issues.push(Issue::InvalidAlternativeName {
+                        reference_sequence_name: name.clone(),
+                        alternative_name: alternative_name.into(),
+                    });
+                } else if !names.insert(alternative_name) {
+                    issues.push(Issue::DuplicateReferenceSequenceName(
+                        alternative_name.into(),
+                    ));
+                }
+            }
+        }
+
+        if let Some(value) = other_fields.get(&reference_sequence_tag::MD5_CHECKSUM) {
+            if value.len() != 32 || !value.iter().all(|b| b.is_ascii_hexdigit()) {
+                issues.push(Issue::InvalidMd5Checksum {
+                    reference_sequence_name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        if let Some(value) = other_fields.get(&reference_sequence_tag::MOLECULE_TOPOLOGY) {
+            if ![molecule_topology::LINEAR, molecule_topology::CIRCULAR].contains(&value.as_bytes())
+            {
+                issues.push(Issue::InvalidMoleculeTopology {
+                    reference_sequence_name: name.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+}
+
+// § 1.2.1 "Character set restrictions" (2024-11-06):
+// `[0-9A-Za-z!#$%&+./:;?@^_|~-][0-9A-Za-z!#$%&*+./:;=?@^_|~-]*`.
+pub(crate) fn is_valid_reference_sequence_name(name: &BStr) -> bool {
+    fn is_valid_char(b: u8) -> bool {
+        b.is_ascii_graphic()
+            && !matches!(
+                b,
+                b'\\'
+                    | b','
+                    | b'"'
+                    | b'\''
+                    | b'`'
+                    | b'('
+                    | b')'
+                    | b'['
+                    | b']'
+                    | b'{'
+                    | b'}'
+                    | b'<'
+                    | b'>'
+            )
+    }
+
+    match name.split_first() {
+        Some((b'*' | b'=', _)) | None => false,
+        Some(_) => name.iter().copied().all(is_valid_char),
+    }
+}
+
+fn validate_read_groups(header: &Header, issues: &mut Vec<Issue>) {
+    for (id, read_group) in header.read_groups() {
+        let other_fields = read_group.other_fields();
+
+        if !other_fields.contains_key(&read_group_tag::SAMPLE) {
+            issues.push(Issue::MissingReadGroupSample(id.clone()));
+        }
+
+        if let Some(value) = other_fields.get(&read_group_tag::PLATFORM) {
+            if !platform::ALL
+                .iter()
+                .any(|platform| platform.eq_ignore_ascii_case(value))
+            {
+                issues.push(Issue::InvalidPlatform {
+                    read_group_id: id.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+
+        if let Some(value) = other_fields.get(&read_group_tag::PREDICTED_MEDIAN_INSERT_SIZE) {
+            if lexical_core::parse::<i64>(value).is_err() {
+                issues.push(Issue::InvalidPredictedMedianInsertSize {
+                    read_group_id: id.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+}
+
+fn validate_programs(header: &Header, issues: &mut Vec<Issue>) {
+    let programs = header.programs().as_ref();
+
+    let mut previous_ids = HashMap::new();
+
+    for (id, program) in programs {
+        if let Some(previous_id) = program
+            .other_fields()
+            .get(&program_tag::PREVIOUS_PROGRAM_ID)
+        {
+            if programs.contains_key(previous_id) {
+                previous_ids.insert(id.as_bstr(), previous_id.as_bstr());
+            } else {
+                issues.push(Issue::MissingPreviousProgram {
+                    program_id: id.clone(),
+                    previous_program_id: previous_id.clone(),
+                });
+            }
+        }
+    }
+
+    // Each cycle is reported once, by its first program ID.
+    let mut cycle_ids: HashSet<&BStr> = HashSet::new();
+
+    for id in programs.keys() {
+        let id = id.as_bstr();
+
+        if cycle_ids.contains(id) {
+            continue;
+        }
+
+        let mut path = vec![id];
+        let mut node = id;
+
+        while let Some(&previous_id) = previous_ids.get(node) {
+            if previous_id == id {
+                cycle_ids.extend(path);
+                issues.push(Issue::ProgramCycle(id.into()));
+                break;
+            } else if path.contains(&previous_id) {
+                break;
+            }
+
+            path.push(previous_id);
+            node = previous_id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::BString;
+
+    use super::*;
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header: Header = "\
+@HD\tVN:1.6\tSO:coordinate\tGO:query\tSS:coordinate:MI
+@SQ\tSN:sq0\tLN:8\tAN:chr0,0\tM5:d7eba311421bbc9d3ada44709dd61534\tTP:linear
+@RG\tID:rg0\tSM:sample0\tPL:ILLUMINA\tPI:300
+@PG\tID:pg0
+@PG\tID:pg1\tPP:pg0
+"
+        .parse()?;
+
+        assert!(validate(&header).is_empty());
+
+        let header: Header = "\
+@HD\tVN:2.0\tSO:sorted\tGO:random\tSS:queryname:MI
+@SQ\tSN:sq0\tLN:8\tAN:sq1,*x\tM5:d7eba311\tTP:loop
+@SQ\tSN:sq1\tLN:13
+@SQ\tSN:=sq2\tLN:2147483648
+@RG\tID:rg0\tPL:illumina
+@RG\tID:rg1\tSM:sample0\tPL:ILLUMINA2\tPI:3OO
+@PG\tID:pg0\tPP:pg9
+"
+        .parse()?;
+
+        assert_eq!(
+            validate(&header),
+            [
+                Issue::UnsupportedVersion(Version::new(2, 0)),
+                Issue::InvalidSortOrder(BString::from("sorted")),
+                Issue::InvalidGroupOrder(BString::from("random")),
+                Issue::InvalidSubsortOrder(BString::from("queryname:MI")),
+                Issue::DuplicateReferenceSequenceName(BString::from("sq1")),
+                Issue::InvalidAlternativeName {
+                    reference_sequence_name: BString::from("sq0"),
+                    alternative_name: BString::from("*x"),
+                },
+                Issue::InvalidMd5Checksum {
+                    reference_sequence_name: BString::from("sq0"),
+                    value: BString::from("d7eba311"),
+                },
+                Issue::InvalidMoleculeTopology {
+                    reference_sequence_name: BString::from("sq0"),
+                    value: BString::from("loop"),
+                },
+                Issue::InvalidReferenceSequenceName(BString::from("=sq2")),
+                Issue::InvalidReferenceSequenceLength {
+                    reference_sequence_name: BString::from("=sq2"),
+                    length: 2147483648,
+                },
+                Issue::MissingReadGroupSample(BString::from("rg0")),
+                Issue::InvalidPlatform {
+                    read_group_id: BString::from("rg1"),
+                    value: BString::from("ILLUMINA2"),
+                },
+                Issue::InvalidPredictedMedianInsertSize {
+                    read_group_id: BString::from("rg1"),
+                    value: BString::from("3OO"),
+                },
+                Issue::MissingPreviousProgram {
+                    program_id: BString::from("pg0"),
+                    previous_program_id: BString::from("pg9"),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_programs_with_cycles() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::header::record::value::{Map, map::Program};
+
+        let mut header = Header::default();
+        let programs = header.programs_mut().as_mut();
+
+        for (id, previous_id) in [
+            ("pg0", "pg2"),
+            ("pg1", "pg0"),
+            ("pg2", "pg1"),
+            ("pg3", "pg3"),
+        ] {
+            let map = Map::<Program>::builder()
+                .insert(program_tag::PREVIOUS_PROGRAM_ID, previous_id)
+                .build()?;
+
+            programs.insert(BString::from(id), map);
+        }
+
+        programs.insert(BString::from("pg4"), Map::default());
+
+        assert_eq!(
+            validate(&header),
+            [
+                Issue::ProgramCycle(BString::from("pg0")),
+                Issue::ProgramCycle(BString::from("pg3")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_valid_reference_sequence_name() {
+        assert!(is_valid_reference_sequence_name(b"sq0".as_bstr()));
+        assert!(is_valid_reference_sequence_name(b"HLA-A*01:01".as_bstr()));
+        assert!(!is_valid_reference_sequence_name(b"".as_bstr()));
+        assert!(!is_valid_reference_sequence_name(b"*sq0".as_bstr()));
+        assert!(!is_valid_reference_sequence_name(b"=sq0".as_bstr()));
+        assert!(!is_valid_reference_sequence_name(b"sq 0".as_bstr()));
+        assert!(!is_valid_reference_sequence_name(b"sq[0]".as_bstr()));
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt;
+
+use bstr::BString;
+
+use crate::header::record::value::map::header::Version;
+
+/// The severity of a header validation issue.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The header is suspicious but can be used.
+    Warning,
+    /// The header is invalid.
+    Error,
+}
+
+/// A SAM header validation issue.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Issue {
+    /// The header format version (`@HD VN`) is not a known version.
+    UnsupportedVersion(Version),
+    /// The sort order (`@HD SO`) is invalid.
+    InvalidSortOrder(BString),
+    /// The group order (`@HD GO`) is invalid.
+    InvalidGroupOrder(BString),
+    /// The subsort order (`@HD SS`) is invalid or does not match the sort order.
+    InvalidSubsortOrder(BString),
+    /// The reference sequence name (`@SQ SN`) is invalid.
+    InvalidReferenceSequenceName(BString),
+    /// The reference sequence length (`@SQ LN`) is out of range.
+    InvalidReferenceSequenceLength {
+        /// The reference sequence name.
+        reference_sequence_name: BString,
+        /// The reference sequence length.
+        length: usize,
+    },
+    /// An alternative reference sequence name (`@SQ AN`) is invalid.
+    InvalidAlternativeName {
+        /// The reference sequence name.
+        reference_sequence_name: BString,
+        /// The alternative name.
+        alternative_name: BString,
+    },
+    /// An alternative reference sequence name (`@SQ AN`) is already used by another reference
+    /// sequence.
+    DuplicateReferenceSequenceName(BString),
+    /// The reference sequence MD5 checksum (`@SQ M5`) is not 32 hexadecimal digits.
+    InvalidMd5Checksum {
+        /// The reference sequence name.
+        reference_sequence_name: BString,
+        /// The raw value.
+        value: BString,
+    },
+    /// The reference sequence molecule topology (`@SQ TP`) is invalid.
+    InvalidMoleculeTopology {
+        /// The reference sequence name.
+        reference_sequence_name: BString,
+        /// The raw value.
+        value: BString,
+    },
+    /// The read group is missing a sample (`@RG SM`).
+    MissingReadGroupSample(BString),
+    /// The read group platform (`@RG PL`) is invalid.
+    InvalidPlatform {
+        /// The read group ID.
+        read_group_id: BString,
+        /// The raw value.
+        value: BString,
+    },
+    /// The read group predicted median insert size (`@RG PI`) is not an integer.
+    InvalidPredictedMedianInsertSize {
+        /// The read group ID.
+        read_group_id: BString,
+        /// The raw value.
+        value: BString,
+    },
+    /// The previous program (`@PG PP`) is not in the header programs.
+    MissingPreviousProgram {
+        /// The program ID.
+        program_id: BString,
+        /// The previous program ID.
+        previous_program_id: BString,
+    },
+    /// The program is part of a cycle of previous program (`@PG PP`) links.
+    ProgramCycle(BString),
+}
+
+impl Issue {
+    /// Returns the severity of the issue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::header::validation::{Issue, Severity};
+    /// assert_eq!(Issue::MissingReadGroupSample("rg0".into()).severity(), Severity::Warning);
+    /// assert_eq!(Issue::ProgramCycle("pg0".into()).severity(), Severity::Error);
+    /// ```
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::UnsupportedVersion(_) | Self::MissingReadGroupSample(_) => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedVersion(version) => write!(f, "@HD: unsupported version: {version}"),
+            Self::InvalidSortOrder(value) => write!(f, "@HD: invalid sort order (SO): {value}"),
+            Self::InvalidGroupOrder(value) => write!(f, "@HD: invalid group order (GO): {value}"),
+            Self::InvalidSubsortOrder(value) => {
+                write!(f, "@HD: invalid subsort order (SS): {value}")
+            }
+            Self::InvalidReferenceSequenceName(name) => {
+                write!(f, "@SQ: invalid reference sequence name (SN): {name}")
+            }
+            Self::InvalidReferenceSequenceLength {
+                reference_sequence_name,
+                length,
+            } => write!(
+                f,
+                "@SQ {reference_sequence_name}: invalid reference sequence length (LN): {length}"
+            ),
+            Self::InvalidAlternativeName {
+                reference_sequence_name,
+                alternative_name,
+            } => write!(
+                f,
+                "@SQ {reference_sequence_name}: invalid alternative name (AN): {alternative_name}"
+            ),
+            Self::DuplicateReferenceSequenceName(name) => {
+                write!(f, "@SQ: duplicate reference sequence name: {name}")
+            }
+            Self::InvalidMd5Checksum {
+                reference_sequence_name,
+                value,
+            } => write!(
+                f,
+                "@SQ {reference_sequence_name}: invalid MD5 checksum (M5): {value}"
+            ),
+            Self::InvalidMoleculeTopology {
+                reference_sequence_name,
+                value,
+            } => write!(
+                f,
+                "@SQ {reference_sequence_name}: invalid molecule topology (TP): {value}"
+            ),
+            Self::MissingReadGroupSample(id) => write!(f, "@RG {id}: missing sample (SM)"),
+            Self::InvalidPlatform {
+                read_group_id,
+                value,
+            } => write!(f, "@RG {read_group_id}: invalid platform (PL): {value}"),
+            Self::InvalidPredictedMedianInsertSize {
+                read_group_id,
+                value,
+            } => write!(
+                f,
+                "@RG {read_group_id}: invalid predicted median insert size (PI): {value}"
+            ),
+            Self::MissingPreviousProgram {
+                program_id,
+                previous_program_id,
+            } => write!(
+                f,
+                "@PG {program_id}: missing previous program (PP): {previous_program_id}"
+            ),
+            Self::ProgramCycle(id) => write!(f, "@PG {id}: program chain has a cycle"),
+        }
+    }
+}
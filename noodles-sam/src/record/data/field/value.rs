@@ -3,8 +3,11 @@
 mod array;
 pub mod base_modifications;
 mod integer;
+pub mod supplementary_alignments;
 
-pub use self::base_modifications::BaseModifications;
+pub use self::{
+    base_modifications::BaseModifications, supplementary_alignments::SupplementaryAlignments,
+};
 
 use std::io;
 
@@ -2,10 +2,16 @@
 
 pub mod group;
 mod parser;
+mod site;
+mod writer;
 
-pub use self::group::Group;
+pub use self::{group::Group, site::Site};
 
-use crate::alignment::record_buf::Sequence;
+use std::io;
+
+use noodles_core::Position;
+
+use crate::alignment::{record::Cigar, record_buf::Sequence};
 
 /// Base modifications.
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -54,6 +60,188 @@ impl BaseModifications {
     ) -> Result<Self, parser::ParseError> {
         parser::parse(s, is_reverse_complemented, sequence)
     }
+
+    /// Parses base modifications (`MM`) with their probabilities (`ML`).
+    ///
+    /// The probabilities are distributed to each group in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     alignment::record_buf::Sequence,
+    ///     record::data::field::value::BaseModifications,
+    /// };
+    ///
+    /// let is_reverse_complemented = false;
+    /// let sequence = Sequence::from(b"CACCCGATGACCGGCT");
+    /// let base_modifications = BaseModifications::parse_with_probabilities(
+    ///     "C+m,1,3,0;G-o,2;",
+    ///     &[200, 10, 5, 128],
+    ///     is_reverse_complemented,
+    ///     &sequence,
+    /// )?;
+    ///
+    /// let groups = base_modifications.as_ref();
+    /// assert_eq!(groups[0].probabilities(), [200, 10, 5]);
+    /// assert_eq!(groups[1].probabilities(), [128]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse_with_probabilities(
+        s: &str,
+        probabilities: &[u8],
+        is_reverse_complemented: bool,
+        sequence: &Sequence,
+    ) -> Result<Self, parser::ParseError> {
+        parser::parse_with_probabilities(s, probabilities, is_reverse_complemented, sequence)
+    }
+
+    /// Encodes base modifications as raw `MM` and `ML` values.
+    ///
+    /// Positions are converted back to skip counts relative to the given sequence. The
+    /// probabilities are empty if no group has probabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{
+    ///     alignment::record_buf::Sequence,
+    ///     record::data::field::value::BaseModifications,
+    /// };
+    ///
+    /// let is_reverse_complemented = false;
+    /// let sequence = Sequence::from(b"CACCCGATGACCGGCT");
+    /// let base_modifications = BaseModifications::parse_with_probabilities(
+    ///     "C+m,1,3,0;",
+    ///     &[200, 10, 5],
+    ///     is_reverse_complemented,
+    ///     &sequence,
+    /// )?;
+    ///
+    /// let (mm, ml) = base_modifications.encode(is_reverse_complemented, &sequence)?;
+    /// assert_eq!(mm, "C+m,1,3,0;");
+    /// assert_eq!(ml, [200, 10, 5]);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn encode(
+        &self,
+        is_reverse_complemented: bool,
+        sequence: &Sequence,
+    ) -> io::Result<(String, Vec<u8>)> {
+        writer::write(self, is_reverse_complemented, sequence)
+    }
+
+    /// Returns the modification calls with their read and reference positions.
+    ///
+    /// There is one site per position and modification, ordered by group. Reference positions
+    /// are resolved using the CIGAR and alignment start; they are unset for unmapped records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     alignment::record_buf::{Cigar, Sequence},
+    ///     alignment::record::cigar::{op::Kind, Op},
+    ///     record::data::field::value::BaseModifications,
+    /// };
+    ///
+    /// let is_reverse_complemented = false;
+    /// let sequence = Sequence::from(b"CACCG");
+    /// let cigar: Cigar = [Op::new(Kind::SoftClip, 1), Op::new(Kind::Match, 4)]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let base_modifications = BaseModifications::parse_with_probabilities(
+    ///     "C+m,0,1;",
+    ///     &[200, 10],
+    ///     is_reverse_complemented,
+    ///     &sequence,
+    /// )?;
+    ///
+    /// let sites = base_modifications.sites(
+    ///     is_reverse_complemented,
+    ///     sequence.len(),
+    ///     &cigar,
+    ///     Some(Position::try_from(8)?),
+    /// )?;
+    ///
+    /// assert_eq!(sites.len(), 2);
+    /// assert_eq!(sites[0].position(), 0);
+    /// assert!(sites[0].reference_position().is_none());
+    /// assert_eq!(sites[1].position(), 3);
+    /// assert_eq!(sites[1].reference_position(), Some(Position::try_from(10)?));
+    /// assert_eq!(sites[1].probability(), Some(10));
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn sites<C>(
+        &self,
+        is_reverse_complemented: bool,
+        sequence_len: usize,
+        cigar: &C,
+        alignment_start: Option<Position>,
+    ) -> io::Result<Vec<Site>>
+    where
+        C: Cigar + ?Sized,
+    {
+        let mut reference_positions = vec![None; sequence_len];
+
+        if let Some(alignment_start) = alignment_start {
+            for result in cigar.aligned_pairs(alignment_start) {
+                let pair = result?;
+
+                if let (Some(i), Some(position)) = (pair.read_position(), pair.reference_position())
+                {
+                    let slot = reference_positions.get_mut(i).ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "CIGAR read length exceeds sequence length",
+                        )
+                    })?;
+
+                    *slot = Some(position);
+                }
+            }
+        }
+
+        let mut sites = Vec::new();
+
+        for group in &self.0 {
+            let modifications = group.modifications();
+            let probabilities = group.probabilities();
+
+            for (i, &position) in group.positions().iter().enumerate() {
+                let reference_position = reference_positions.get(position).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "base modification position exceeds sequence length",
+                    )
+                })?;
+
+                let read_position = if is_reverse_complemented {
+                    sequence_len - 1 - position
+                } else {
+                    position
+                };
+
+                for (j, &modification) in modifications.iter().enumerate() {
+                    let probability = probabilities.get(i * modifications.len() + j).copied();
+
+                    sites.push(Site::new(
+                        group.unmodified_base(),
+                        group.strand(),
+                        modification,
+                        position,
+                        read_position,
+                        *reference_position,
+                        probability,
+                    ));
+                }
+            }
+        }
+
+        Ok(sites)
+    }
 }
 
 impl AsRef<[Group]> for BaseModifications {
@@ -79,3 +267,51 @@ impl From<BaseModifications> for Vec<Group> {
         base_modifications.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::{
+        record::cigar::{Op, op::Kind},
+        record_buf,
+    };
+
+    #[test]
+    fn test_sites() -> Result<(), Box<dyn std::error::Error>> {
+        // original read: CGGTGCCA
+        let sequence = Sequence::from(b"TGGCACCG");
+        let cigar: record_buf::Cigar = [
+            Op::new(Kind::Match, 3),
+            Op::new(Kind::Insertion, 2),
+            Op::new(Kind::Deletion, 1),
+            Op::new(Kind::Match, 3),
+        ]
+        .into_iter()
+        .collect();
+
+        let base_modifications =
+            BaseModifications::parse_with_probabilities("C+m,0,1;", &[255, 3], true, &sequence)?;
+
+        let sites = base_modifications.sites(true, sequence.len(), &cigar, Position::new(5))?;
+
+        let actual: Vec<_> = sites
+            .iter()
+            .map(|site| {
+                (
+                    site.position(),
+                    site.read_position(),
+                    site.reference_position().map(usize::from),
+                    site.probability(),
+                )
+            })
+            .collect();
+
+        let expected = [(7, 0, Some(11), Some(255)), (1, 6, Some(6), Some(3))];
+        assert_eq!(actual, expected);
+
+        let sites = base_modifications.sites(true, sequence.len(), &cigar, None)?;
+        assert!(sites.iter().all(|site| site.reference_position().is_none()));
+
+        Ok(())
+    }
+}
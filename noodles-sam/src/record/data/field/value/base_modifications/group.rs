@@ -17,6 +17,7 @@ pub struct Group {
     modifications: Vec<Modification>,
     status: Option<Status>,
     positions: Vec<usize>,
+    probabilities: Vec<u8>,
 }
 
 impl Group {
@@ -34,6 +35,7 @@ impl Group {
             modifications,
             status,
             positions,
+            probabilities: Vec::new(),
         }
     }
 
@@ -61,4 +63,19 @@ impl Group {
     pub fn positions(&self) -> &[usize] {
         &self.positions
     }
+
+    /// Returns the modification probabilities (`ML`).
+    ///
+    /// Probabilities are ordered by position and then by modification, i.e., the probability of
+    /// modification `j` at position `i` is at index `i * modifications.len() + j`. A value `n`
+    /// represents the probability range `[n/256, (n + 1)/256)`. This is empty if the probabilities
+    /// are not set.
+    pub fn probabilities(&self) -> &[u8] {
+        &self.probabilities
+    }
+
+    /// Returns a mutable reference to the modification probabilities (`ML`).
+    pub fn probabilities_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.probabilities
+    }
 }
@@ -1,4 +1,4 @@
-mod group;
+pub(super) mod group;
 
 use std::{error, fmt};
 
@@ -10,12 +10,15 @@ use crate::alignment::record_buf::Sequence;
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ParseError {
     InvalidGroup(group::ParseError),
+    /// The number of probabilities does not match the number of modification calls.
+    InvalidProbabilities,
 }
 
 impl error::Error for ParseError {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
         match self {
             Self::InvalidGroup(e) => Some(e),
+            Self::InvalidProbabilities => None,
         }
     }
 }
@@ -24,6 +27,7 @@ impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::InvalidGroup(_) => write!(f, "invalid group"),
+            Self::InvalidProbabilities => write!(f, "invalid probabilities"),
         }
     }
 }
@@ -46,6 +50,36 @@ pub(super) fn parse(
     Ok(BaseModifications(groups))
 }
 
+pub(super) fn parse_with_probabilities(
+    s: &str,
+    probabilities: &[u8],
+    is_reverse_complemented: bool,
+    sequence: &Sequence,
+) -> Result<BaseModifications, ParseError> {
+    let mut base_modifications = parse(s, is_reverse_complemented, sequence)?;
+    let mut src = probabilities;
+
+    // Probabilities are listed in group order. Within a group, they are ordered by position and
+    // then by modification.
+    for group in &mut base_modifications.0 {
+        let len = group.positions().len() * group.modifications().len();
+
+        if len > src.len() {
+            return Err(ParseError::InvalidProbabilities);
+        }
+
+        let (buf, rest) = src.split_at(len);
+        *group.probabilities_mut() = buf.to_vec();
+        src = rest;
+    }
+
+    if src.is_empty() {
+        Ok(base_modifications)
+    } else {
+        Err(ParseError::InvalidProbabilities)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,4 +114,41 @@ mod tests {
 
         assert_eq!(actual, Ok(expected));
     }
+
+    #[test]
+    fn test_parse_with_probabilities() {
+        let is_reverse_complemented = false;
+        let sequence = Sequence::from(b"CACCCGATGACCGGCT");
+
+        let actual = parse_with_probabilities(
+            "C+mh,1,3;G-o,2;",
+            &[200, 10, 5, 128, 255],
+            is_reverse_complemented,
+            &sequence,
+        )
+        .map(|base_modifications| {
+            base_modifications
+                .as_ref()
+                .iter()
+                .map(|group| group.probabilities().to_vec())
+                .collect::<Vec<_>>()
+        });
+
+        assert_eq!(actual, Ok(vec![vec![200, 10, 5, 128], vec![255]]));
+
+        assert_eq!(
+            parse_with_probabilities("C+m,1,3;", &[200], is_reverse_complemented, &sequence),
+            Err(ParseError::InvalidProbabilities)
+        );
+
+        assert_eq!(
+            parse_with_probabilities(
+                "C+m,1,3;",
+                &[200, 10, 5],
+                is_reverse_complemented,
+                &sequence
+            ),
+            Err(ParseError::InvalidProbabilities)
+        );
+    }
 }
@@ -32,6 +32,8 @@ pub enum ParseError {
     InvalidSkipCount(lexical_core::Error),
     /// The terminator is invalid.
     InvalidTerminator,
+    /// A skip count does not resolve to a position in the sequence.
+    InvalidPosition,
 }
 
 impl error::Error for ParseError {
@@ -56,6 +58,7 @@ impl fmt::Display for ParseError {
             Self::InvalidStatus => write!(f, "invalid status"),
             Self::InvalidSkipCount(_) => write!(f, "invalid skip count"),
             Self::InvalidTerminator => write!(f, "invalid terminator"),
+            Self::InvalidPosition => write!(f, "invalid position"),
         }
     }
 }
@@ -134,40 +137,50 @@ fn decode_positions(
     unmodified_base: UnmodifiedBase,
 ) -> Result<Vec<usize>, ParseError> {
     let mut positions = Vec::with_capacity(skip_counts.len());
-
-    let mut iter: Box<dyn Iterator<Item = usize>> = if is_reverse_complemented {
-        let unmodified_base = u8::from(unmodified_base.complement());
-
-        Box::new(
-            sequence
-                .as_ref()
-                .iter()
-                .enumerate()
-                .rev()
-                .filter(move |&(_, &base)| base == unmodified_base)
-                .map(|(i, _)| i),
-        )
-    } else {
-        let unmodified_base = u8::from(unmodified_base);
-
-        Box::new(
-            sequence
-                .as_ref()
-                .iter()
-                .enumerate()
-                .filter(move |&(_, &base)| base == unmodified_base)
-                .map(|(i, _)| i),
-        )
-    };
+    let mut iter = candidate_positions(is_reverse_complemented, sequence, unmodified_base);
 
     for &count in skip_counts {
-        let i = iter.nth(count).unwrap();
+        let i = iter.nth(count).ok_or(ParseError::InvalidPosition)?;
         positions.push(i);
     }
 
     Ok(positions)
 }
 
+/// Returns an iterator over the sequence positions that can be modified.
+///
+/// For reverse-complemented records, positions are iterated from the end of the sequence, as skip
+/// counts are relative to the original read orientation.
+pub(crate) fn candidate_positions(
+    is_reverse_complemented: bool,
+    sequence: &Sequence,
+    unmodified_base: UnmodifiedBase,
+) -> Box<dyn Iterator<Item = usize> + '_> {
+    let unmodified_base = if is_reverse_complemented {
+        unmodified_base.complement()
+    } else {
+        unmodified_base
+    };
+
+    let is_candidate = move |base: u8| {
+        unmodified_base == UnmodifiedBase::N
+            || base.to_ascii_uppercase() == u8::from(unmodified_base)
+    };
+
+    let positions = sequence
+        .as_ref()
+        .iter()
+        .enumerate()
+        .filter(move |&(_, &base)| is_candidate(base))
+        .map(|(i, _)| i);
+
+    if is_reverse_complemented {
+        Box::new(positions.rev())
+    } else {
+        Box::new(positions)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +250,23 @@ mod tests {
             parse_group(&mut src, is_reverse_complemented, &sequence),
             Err(ParseError::InvalidSkipCount(_))
         ));
+
+        let mut src = &b"C+m,8;"[..];
+        assert_eq!(
+            parse_group(&mut src, is_reverse_complemented, &sequence),
+            Err(ParseError::InvalidPosition)
+        );
+
+        let mut src = &b"N+n,0,3;"[..];
+        let actual = parse_group(&mut src, is_reverse_complemented, &sequence);
+        let expected = Group::new(
+            UnmodifiedBase::N,
+            Strand::Forward,
+            vec![modification::XANTHOSINE],
+            None,
+            vec![0, 4],
+        );
+        assert_eq!(actual, Ok(expected));
     }
 
     #[test]
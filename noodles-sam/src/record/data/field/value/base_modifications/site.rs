@@ -0,0 +1,81 @@
+use noodles_core::Position;
+
+use super::group::{Modification, Strand, UnmodifiedBase};
+
+/// A base modification call at a single position.
+///
+/// This is created by calling [`super::BaseModifications::sites`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Site {
+    unmodified_base: UnmodifiedBase,
+    strand: Strand,
+    modification: Modification,
+    position: usize,
+    read_position: usize,
+    reference_position: Option<Position>,
+    probability: Option<u8>,
+}
+
+impl Site {
+    pub(super) fn new(
+        unmodified_base: UnmodifiedBase,
+        strand: Strand,
+        modification: Modification,
+        position: usize,
+        read_position: usize,
+        reference_position: Option<Position>,
+        probability: Option<u8>,
+    ) -> Self {
+        Self {
+            unmodified_base,
+            strand,
+            modification,
+            position,
+            read_position,
+            reference_position,
+            probability,
+        }
+    }
+
+    /// Returns the unmodified base.
+    ///
+    /// This is relative to the original read orientation.
+    pub fn unmodified_base(&self) -> UnmodifiedBase {
+        self.unmodified_base
+    }
+
+    /// Returns the strand.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns the modification.
+    pub fn modification(&self) -> Modification {
+        self.modification
+    }
+
+    /// Returns the 0-based position on the sequence as stored in the record (`SEQ`).
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the 0-based position on the read in its original orientation.
+    ///
+    /// This differs from [`Self::position`] only when the record is reverse complemented.
+    pub fn read_position(&self) -> usize {
+        self.read_position
+    }
+
+    /// Returns the 1-based reference position the base is aligned to.
+    ///
+    /// This is `None` if the record is unmapped or the base is not aligned to the reference, e.g.,
+    /// it is part of an insertion or soft clip.
+    pub fn reference_position(&self) -> Option<Position> {
+        self.reference_position
+    }
+
+    /// Returns the modification probability (`ML`).
+    pub fn probability(&self) -> Option<u8> {
+        self.probability
+    }
+}
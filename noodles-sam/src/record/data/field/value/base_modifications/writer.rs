@@ -0,0 +1,188 @@
+use std::io;
+
+use super::{
+    BaseModifications, Group,
+    group::{Modification, Status, Strand},
+    parser::group::candidate_positions,
+};
+use crate::alignment::record_buf::Sequence;
+
+pub(super) fn write(
+    base_modifications: &BaseModifications,
+    is_reverse_complemented: bool,
+    sequence: &Sequence,
+) -> io::Result<(String, Vec<u8>)> {
+    let mut dst = String::new();
+    let mut probabilities = Vec::new();
+
+    for group in base_modifications.as_ref() {
+        write_group(&mut dst, group, is_reverse_complemented, sequence)?;
+        write_probabilities(&mut probabilities, group)?;
+    }
+
+    Ok((dst, probabilities))
+}
+
+fn write_group(
+    dst: &mut String,
+    group: &Group,
+    is_reverse_complemented: bool,
+    sequence: &Sequence,
+) -> io::Result<()> {
+    const DELIMITER: char = ',';
+    const TERMINATOR: char = ';';
+
+    dst.push(char::from(u8::from(group.unmodified_base())));
+
+    dst.push(match group.strand() {
+        Strand::Forward => '+',
+        Strand::Reverse => '-',
+    });
+
+    write_modifications(dst, group.modifications())?;
+
+    match group.status() {
+        Some(Status::Implicit) => dst.push('.'),
+        Some(Status::Explicit) => dst.push('?'),
+        None => {}
+    }
+
+    let skip_counts = encode_skip_counts(group, is_reverse_complemented, sequence)?;
+
+    for n in skip_counts {
+        dst.push(DELIMITER);
+        dst.push_str(&n.to_string());
+    }
+
+    dst.push(TERMINATOR);
+
+    Ok(())
+}
+
+fn write_modifications(dst: &mut String, modifications: &[Modification]) -> io::Result<()> {
+    if modifications.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "missing modifications",
+        ));
+    }
+
+    match modifications {
+        [Modification::ChebiId(id)] => dst.push_str(&id.to_string()),
+        _ => {
+            for modification in modifications {
+                match modification {
+                    Modification::Code(b) => dst.push(char::from(*b)),
+                    Modification::ChebiId(_) => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            "ChEBI IDs cannot be combined with other modifications",
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn encode_skip_counts(
+    group: &Group,
+    is_reverse_complemented: bool,
+    sequence: &Sequence,
+) -> io::Result<Vec<usize>> {
+    let mut candidates =
+        candidate_positions(is_reverse_complemented, sequence, group.unmodified_base());
+    let mut skip_counts = Vec::with_capacity(group.positions().len());
+
+    for &position in group.positions() {
+        let mut n = 0;
+
+        loop {
+            match candidates.next() {
+                Some(i) if i == position => break,
+                Some(_) => n += 1,
+                None => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "invalid base modification position",
+                    ));
+                }
+            }
+        }
+
+        skip_counts.push(n);
+    }
+
+    Ok(skip_counts)
+}
+
+fn write_probabilities(dst: &mut Vec<u8>, group: &Group) -> io::Result<()> {
+    let probabilities = group.probabilities();
+
+    if probabilities.is_empty() {
+        return Ok(());
+    }
+
+    let len = group.positions().len() * group.modifications().len();
+
+    if probabilities.len() == len {
+        dst.extend_from_slice(probabilities);
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid base modification probabilities",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::data::field::value::base_modifications::group::{
+        UnmodifiedBase, modification,
+    };
+
+    #[test]
+    fn test_write() -> Result<(), Box<dyn std::error::Error>> {
+        let sequence = Sequence::from(b"CACCCGATGACCGGCT");
+
+        for (s, is_reverse_complemented) in [
+            ("C+m,1,3,0;G-o,2;", false),
+            ("C+mh.,1,0;", false),
+            ("C+m?,1,0,0;", true),
+            ("C+76792,0;", false),
+            ("N+n,0,3;", false),
+        ] {
+            let base_modifications =
+                BaseModifications::parse(s, is_reverse_complemented, &sequence)?;
+            let (actual, _) = write(&base_modifications, is_reverse_complemented, &sequence)?;
+            assert_eq!(actual, s);
+        }
+
+        let base_modifications = BaseModifications::parse_with_probabilities(
+            "C+mh,1,3;",
+            &[200, 10, 5, 128],
+            false,
+            &sequence,
+        )?;
+        let (_, probabilities) = write(&base_modifications, false, &sequence)?;
+        assert_eq!(probabilities, [200, 10, 5, 128]);
+
+        let base_modifications = BaseModifications::from(vec![Group::new(
+            UnmodifiedBase::C,
+            Strand::Forward,
+            vec![modification::FIVE_METHYLCYTOSINE],
+            None,
+            vec![1],
+        )]);
+        assert!(matches!(
+            write(&base_modifications, false, &sequence),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}
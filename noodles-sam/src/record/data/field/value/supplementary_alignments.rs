@@ -0,0 +1,131 @@
+//! SAM record data field supplementary alignments value.
+
+mod parser;
+pub mod supplementary_alignment;
+
+pub use self::{parser::ParseError, supplementary_alignment::SupplementaryAlignment};
+
+use std::{fmt, str::FromStr};
+
+/// Supplementary alignments (`SA`).
+///
+/// These are the other alignments of a chimeric alignment.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SupplementaryAlignments(Vec<SupplementaryAlignment>);
+
+impl SupplementaryAlignments {
+    /// Parses supplementary alignments from a raw `SA` value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::record::data::field::value::{
+    ///     supplementary_alignments::supplementary_alignment::Strand, SupplementaryAlignments,
+    /// };
+    ///
+    /// let supplementary_alignments = SupplementaryAlignments::parse(b"sq0,8,-,4S4M,13,0;")?;
+    /// let supplementary_alignment = &supplementary_alignments.as_ref()[0];
+    ///
+    /// assert_eq!(supplementary_alignment.reference_sequence_name(), "sq0");
+    /// assert_eq!(supplementary_alignment.position(), Position::try_from(8)?);
+    /// assert_eq!(supplementary_alignment.strand(), Strand::Reverse);
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn parse(src: &[u8]) -> Result<Self, ParseError> {
+        parser::parse(src)
+    }
+}
+
+impl AsRef<[SupplementaryAlignment]> for SupplementaryAlignments {
+    fn as_ref(&self) -> &[SupplementaryAlignment] {
+        &self.0
+    }
+}
+
+impl AsMut<Vec<SupplementaryAlignment>> for SupplementaryAlignments {
+    fn as_mut(&mut self) -> &mut Vec<SupplementaryAlignment> {
+        &mut self.0
+    }
+}
+
+impl From<Vec<SupplementaryAlignment>> for SupplementaryAlignments {
+    fn from(supplementary_alignments: Vec<SupplementaryAlignment>) -> Self {
+        Self(supplementary_alignments)
+    }
+}
+
+impl From<SupplementaryAlignments> for Vec<SupplementaryAlignment> {
+    fn from(supplementary_alignments: SupplementaryAlignments) -> Self {
+        supplementary_alignments.0
+    }
+}
+
+impl fmt::Display for SupplementaryAlignments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const TERMINATOR: char = ';';
+
+        for supplementary_alignment in &self.0 {
+            write!(f, "{supplementary_alignment}{TERMINATOR}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for SupplementaryAlignments {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_core::Position;
+
+    use super::*;
+    use crate::alignment::{
+        record::{
+            MappingQuality,
+            cigar::{Op, op::Kind},
+        },
+        record_buf::Cigar,
+    };
+
+    #[test]
+    fn test_fmt() -> Result<(), Box<dyn std::error::Error>> {
+        use supplementary_alignment::Strand;
+
+        let supplementary_alignments = SupplementaryAlignments::from(vec![
+            SupplementaryAlignment::new(
+                "sq0",
+                Position::try_from(8)?,
+                Strand::Forward,
+                [Op::new(Kind::Match, 4), Op::new(Kind::SoftClip, 4)]
+                    .into_iter()
+                    .collect(),
+                MappingQuality::new(13),
+                0,
+            ),
+            SupplementaryAlignment::new(
+                "sq1",
+                Position::try_from(21)?,
+                Strand::Reverse,
+                Cigar::from(vec![Op::new(Kind::HardClip, 4), Op::new(Kind::Match, 4)]),
+                None,
+                2,
+            ),
+        ]);
+
+        assert_eq!(
+            supplementary_alignments.to_string(),
+            "sq0,8,+,4M4S,13,0;sq1,21,-,4H4M,255,2;"
+        );
+
+        assert_eq!(SupplementaryAlignments::default().to_string(), "");
+
+        Ok(())
+    }
+}
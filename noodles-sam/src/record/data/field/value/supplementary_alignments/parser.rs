@@ -0,0 +1,228 @@
+use std::{error, fmt};
+
+use bstr::ByteSlice;
+use noodles_core::Position;
+
+use super::{
+    SupplementaryAlignments,
+    supplementary_alignment::{Strand, SupplementaryAlignment},
+};
+use crate::{
+    alignment::{record::MappingQuality, record_buf::Cigar},
+    io::reader::record_buf::cigar::parse_cigar,
+};
+
+/// An error returned when supplementary alignments fail to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The reference sequence name is missing.
+    MissingReferenceSequenceName,
+    /// The position is missing.
+    MissingPosition,
+    /// The position is invalid.
+    InvalidPosition,
+    /// The strand is missing.
+    MissingStrand,
+    /// The strand is invalid.
+    InvalidStrand,
+    /// The CIGAR is missing.
+    MissingCigar,
+    /// The CIGAR is invalid.
+    InvalidCigar,
+    /// The mapping quality is missing.
+    MissingMappingQuality,
+    /// The mapping quality is invalid.
+    InvalidMappingQuality(lexical_core::Error),
+    /// The edit distance is missing.
+    MissingEditDistance,
+    /// The edit distance is invalid.
+    InvalidEditDistance(lexical_core::Error),
+    /// A supplementary alignment has unexpected trailing fields.
+    ExpectedEndOfAlignment,
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::InvalidMappingQuality(e) => Some(e),
+            Self::InvalidEditDistance(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingReferenceSequenceName => write!(f, "missing reference sequence name"),
+            Self::MissingPosition => write!(f, "missing position"),
+            Self::InvalidPosition => write!(f, "invalid position"),
+            Self::MissingStrand => write!(f, "missing strand"),
+            Self::InvalidStrand => write!(f, "invalid strand"),
+            Self::MissingCigar => write!(f, "missing CIGAR"),
+            Self::InvalidCigar => write!(f, "invalid CIGAR"),
+            Self::MissingMappingQuality => write!(f, "missing mapping quality"),
+            Self::InvalidMappingQuality(_) => write!(f, "invalid mapping quality"),
+            Self::MissingEditDistance => write!(f, "missing edit distance"),
+            Self::InvalidEditDistance(_) => write!(f, "invalid edit distance"),
+            Self::ExpectedEndOfAlignment => write!(f, "expected end of alignment"),
+        }
+    }
+}
+
+// The `SA` value is a list of `rname,pos,strand,CIGAR,mapQ,NM;` entries.
+pub(super) fn parse(src: &[u8]) -> Result<SupplementaryAlignments, ParseError> {
+    const TERMINATOR: u8 = b';';
+
+    let mut supplementary_alignments = Vec::new();
+
+    for buf in src.split(|&b| b == TERMINATOR) {
+        // The terminator of the last alignment is sometimes omitted.
+        if buf.is_empty() {
+            continue;
+        }
+
+        let supplementary_alignment = parse_supplementary_alignment(buf)?;
+        supplementary_alignments.push(supplementary_alignment);
+    }
+
+    Ok(SupplementaryAlignments(supplementary_alignments))
+}
+
+fn parse_supplementary_alignment(src: &[u8]) -> Result<SupplementaryAlignment, ParseError> {
+    const DELIMITER: u8 = b',';
+
+    let mut fields = src.split(|&b| b == DELIMITER);
+
+    let reference_sequence_name = fields
+        .next()
+        .filter(|buf| !buf.is_empty())
+        .ok_or(ParseError::MissingReferenceSequenceName)?;
+
+    let position = fields
+        .next()
+        .ok_or(ParseError::MissingPosition)
+        .and_then(parse_position)?;
+
+    let strand = fields
+        .next()
+        .ok_or(ParseError::MissingStrand)
+        .and_then(parse_strand)?;
+
+    let cigar = fields
+        .next()
+        .ok_or(ParseError::MissingCigar)
+        .and_then(|buf| {
+            let mut cigar = Cigar::default();
+            parse_cigar(buf, &mut cigar).map_err(|_| ParseError::InvalidCigar)?;
+            Ok(cigar)
+        })?;
+
+    let mapping_quality = fields
+        .next()
+        .ok_or(ParseError::MissingMappingQuality)
+        .and_then(|buf| {
+            lexical_core::parse::<u8>(buf)
+                .map(MappingQuality::new)
+                .map_err(ParseError::InvalidMappingQuality)
+        })?;
+
+    let edit_distance = fields
+        .next()
+        .ok_or(ParseError::MissingEditDistance)
+        .and_then(|buf| lexical_core::parse(buf).map_err(ParseError::InvalidEditDistance))?;
+
+    if fields.next().is_some() {
+        return Err(ParseError::ExpectedEndOfAlignment);
+    }
+
+    Ok(SupplementaryAlignment::new(
+        reference_sequence_name.as_bstr(),
+        position,
+        strand,
+        cigar,
+        mapping_quality,
+        edit_distance,
+    ))
+}
+
+fn parse_position(src: &[u8]) -> Result<Position, ParseError> {
+    lexical_core::parse::<usize>(src)
+        .ok()
+        .and_then(Position::new)
+        .ok_or(ParseError::InvalidPosition)
+}
+
+fn parse_strand(src: &[u8]) -> Result<Strand, ParseError> {
+    match src {
+        b"+" => Ok(Strand::Forward),
+        b"-" => Ok(Strand::Reverse),
+        _ => Err(ParseError::InvalidStrand),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::alignment::record::cigar::{Op, op::Kind};
+
+    #[test]
+    fn test_parse() -> Result<(), Box<dyn std::error::Error>> {
+        let actual = parse(b"sq0,8,+,4M4S,13,0;sq1,21,-,4H4M,255,2;")?;
+
+        let expected = SupplementaryAlignments(vec![
+            SupplementaryAlignment::new(
+                "sq0",
+                Position::try_from(8)?,
+                Strand::Forward,
+                [Op::new(Kind::Match, 4), Op::new(Kind::SoftClip, 4)]
+                    .into_iter()
+                    .collect(),
+                MappingQuality::new(13),
+                0,
+            ),
+            SupplementaryAlignment::new(
+                "sq1",
+                Position::try_from(21)?,
+                Strand::Reverse,
+                [Op::new(Kind::HardClip, 4), Op::new(Kind::Match, 4)]
+                    .into_iter()
+                    .collect(),
+                None,
+                2,
+            ),
+        ]);
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(parse(b"sq0,8,+,4M,13,0")?.as_ref().len(), 1);
+        assert!(parse(b"")?.as_ref().is_empty());
+
+        assert_eq!(
+            parse(b",8,+,4M,13,0;"),
+            Err(ParseError::MissingReferenceSequenceName)
+        );
+        assert_eq!(parse(b"sq0;"), Err(ParseError::MissingPosition));
+        assert_eq!(parse(b"sq0,0,+,4M,13,0;"), Err(ParseError::InvalidPosition));
+        assert_eq!(parse(b"sq0,8,.,4M,13,0;"), Err(ParseError::InvalidStrand));
+        assert_eq!(parse(b"sq0,8,+,4Z,13,0;"), Err(ParseError::InvalidCigar));
+        assert!(matches!(
+            parse(b"sq0,8,+,4M,mq,0;"),
+            Err(ParseError::InvalidMappingQuality(_))
+        ));
+        assert_eq!(
+            parse(b"sq0,8,+,4M,13;"),
+            Err(ParseError::MissingEditDistance)
+        );
+        assert!(matches!(
+            parse(b"sq0,8,+,4M,13,-1;"),
+            Err(ParseError::InvalidEditDistance(_))
+        ));
+        assert_eq!(
+            parse(b"sq0,8,+,4M,13,0,x;"),
+            Err(ParseError::ExpectedEndOfAlignment)
+        );
+
+        Ok(())
+    }
+}
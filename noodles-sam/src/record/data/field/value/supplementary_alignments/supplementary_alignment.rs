@@ -0,0 +1,140 @@
+//! SAM record data field supplementary alignments value supplementary alignment.
+
+use std::{fmt, str};
+
+use bstr::{BStr, BString};
+use noodles_core::Position;
+
+use crate::{
+    alignment::{record::MappingQuality, record_buf::Cigar},
+    io::writer::record::write_cigar,
+};
+
+/// The strand of a supplementary alignment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strand {
+    /// Forward strand (`+`).
+    Forward,
+    /// Reverse strand (`-`).
+    Reverse,
+}
+
+/// A supplementary alignment.
+///
+/// This is a part of a chimeric alignment, listed in the `SA` data field.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SupplementaryAlignment {
+    reference_sequence_name: BString,
+    position: Position,
+    strand: Strand,
+    cigar: Cigar,
+    mapping_quality: Option<MappingQuality>,
+    edit_distance: u32,
+}
+
+impl SupplementaryAlignment {
+    /// Creates a supplementary alignment.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_core::Position;
+    /// use noodles_sam::{
+    ///     alignment::{record::MappingQuality, record_buf::Cigar},
+    ///     record::data::field::value::supplementary_alignments::{
+    ///         supplementary_alignment::Strand, SupplementaryAlignment,
+    ///     },
+    /// };
+    ///
+    /// let supplementary_alignment = SupplementaryAlignment::new(
+    ///     "sq0",
+    ///     Position::MIN,
+    ///     Strand::Forward,
+    ///     Cigar::default(),
+    ///     MappingQuality::new(13),
+    ///     0,
+    /// );
+    /// ```
+    pub fn new<N>(
+        reference_sequence_name: N,
+        position: Position,
+        strand: Strand,
+        cigar: Cigar,
+        mapping_quality: Option<MappingQuality>,
+        edit_distance: u32,
+    ) -> Self
+    where
+        N: Into<BString>,
+    {
+        Self {
+            reference_sequence_name: reference_sequence_name.into(),
+            position,
+            strand,
+            cigar,
+            mapping_quality,
+            edit_distance,
+        }
+    }
+
+    /// Returns the reference sequence name.
+    pub fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name.as_ref()
+    }
+
+    /// Returns the alignment start position.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the strand.
+    pub fn strand(&self) -> Strand {
+        self.strand
+    }
+
+    /// Returns whether the alignment is on the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.strand == Strand::Reverse
+    }
+
+    /// Returns the CIGAR operations.
+    pub fn cigar(&self) -> &Cigar {
+        &self.cigar
+    }
+
+    /// Returns the mapping quality.
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+impl fmt::Display for SupplementaryAlignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const MISSING_MAPPING_QUALITY: u8 = 255;
+        const DELIMITER: char = ',';
+
+        let strand = match self.strand {
+            Strand::Forward => '+',
+            Strand::Reverse => '-',
+        };
+
+        let mut cigar = Vec::new();
+        write_cigar(&mut cigar, &self.cigar).map_err(|_| fmt::Error)?;
+        let cigar = str::from_utf8(&cigar).map_err(|_| fmt::Error)?;
+
+        let mapping_quality = self
+            .mapping_quality
+            .map(|mapping_quality| mapping_quality.get())
+            .unwrap_or(MISSING_MAPPING_QUALITY);
+
+        write!(
+            f,
+            "{}{DELIMITER}{}{DELIMITER}{strand}{DELIMITER}{cigar}{DELIMITER}{mapping_quality}{DELIMITER}{}",
+            self.reference_sequence_name, self.position, self.edit_distance
+        )
+    }
+}
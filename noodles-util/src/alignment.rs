@@ -3,8 +3,12 @@
 #[cfg(feature = "async")]
 pub mod r#async;
 
+pub mod calmd;
+pub mod chimera;
+pub mod fixmate;
 pub mod io;
 pub mod iter;
+pub mod markdup;
 mod record;
 
 pub use self::record::Record;
@@ -0,0 +1,594 @@
+//! Alignment record mismatched positions (`MD`) and edit distance (`NM`) calculation.
+//!
+//! This is similar to `samtools calmd`. Given a reference sequence repository, the `MD` and `NM`
+//! data field values are calculated from the CIGAR operations, sequence, and reference sequence
+//! of an alignment record.
+
+use std::io;
+
+use bstr::{BStr, BString};
+use noodles_core::Position;
+use noodles_fasta as fasta;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        Record, RecordBuf,
+        record::{Cigar, Sequence, cigar::op::Kind, data::field::Tag},
+        record_buf::data::field::Value,
+    },
+};
+
+const MISSING_BASE: u8 = b'N';
+const MATCH_BASE: u8 = b'=';
+
+/// Calculated mismatched positions (`MD`) and edit distance (`NM`) values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fields {
+    mismatched_positions: BString,
+    edit_distance: u32,
+}
+
+impl Fields {
+    /// Returns the mismatched positions (`MD`).
+    pub fn mismatched_positions(&self) -> &BStr {
+        self.mismatched_positions.as_ref()
+    }
+
+    /// Returns the edit distance (`NM`).
+    pub fn edit_distance(&self) -> u32 {
+        self.edit_distance
+    }
+}
+
+/// A difference between a record's existing data fields and the calculated values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Discrepancy {
+    /// The mismatched positions (`MD`) field is missing or differs.
+    MismatchedPositions {
+        /// The calculated value.
+        expected: BString,
+        /// The record value, if present.
+        actual: Option<BString>,
+    },
+    /// The edit distance (`NM`) field is missing or differs.
+    EditDistance {
+        /// The calculated value.
+        expected: u32,
+        /// The record value, if present.
+        actual: Option<i64>,
+    },
+}
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) of an alignment record.
+///
+/// This returns `None` if the record is unmapped, has no CIGAR operations, or has no sequence.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_fasta::{self as fasta, record::{Definition, Sequence}};
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{record::{cigar::{op::Kind, Op}, Flags}, RecordBuf},
+///     header::record::value::{map::ReferenceSequence, Map},
+/// };
+/// use noodles_util::alignment::calmd;
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(8.try_into()?))
+///     .build();
+///
+/// let repository = fasta::Repository::new(vec![fasta::Record::new(
+///     Definition::new("sq0", None),
+///     Sequence::from(b"ACGTACGT".to_vec()),
+/// )]);
+///
+/// let record = RecordBuf::builder()
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(2)?)
+///     .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect())
+///     .set_sequence(b"CGCT".into())
+///     .build();
+///
+/// let fields = calmd::calculate(&header, &repository, &record)?.unwrap();
+/// assert_eq!(fields.mismatched_positions(), "2T0A0");
+/// assert_eq!(fields.edit_distance(), 2);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn calculate<R>(
+    header: &sam::Header,
+    repository: &fasta::Repository,
+    record: &R,
+) -> io::Result<Option<Fields>>
+where
+    R: Record + ?Sized,
+{
+    let Some((reference_sequence, alignment_start)) =
+        get_reference_sequence_and_alignment_start(header, repository, record)?
+    else {
+        return Ok(None);
+    };
+
+    calculate_with_reference_sequence_and_record(record, &reference_sequence, alignment_start)
+        .map(Some)
+}
+
+fn calculate_with_reference_sequence_and_record<R>(
+    record: &R,
+    reference_sequence: &fasta::record::Sequence,
+    alignment_start: Position,
+) -> io::Result<Fields>
+where
+    R: Record + ?Sized,
+{
+    let sequence: Vec<u8> = record.sequence().iter().collect();
+
+    calculate_with_reference_sequence(
+        &record.cigar(),
+        &sequence,
+        reference_sequence,
+        alignment_start,
+    )
+}
+
+/// Calculates the mismatched positions (`MD`) and edit distance (`NM`) using the given reference
+/// sequence.
+///
+/// `alignment_start` is the 1-based position in `reference_sequence` of the first reference base
+/// consumed by the alignment.
+pub fn calculate_with_reference_sequence<C>(
+    cigar: &C,
+    sequence: &[u8],
+    reference_sequence: &fasta::record::Sequence,
+    alignment_start: Position,
+) -> io::Result<Fields>
+where
+    C: Cigar + ?Sized,
+{
+    let mut mismatched_positions = BString::default();
+    let mut edit_distance = 0;
+    let mut match_count = 0;
+
+    let mut read_position = 0;
+    let mut reference_position = usize::from(alignment_start);
+
+    for result in cigar.iter() {
+        let op = result?;
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                let read_bases = get_read_bases(sequence, read_position, len)?;
+                let reference_bases =
+                    get_reference_bases(reference_sequence, reference_position, len)?;
+
+                for (&read_base, &reference_base) in read_bases.iter().zip(reference_bases) {
+                    if bases_eq(read_base, reference_base) {
+                        match_count += 1;
+                    } else {
+                        push_match_count(&mut mismatched_positions, match_count);
+                        mismatched_positions.push(reference_base.to_ascii_uppercase());
+                        match_count = 0;
+                        edit_distance += 1;
+                    }
+                }
+
+                read_position += len;
+                reference_position += len;
+            }
+            Kind::Insertion => {
+                edit_distance += len;
+                read_position += len;
+            }
+            Kind::Deletion => {
+                let reference_bases =
+                    get_reference_bases(reference_sequence, reference_position, len)?;
+
+                push_match_count(&mut mismatched_positions, match_count);
+                mismatched_positions.push(b'^');
+                mismatched_positions.extend(reference_bases.iter().map(u8::to_ascii_uppercase));
+                match_count = 0;
+
+                edit_distance += len;
+                reference_position += len;
+            }
+            Kind::Skip => reference_position += len,
+            Kind::SoftClip => read_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    push_match_count(&mut mismatched_positions, match_count);
+
+    let edit_distance =
+        u32::try_from(edit_distance).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    Ok(Fields {
+        mismatched_positions,
+        edit_distance,
+    })
+}
+
+/// Updates the mismatched positions (`MD`) and edit distance (`NM`) data fields of a record.
+///
+/// If `replace_matches` is set, read bases that match the reference sequence are replaced with
+/// `=`.
+///
+/// This returns whether the record was updated. Unmapped records and records without CIGAR
+/// operations or a sequence are left unchanged.
+pub fn update(
+    header: &sam::Header,
+    repository: &fasta::Repository,
+    record: &mut RecordBuf,
+    replace_matches: bool,
+) -> io::Result<bool> {
+    let Some((reference_sequence, alignment_start)) =
+        get_reference_sequence_and_alignment_start(header, repository, record)?
+    else {
+        return Ok(false);
+    };
+
+    let fields =
+        calculate_with_reference_sequence_and_record(record, &reference_sequence, alignment_start)?;
+
+    if replace_matches {
+        replace_matching_bases(record, &reference_sequence, alignment_start)?;
+    }
+
+    let data = record.data_mut();
+
+    data.insert(
+        Tag::MISMATCHED_POSITIONS,
+        Value::String(fields.mismatched_positions),
+    );
+
+    data.insert(Tag::EDIT_DISTANCE, Value::from(fields.edit_distance));
+
+    Ok(true)
+}
+
+/// Validates the mismatched positions (`MD`) and edit distance (`NM`) data fields of a record.
+///
+/// This returns the list of discrepancies between the record's data fields and the calculated
+/// values. Unmapped records and records without CIGAR operations or a sequence have no
+/// discrepancies.
+pub fn validate<R>(
+    header: &sam::Header,
+    repository: &fasta::Repository,
+    record: &R,
+) -> io::Result<Vec<Discrepancy>>
+where
+    R: Record + ?Sized,
+{
+    use sam::alignment::record::data::field::Value;
+
+    let mut discrepancies = Vec::new();
+
+    let Some(fields) = calculate(header, repository, record)? else {
+        return Ok(discrepancies);
+    };
+
+    let data = record.data();
+
+    let actual_mismatched_positions = match data.get(&Tag::MISMATCHED_POSITIONS).transpose()? {
+        Some(Value::String(s)) => Some(BString::from(s.to_vec())),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid MD field type",
+            ));
+        }
+        None => None,
+    };
+
+    if actual_mismatched_positions.as_ref() != Some(&fields.mismatched_positions) {
+        discrepancies.push(Discrepancy::MismatchedPositions {
+            expected: fields.mismatched_positions,
+            actual: actual_mismatched_positions,
+        });
+    }
+
+    let actual_edit_distance =
+        match data.get(&Tag::EDIT_DISTANCE).transpose()? {
+            Some(value) => Some(value.as_int().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidData, "invalid NM field type")
+            })?),
+            None => None,
+        };
+
+    if actual_edit_distance != Some(i64::from(fields.edit_distance)) {
+        discrepancies.push(Discrepancy::EditDistance {
+            expected: fields.edit_distance,
+            actual: actual_edit_distance,
+        });
+    }
+
+    Ok(discrepancies)
+}
+
+fn get_reference_sequence_and_alignment_start<R>(
+    header: &sam::Header,
+    repository: &fasta::Repository,
+    record: &R,
+) -> io::Result<Option<(fasta::record::Sequence, Position)>>
+where
+    R: Record + ?Sized,
+{
+    if record.flags()?.is_unmapped() || record.cigar().is_empty() || record.sequence().is_empty() {
+        return Ok(None);
+    }
+
+    let Some((name, _)) = record.reference_sequence(header).transpose()? else {
+        return Ok(None);
+    };
+
+    let Some(alignment_start) = record.alignment_start().transpose()? else {
+        return Ok(None);
+    };
+
+    let reference_sequence = repository.get(name).transpose()?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("missing reference sequence: {name}"),
+        )
+    })?;
+
+    Ok(Some((reference_sequence, alignment_start)))
+}
+
+fn replace_matching_bases(
+    record: &mut RecordBuf,
+    reference_sequence: &fasta::record::Sequence,
+    alignment_start: Position,
+) -> io::Result<()> {
+    let mut read_position = 0;
+    let mut reference_position = usize::from(alignment_start);
+
+    let ops: Vec<_> = record.cigar().as_ref().to_vec();
+    let sequence = record.sequence_mut().as_mut();
+
+    for op in ops {
+        let len = op.len();
+
+        match op.kind() {
+            Kind::Match | Kind::SequenceMatch | Kind::SequenceMismatch => {
+                let reference_bases =
+                    get_reference_bases(reference_sequence, reference_position, len)?;
+
+                let read_bases = sequence
+                    .get_mut(read_position..read_position + len)
+                    .ok_or_else(|| {
+                        io::Error::new(io::ErrorKind::InvalidData, "sequence length mismatch")
+                    })?;
+
+                for (read_base, &reference_base) in read_bases.iter_mut().zip(reference_bases) {
+                    if bases_eq(*read_base, reference_base) {
+                        *read_base = MATCH_BASE;
+                    }
+                }
+
+                read_position += len;
+                reference_position += len;
+            }
+            Kind::Insertion | Kind::SoftClip => read_position += len,
+            Kind::Deletion | Kind::Skip => reference_position += len,
+            Kind::HardClip | Kind::Pad => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn get_read_bases(sequence: &[u8], start: usize, len: usize) -> io::Result<&[u8]> {
+    sequence
+        .get(start..start + len)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "sequence length mismatch"))
+}
+
+fn get_reference_bases(
+    reference_sequence: &fasta::record::Sequence,
+    start: usize,
+    len: usize,
+) -> io::Result<&[u8]> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+
+    let (Some(start), Some(end)) = (Position::new(start), Position::new(start + len - 1)) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid position",
+        ));
+    };
+
+    reference_sequence.get(start..=end).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "alignment exceeds reference sequence bounds",
+        )
+    })
+}
+
+fn bases_eq(read_base: u8, reference_base: u8) -> bool {
+    read_base == MATCH_BASE
+        || (read_base.eq_ignore_ascii_case(&reference_base)
+            && !read_base.eq_ignore_ascii_case(&MISSING_BASE))
+}
+
+fn push_match_count(dst: &mut BString, n: usize) {
+    dst.extend_from_slice(n.to_string().as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use fasta::record::{Definition, Sequence};
+    use sam::{
+        alignment::record::{
+            Flags,
+            cigar::{Op, op::Kind},
+        },
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(16.try_into()?))
+            .build())
+    }
+
+    fn build_repository() -> fasta::Repository {
+        fasta::Repository::new(vec![fasta::Record::new(
+            Definition::new("sq0", None),
+            Sequence::from(b"ACGTACGTacgtNNGG".to_vec()),
+        )])
+    }
+
+    fn build_record(
+        alignment_start: usize,
+        ops: &[Op],
+        sequence: &[u8],
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        Ok(RecordBuf::builder()
+            .set_flags(Flags::empty())
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar(ops.iter().copied().collect())
+            .set_sequence(sequence.into())
+            .build())
+    }
+
+    #[test]
+    fn test_calculate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let repository = build_repository();
+
+        let record = build_record(1, &[Op::new(Kind::Match, 8)], b"ACGTACGT")?;
+        let fields = calculate(&header, &repository, &record)?;
+        assert_eq!(
+            fields,
+            Some(Fields {
+                mismatched_positions: BString::from("8"),
+                edit_distance: 0,
+            })
+        );
+
+        // reference: ACGTACGTacgt
+        //      read: AC--ACTTACGTT
+        let record = build_record(
+            1,
+            &[
+                Op::new(Kind::SoftClip, 1),
+                Op::new(Kind::Match, 2),
+                Op::new(Kind::Deletion, 2),
+                Op::new(Kind::Match, 4),
+                Op::new(Kind::Insertion, 1),
+                Op::new(Kind::Match, 4),
+            ],
+            b"GACACTTTacgt",
+        )?;
+        let fields = calculate(&header, &repository, &record)?;
+        assert_eq!(
+            fields,
+            Some(Fields {
+                mismatched_positions: BString::from("2^GT2G5"),
+                edit_distance: 4,
+            })
+        );
+
+        let record = build_record(11, &[Op::new(Kind::Match, 4)], b"GTNN")?;
+        let fields = calculate(&header, &repository, &record)?;
+        assert_eq!(
+            fields,
+            Some(Fields {
+                mismatched_positions: BString::from("2N0N0"),
+                edit_distance: 2,
+            })
+        );
+
+        let record = RecordBuf::default();
+        assert!(calculate(&header, &repository, &record)?.is_none());
+
+        let record = build_record(15, &[Op::new(Kind::Match, 4)], b"GGAA")?;
+        assert!(matches!(
+            calculate(&header, &repository, &record),
+            Err(e) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_update() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let repository = build_repository();
+
+        let mut record = build_record(
+            2,
+            &[Op::new(Kind::Match, 3), Op::new(Kind::SoftClip, 1)],
+            b"CATT",
+        )?;
+
+        assert!(update(&header, &repository, &mut record, true)?);
+
+        assert_eq!(record.sequence().as_ref(), b"=A=T");
+        assert_eq!(
+            record.data().get(&Tag::MISMATCHED_POSITIONS),
+            Some(&Value::from("1G1"))
+        );
+        assert_eq!(
+            record.data().get(&Tag::EDIT_DISTANCE),
+            Some(&Value::UInt8(1))
+        );
+
+        let mut record = RecordBuf::default();
+        assert!(!update(&header, &repository, &mut record, true)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+        let repository = build_repository();
+
+        let mut record = build_record(1, &[Op::new(Kind::Match, 4)], b"ACTT")?;
+
+        assert_eq!(
+            validate(&header, &repository, &record)?,
+            [
+                Discrepancy::MismatchedPositions {
+                    expected: BString::from("2G1"),
+                    actual: None,
+                },
+                Discrepancy::EditDistance {
+                    expected: 1,
+                    actual: None,
+                },
+            ]
+        );
+
+        record
+            .data_mut()
+            .insert(Tag::MISMATCHED_POSITIONS, Value::from("2G1"));
+        record
+            .data_mut()
+            .insert(Tag::EDIT_DISTANCE, Value::Int32(2));
+
+        assert_eq!(
+            validate(&header, &repository, &record)?,
+            [Discrepancy::EditDistance {
+                expected: 1,
+                actual: Some(2),
+            }]
+        );
+
+        update(&header, &repository, &mut record, false)?;
+        assert!(validate(&header, &repository, &record)?.is_empty());
+
+        Ok(())
+    }
+}
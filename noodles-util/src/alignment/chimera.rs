@@ -0,0 +1,522 @@
+//! Alignment record chimeric alignment reconstruction.
+//!
+//! A chimeric alignment is a read whose parts align to distinct regions, e.g., across a
+//! structural variant breakpoint. It is represented by a primary record and one or more
+//! supplementary records, each listing the others in its `SA` data field.
+//!
+//! [`reconstruct`] collects these alignments into a [`Chain`] per read segment, ordered by their
+//! position on the read.
+
+use std::io;
+
+use bstr::{BStr, BString};
+use noodles_core::Position;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        Record,
+        record::{
+            Cigar, Flags, MappingQuality,
+            cigar::{Op, op::Kind},
+            data::field::{Tag, Value},
+        },
+    },
+    record::data::field::value::{
+        SupplementaryAlignments, supplementary_alignments::SupplementaryAlignment,
+    },
+};
+
+/// A linear alignment of a part of a read.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    reference_sequence_name: BString,
+    alignment_start: Position,
+    alignment_end: Position,
+    is_reverse_complemented: bool,
+    mapping_quality: Option<MappingQuality>,
+    query_start: usize,
+    query_end: usize,
+    is_primary: bool,
+}
+
+impl Segment {
+    /// Returns the reference sequence name.
+    pub fn reference_sequence_name(&self) -> &BStr {
+        self.reference_sequence_name.as_ref()
+    }
+
+    /// Returns the alignment start.
+    pub fn alignment_start(&self) -> Position {
+        self.alignment_start
+    }
+
+    /// Returns the alignment end.
+    pub fn alignment_end(&self) -> Position {
+        self.alignment_end
+    }
+
+    /// Returns whether the alignment is on the reverse strand.
+    pub fn is_reverse_complemented(&self) -> bool {
+        self.is_reverse_complemented
+    }
+
+    /// Returns the mapping quality.
+    pub fn mapping_quality(&self) -> Option<MappingQuality> {
+        self.mapping_quality
+    }
+
+    /// Returns the 0-based start of the aligned part of the read.
+    ///
+    /// This is relative to the original read orientation.
+    pub fn query_start(&self) -> usize {
+        self.query_start
+    }
+
+    /// Returns the 0-based exclusive end of the aligned part of the read.
+    ///
+    /// This is relative to the original read orientation.
+    pub fn query_end(&self) -> usize {
+        self.query_end
+    }
+
+    /// Returns whether this is the primary alignment.
+    pub fn is_primary(&self) -> bool {
+        self.is_primary
+    }
+
+    fn is_same_alignment(&self, other: &Self) -> bool {
+        self.reference_sequence_name == other.reference_sequence_name
+            && self.alignment_start == other.alignment_start
+            && self.is_reverse_complemented == other.is_reverse_complemented
+    }
+}
+
+/// The linear alignments of a read segment, ordered by their position on the read.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Chain {
+    segments: Vec<Segment>,
+}
+
+impl Chain {
+    /// Returns the segments.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Returns the primary segment.
+    pub fn primary_segment(&self) -> Option<&Segment> {
+        self.segments.iter().find(|segment| segment.is_primary())
+    }
+
+    /// Returns whether the chain has more than one segment.
+    pub fn is_chimeric(&self) -> bool {
+        self.segments.len() > 1
+    }
+
+    /// Returns an iterator over pairs of segments that are adjacent on the read.
+    ///
+    /// Each pair is the evidence of a junction, i.e., the end of the first segment joins the
+    /// start of the second segment in read order.
+    pub fn junctions(&self) -> impl Iterator<Item = (&Segment, &Segment)> {
+        self.segments.windows(2).map(|pair| (&pair[0], &pair[1]))
+    }
+
+    fn add(&mut self, segment: Segment) {
+        match self
+            .segments
+            .iter_mut()
+            .find(|s| s.is_same_alignment(&segment))
+        {
+            Some(s) => s.is_primary |= segment.is_primary,
+            None => self.segments.push(segment),
+        }
+    }
+}
+
+/// Reconstructs the chimeric alignment chains of the records of a single template.
+///
+/// The records are expected to share a name, e.g., a [`super::iter::Template`]. One chain is
+/// built for each read segment (e.g., the first and last segments of a pair) that has a mapped
+/// primary or supplementary record. Secondary and unmapped records are ignored.
+///
+/// Alignments listed in the `SA` data field of a record but missing from the given records are
+/// also added to the chain. This allows a chain to be reconstructed from a primary record alone.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::{
+///     self as sam,
+///     alignment::{
+///         record::{cigar::{op::Kind, Op}, data::field::Tag, Flags},
+///         record_buf::data::field::Value,
+///         RecordBuf,
+///     },
+///     header::record::value::{map::ReferenceSequence, Map},
+/// };
+/// use noodles_util::alignment::chimera;
+/// use std::num::NonZeroUsize;
+///
+/// let header = sam::Header::builder()
+///     .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(NonZeroUsize::try_from(1000)?))
+///     .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(NonZeroUsize::try_from(1000)?))
+///     .build();
+///
+/// let record = RecordBuf::builder()
+///     .set_name("r0")
+///     .set_flags(Flags::empty())
+///     .set_reference_sequence_id(0)
+///     .set_alignment_start(Position::try_from(100)?)
+///     .set_cigar([Op::new(Kind::Match, 5), Op::new(Kind::SoftClip, 3)].into_iter().collect())
+///     .set_data(
+///         [(Tag::OTHER_ALIGNMENTS, Value::from("sq1,500,+,5S3M,60,0;"))]
+///             .into_iter()
+///             .collect(),
+///     )
+///     .build();
+///
+/// let chains = chimera::reconstruct(&header, &[record])?;
+/// assert_eq!(chains.len(), 1);
+///
+/// let segments = chains[0].segments();
+/// assert_eq!(segments.len(), 2);
+/// assert_eq!(segments[0].reference_sequence_name(), "sq0");
+/// assert_eq!(segments[1].reference_sequence_name(), "sq1");
+/// assert_eq!(segments[1].query_start(), 5);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn reconstruct<R>(header: &sam::Header, records: &[R]) -> io::Result<Vec<Chain>>
+where
+    R: Record,
+{
+    let mut chains: Vec<(SegmentKind, Chain)> = Vec::new();
+
+    for record in records {
+        let flags = record.flags()?;
+
+        if flags.is_secondary() || flags.is_unmapped() {
+            continue;
+        }
+
+        let kind = SegmentKind::from(flags);
+
+        let i = match chains.iter().position(|(k, _)| *k == kind) {
+            Some(i) => i,
+            None => {
+                chains.push((kind, Chain::default()));
+                chains.len() - 1
+            }
+        };
+
+        let chain = &mut chains[i].1;
+
+        chain.add(segment_from_record(header, record, flags)?);
+
+        for (j, supplementary_alignment) in supplementary_alignments(record)?
+            .as_ref()
+            .iter()
+            .enumerate()
+        {
+            // By convention, the first alignment listed by a supplementary record is the primary
+            // alignment.
+            let is_primary = flags.is_supplementary() && j == 0;
+            chain.add(segment_from_supplementary_alignment(
+                supplementary_alignment,
+                is_primary,
+            )?);
+        }
+    }
+
+    chains.sort_by_key(|(kind, _)| *kind);
+
+    Ok(chains
+        .into_iter()
+        .map(|(_, mut chain)| {
+            chain
+                .segments
+                .sort_by_key(|segment| (segment.query_start, segment.query_end));
+            chain
+        })
+        .collect())
+}
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum SegmentKind {
+    First,
+    Last,
+    Other,
+}
+
+impl From<Flags> for SegmentKind {
+    fn from(flags: Flags) -> Self {
+        if flags.is_first_segment() || !flags.is_segmented() {
+            Self::First
+        } else if flags.is_last_segment() {
+            Self::Last
+        } else {
+            Self::Other
+        }
+    }
+}
+
+fn segment_from_record<R>(header: &sam::Header, record: &R, flags: Flags) -> io::Result<Segment>
+where
+    R: Record,
+{
+    let (reference_sequence_name, _) = record
+        .reference_sequence(header)
+        .transpose()?
+        .ok_or_else(|| missing_field_error("reference sequence ID"))?;
+
+    let alignment_start = record
+        .alignment_start()
+        .transpose()?
+        .ok_or_else(|| missing_field_error("alignment start"))?;
+
+    let mapping_quality = record.mapping_quality().transpose()?;
+    let cigar = record.cigar();
+
+    build_segment(
+        reference_sequence_name,
+        alignment_start,
+        flags.is_reverse_complemented(),
+        cigar.as_ref(),
+        mapping_quality,
+        !flags.is_supplementary(),
+    )
+}
+
+fn segment_from_supplementary_alignment(
+    supplementary_alignment: &SupplementaryAlignment,
+    is_primary: bool,
+) -> io::Result<Segment> {
+    build_segment(
+        supplementary_alignment.reference_sequence_name(),
+        supplementary_alignment.position(),
+        supplementary_alignment.is_reverse_complemented(),
+        supplementary_alignment.cigar(),
+        supplementary_alignment.mapping_quality(),
+        is_primary,
+    )
+}
+
+fn build_segment<C>(
+    reference_sequence_name: &BStr,
+    alignment_start: Position,
+    is_reverse_complemented: bool,
+    cigar: &C,
+    mapping_quality: Option<MappingQuality>,
+    is_primary: bool,
+) -> io::Result<Segment>
+where
+    C: Cigar + ?Sized,
+{
+    let ops: Vec<_> = cigar.iter().collect::<io::Result<_>>()?;
+
+    let alignment_span = cigar.alignment_span()?;
+    let alignment_end = usize::from(alignment_start)
+        .checked_add(alignment_span.saturating_sub(1))
+        .and_then(Position::new)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid alignment end"))?;
+
+    let leading_clip_length = clip_length(ops.iter());
+    let trailing_clip_length = clip_length(ops.iter().rev());
+    let query_span = cigar.query_alignment_span()?;
+
+    let query_start = if is_reverse_complemented {
+        trailing_clip_length
+    } else {
+        leading_clip_length
+    };
+
+    Ok(Segment {
+        reference_sequence_name: reference_sequence_name.into(),
+        alignment_start,
+        alignment_end,
+        is_reverse_complemented,
+        mapping_quality,
+        query_start,
+        query_end: query_start + query_span,
+        is_primary,
+    })
+}
+
+fn clip_length<'a, I>(ops: I) -> usize
+where
+    I: Iterator<Item = &'a Op>,
+{
+    ops.take_while(|op| matches!(op.kind(), Kind::SoftClip | Kind::HardClip))
+        .map(|op| op.len())
+        .sum()
+}
+
+fn supplementary_alignments<R>(record: &R) -> io::Result<SupplementaryAlignments>
+where
+    R: Record,
+{
+    match record.data().get(&Tag::OTHER_ALIGNMENTS).transpose()? {
+        Some(Value::String(s)) => SupplementaryAlignments::parse(s)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+        Some(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "invalid SA field type",
+        )),
+        None => Ok(SupplementaryAlignments::default()),
+    }
+}
+
+fn missing_field_error(name: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("missing {name}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use noodles_sam::{
+        alignment::{RecordBuf, record_buf::data::field::Value as ValueBuf},
+        header::record::value::{Map, map::ReferenceSequence},
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        const LENGTH: NonZeroUsize = match NonZeroUsize::new(1000) {
+            Some(n) => n,
+            None => unreachable!(),
+        };
+
+        Ok(sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(LENGTH))
+            .add_reference_sequence("sq1", Map::<ReferenceSequence>::new(LENGTH))
+            .build())
+    }
+
+    fn build_record(
+        flags: Flags,
+        reference_sequence_id: usize,
+        alignment_start: usize,
+        ops: Vec<Op>,
+        sa: &str,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        Ok(RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(flags)
+            .set_reference_sequence_id(reference_sequence_id)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar(ops.into_iter().collect())
+            .set_data(
+                [(Tag::OTHER_ALIGNMENTS, ValueBuf::from(sa))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build())
+    }
+
+    #[test]
+    fn test_reconstruct() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(
+                Flags::empty(),
+                0,
+                100,
+                vec![Op::new(Kind::Match, 6), Op::new(Kind::SoftClip, 4)],
+                "sq1,500,-,3M7S,30,1;",
+            )?,
+            build_record(
+                Flags::SUPPLEMENTARY | Flags::REVERSE_COMPLEMENTED,
+                1,
+                500,
+                vec![Op::new(Kind::Match, 3), Op::new(Kind::HardClip, 7)],
+                "sq0,100,+,6M4S,60,0;",
+            )?,
+            build_record(Flags::SECONDARY, 1, 800, vec![Op::new(Kind::Match, 10)], "")?,
+        ];
+
+        let chains = reconstruct(&header, &records)?;
+        assert_eq!(chains.len(), 1);
+
+        let chain = &chains[0];
+        assert!(chain.is_chimeric());
+
+        let actual: Vec<_> = chain
+            .segments()
+            .iter()
+            .map(|segment| {
+                (
+                    segment.reference_sequence_name().to_vec(),
+                    usize::from(segment.alignment_start()),
+                    usize::from(segment.alignment_end()),
+                    segment.is_reverse_complemented(),
+                    segment.query_start(),
+                    segment.query_end(),
+                    segment.is_primary(),
+                )
+            })
+            .collect();
+
+        let expected = [
+            (b"sq0".to_vec(), 100, 105, false, 0, 6, true),
+            (b"sq1".to_vec(), 500, 502, true, 7, 10, false),
+        ];
+
+        assert_eq!(actual, expected);
+
+        assert_eq!(chain.junctions().count(), 1);
+        assert_eq!(
+            chain
+                .primary_segment()
+                .map(|segment| segment.alignment_start()),
+            Position::new(100)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reconstruct_with_segments() -> Result<(), Box<dyn std::error::Error>> {
+        let header = build_header()?;
+
+        let records = [
+            build_record(
+                Flags::SEGMENTED | Flags::LAST_SEGMENT,
+                1,
+                300,
+                vec![Op::new(Kind::Match, 10)],
+                "",
+            )?,
+            build_record(
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT,
+                0,
+                100,
+                vec![Op::new(Kind::Match, 10)],
+                "",
+            )?,
+            build_record(
+                Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::UNMAPPED,
+                0,
+                100,
+                Vec::new(),
+                "",
+            )?,
+        ];
+
+        let chains = reconstruct(&header, &records)?;
+
+        assert_eq!(chains.len(), 2);
+        assert!(!chains[0].is_chimeric());
+        assert_eq!(
+            chains[0].segments()[0].reference_sequence_name(),
+            BStr::new("sq0")
+        );
+        assert_eq!(
+            chains[1].segments()[0].reference_sequence_name(),
+            BStr::new("sq1")
+        );
+
+        Ok(())
+    }
+}
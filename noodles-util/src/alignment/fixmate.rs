@@ -0,0 +1,438 @@
+//! Alignment record mate information fixing.
+//!
+//! This is similar to `samtools fixmate`. Given the records of a template, the mate fields of
+//! each segment are filled from the primary record of its mate.
+
+use std::{collections::VecDeque, io};
+
+use bstr::BString;
+use noodles_core::Position;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        RecordBuf,
+        record::{Flags, data::field::Tag},
+        record_buf::data::field::Value,
+    },
+};
+
+use super::iter::Templates;
+
+// § 1.4.5 "_MAPQ_" (2023): "A value 255 indicates that the mapping quality is not available."
+const MISSING_MAPPING_QUALITY: u8 = 255;
+
+// The minimum quality score included in the mate score (`ms`), as used by `samtools fixmate`.
+const MIN_MATE_SCORE_QUALITY_SCORE: u8 = 15;
+
+const MATE_SCORE: Tag = Tag::new(b'm', b's');
+
+/// Fixes the mate information of a template.
+///
+/// The given records must be all the records of a single template, i.e., they must all have the
+/// same name. The primary first and last segments are paired, and each record of a segment has
+/// its mate reference sequence ID, mate alignment start, mate flags, and mate data fields (`MC`,
+/// `MQ`, and `ms`) set from the primary record of the other segment. The template length is set
+/// on both primary records.
+///
+/// Unmapped primary segments with a mapped mate are placed at their mate's position. If both
+/// primary segments are unmapped, their positions are cleared.
+///
+/// Templates that do not have both a primary first and last segment are left unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::{record::Flags, RecordBuf};
+/// use noodles_util::alignment::fixmate;
+///
+/// let mut records = [
+///     RecordBuf::builder()
+///         .set_flags(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(8)?)
+///         .build(),
+///     RecordBuf::builder()
+///         .set_flags(Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED)
+///         .set_reference_sequence_id(0)
+///         .set_alignment_start(Position::try_from(13)?)
+///         .build(),
+/// ];
+///
+/// fixmate::fix(&mut records)?;
+///
+/// assert_eq!(records[0].mate_alignment_start(), Position::new(13));
+/// assert!(records[0].flags().is_mate_reverse_complemented());
+/// assert_eq!(records[1].mate_alignment_start(), Position::new(8));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn fix(records: &mut [RecordBuf]) -> io::Result<()> {
+    let Some((i, j)) = find_primary_segments(records) else {
+        return Ok(());
+    };
+
+    sync_unmapped_positions(records, i, j);
+
+    let mate_1 = Mate::from_record(&records[i])?;
+    let mate_2 = Mate::from_record(&records[j])?;
+
+    let (template_length_1, template_length_2) = calculate_template_lengths(&mate_1, &mate_2);
+    *records[i].template_length_mut() = template_length_1;
+    *records[j].template_length_mut() = template_length_2;
+
+    for record in records.iter_mut() {
+        let flags = record.flags();
+
+        let (mate, other) = if flags.is_first_segment() {
+            (&mate_2, &mate_1)
+        } else if flags.is_last_segment() {
+            (&mate_1, &mate_2)
+        } else {
+            continue;
+        };
+
+        set_mate(record, mate, other);
+    }
+
+    Ok(())
+}
+
+/// An iterator adapter that fixes the mate information of query name-grouped records.
+///
+/// Consecutive records with the same name are treated as one template and fixed using [`fix`].
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::alignment::RecordBuf;
+/// use noodles_util::alignment::fixmate::FixMates;
+///
+/// let records = [Ok(RecordBuf::default()), Ok(RecordBuf::default())];
+/// let fixed_records: Vec<_> = FixMates::new(records.into_iter()).collect::<std::io::Result<_>>()?;
+/// assert_eq!(fixed_records.len(), 2);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct FixMates<I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    templates: Templates<I, RecordBuf>,
+    buf: VecDeque<RecordBuf>,
+}
+
+impl<I> FixMates<I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    /// Creates a mate fixing iterator adapter.
+    ///
+    /// The given iterator must be grouped by query name.
+    pub fn new(records: I) -> Self {
+        Self {
+            templates: Templates::new(records),
+            buf: VecDeque::new(),
+        }
+    }
+}
+
+impl<I> Iterator for FixMates<I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(record) = self.buf.pop_front() {
+            return Some(Ok(record));
+        }
+
+        let mut records = match self.templates.next()? {
+            Ok(template) => template.into_records(),
+            Err(e) => return Some(Err(e)),
+        };
+
+        if let Err(e) = fix(&mut records) {
+            return Some(Err(e));
+        }
+
+        self.buf.extend(records);
+        self.buf.pop_front().map(Ok)
+    }
+}
+
+struct Mate {
+    flags: Flags,
+    reference_sequence_id: Option<usize>,
+    alignment_start: Option<Position>,
+    alignment_end: Option<Position>,
+    mapping_quality: u8,
+    cigar: BString,
+    score: i32,
+}
+
+impl Mate {
+    fn from_record(record: &RecordBuf) -> io::Result<Self> {
+        use sam::alignment::Record;
+
+        let alignment_end = Record::alignment_end(record).transpose()?;
+
+        let mapping_quality = record
+            .mapping_quality()
+            .map(u8::from)
+            .unwrap_or(MISSING_MAPPING_QUALITY);
+
+        let mut cigar = Vec::new();
+        sam::io::writer::record::write_cigar(&mut cigar, record.cigar())?;
+
+        let score = record
+            .quality_scores()
+            .iter()
+            .filter(|&score| score >= MIN_MATE_SCORE_QUALITY_SCORE)
+            .map(i32::from)
+            .sum();
+
+        Ok(Self {
+            flags: record.flags(),
+            reference_sequence_id: record.reference_sequence_id(),
+            alignment_start: record.alignment_start(),
+            alignment_end,
+            mapping_quality,
+            cigar: cigar.into(),
+            score,
+        })
+    }
+
+    fn is_mapped(&self) -> bool {
+        !self.flags.is_unmapped()
+    }
+}
+
+fn is_primary(flags: Flags) -> bool {
+    !flags.is_secondary() && !flags.is_supplementary()
+}
+
+fn find_primary_segments(records: &[RecordBuf]) -> Option<(usize, usize)> {
+    let i = records.iter().position(|record| {
+        let flags = record.flags();
+        is_primary(flags) && flags.is_first_segment()
+    })?;
+
+    let j = records.iter().position(|record| {
+        let flags = record.flags();
+        is_primary(flags) && flags.is_last_segment()
+    })?;
+
+    (i != j).then_some((i, j))
+}
+
+fn sync_unmapped_positions(records: &mut [RecordBuf], i: usize, j: usize) {
+    let is_unmapped_1 = records[i].flags().is_unmapped();
+    let is_unmapped_2 = records[j].flags().is_unmapped();
+
+    match (is_unmapped_1, is_unmapped_2) {
+        (true, true) => {
+            for k in [i, j] {
+                *records[k].reference_sequence_id_mut() = None;
+                *records[k].alignment_start_mut() = None;
+            }
+        }
+        (true, false) => copy_position(records, j, i),
+        (false, true) => copy_position(records, i, j),
+        (false, false) => {}
+    }
+}
+
+fn copy_position(records: &mut [RecordBuf], src: usize, dst: usize) {
+    let reference_sequence_id = records[src].reference_sequence_id();
+    let alignment_start = records[src].alignment_start();
+
+    *records[dst].reference_sequence_id_mut() = reference_sequence_id;
+    *records[dst].alignment_start_mut() = alignment_start;
+}
+
+fn calculate_template_lengths(mate_1: &Mate, mate_2: &Mate) -> (i32, i32) {
+    if !mate_1.is_mapped()
+        || !mate_2.is_mapped()
+        || mate_1.reference_sequence_id.is_none()
+        || mate_1.reference_sequence_id != mate_2.reference_sequence_id
+    {
+        return (0, 0);
+    }
+
+    let (Some(start_1), Some(end_1), Some(start_2), Some(end_2)) = (
+        mate_1.alignment_start,
+        mate_1.alignment_end,
+        mate_2.alignment_start,
+        mate_2.alignment_end,
+    ) else {
+        return (0, 0);
+    };
+
+    let start = start_1.min(start_2);
+    let end = end_1.max(end_2);
+
+    let len = usize::from(end) - usize::from(start) + 1;
+    let len = i32::try_from(len).unwrap_or(i32::MAX);
+
+    if start_1 <= start_2 {
+        (len, -len)
+    } else {
+        (-len, len)
+    }
+}
+
+fn set_mate(record: &mut RecordBuf, mate: &Mate, other: &Mate) {
+    *record.mate_reference_sequence_id_mut() = mate.reference_sequence_id;
+    *record.mate_alignment_start_mut() = mate.alignment_start;
+
+    let flags = record.flags_mut();
+    flags.set(Flags::MATE_UNMAPPED, mate.flags.is_unmapped());
+    flags.set(
+        Flags::MATE_REVERSE_COMPLEMENTED,
+        mate.flags.is_reverse_complemented(),
+    );
+
+    if !mate.is_mapped() || !other.is_mapped() {
+        flags.remove(Flags::PROPERLY_SEGMENTED);
+    }
+
+    let data = record.data_mut();
+
+    if mate.is_mapped() {
+        data.insert(Tag::MATE_CIGAR, Value::String(mate.cigar.clone()));
+        data.insert(Tag::MATE_MAPPING_QUALITY, Value::from(mate.mapping_quality));
+    } else {
+        data.remove(&Tag::MATE_CIGAR);
+        data.remove(&Tag::MATE_MAPPING_QUALITY);
+    }
+
+    data.insert(MATE_SCORE, Value::from(mate.score));
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+    use sam::alignment::record::{
+        MappingQuality,
+        cigar::{Op, op::Kind},
+    };
+
+    use super::*;
+
+    fn build_record(
+        flags: Flags,
+        alignment_start: usize,
+        mapping_quality: u8,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let mut builder = RecordBuf::builder()
+            .set_name("r0")
+            .set_flags(Flags::SEGMENTED | flags)
+            .set_quality_scores(vec![8, 13, 21, 34].into());
+
+        if !flags.is_unmapped() {
+            builder = builder
+                .set_reference_sequence_id(0)
+                .set_alignment_start(Position::try_from(alignment_start)?)
+                .set_mapping_quality(MappingQuality::new(mapping_quality).unwrap())
+                .set_cigar([Op::new(Kind::Match, 4)].into_iter().collect());
+        }
+
+        Ok(builder.build())
+    }
+
+    #[test]
+    fn test_fix() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [
+            build_record(Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED, 8, 13)?,
+            build_record(Flags::FIRST_SEGMENT | Flags::PROPERLY_SEGMENTED, 5, 21)?,
+            build_record(Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY, 55, 8)?,
+        ];
+
+        fix(&mut records)?;
+
+        let record = &records[0];
+        assert_eq!(record.mate_reference_sequence_id(), Some(0));
+        assert_eq!(record.mate_alignment_start(), Position::new(5));
+        assert!(!record.flags().is_mate_unmapped());
+        assert!(!record.flags().is_mate_reverse_complemented());
+        assert_eq!(record.template_length(), -7);
+        assert_eq!(
+            record.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::String(BString::from("4M")))
+        );
+        assert_eq!(
+            record.data().get(&Tag::MATE_MAPPING_QUALITY),
+            Some(&Value::UInt8(21))
+        );
+        assert_eq!(record.data().get(&MATE_SCORE), Some(&Value::UInt8(55)));
+
+        let record = &records[1];
+        assert_eq!(record.mate_alignment_start(), Position::new(8));
+        assert!(record.flags().is_mate_reverse_complemented());
+        assert!(record.flags().is_properly_segmented());
+        assert_eq!(record.template_length(), 7);
+        assert_eq!(
+            record.data().get(&Tag::MATE_MAPPING_QUALITY),
+            Some(&Value::UInt8(13))
+        );
+
+        let record = &records[2];
+        assert_eq!(record.mate_alignment_start(), Position::new(8));
+        assert!(record.flags().is_mate_reverse_complemented());
+        assert_eq!(record.template_length(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_with_unmapped_mate() -> Result<(), Box<dyn std::error::Error>> {
+        let mut records = [
+            build_record(Flags::FIRST_SEGMENT | Flags::PROPERLY_SEGMENTED, 8, 13)?,
+            build_record(Flags::LAST_SEGMENT | Flags::UNMAPPED, 0, 0)?,
+        ];
+
+        fix(&mut records)?;
+
+        let record = &records[0];
+        assert_eq!(record.mate_reference_sequence_id(), Some(0));
+        assert_eq!(record.mate_alignment_start(), Position::new(8));
+        assert!(record.flags().is_mate_unmapped());
+        assert!(!record.flags().is_properly_segmented());
+        assert_eq!(record.template_length(), 0);
+        assert!(record.data().get(&Tag::MATE_CIGAR).is_none());
+        assert!(record.data().get(&Tag::MATE_MAPPING_QUALITY).is_none());
+
+        let record = &records[1];
+        assert_eq!(record.reference_sequence_id(), Some(0));
+        assert_eq!(record.alignment_start(), Position::new(8));
+        assert_eq!(record.mate_alignment_start(), Position::new(8));
+        assert!(!record.flags().is_mate_unmapped());
+        assert_eq!(
+            record.data().get(&Tag::MATE_CIGAR),
+            Some(&Value::String(BString::from("4M")))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fix_mates() -> Result<(), Box<dyn std::error::Error>> {
+        let mut r1_1 = build_record(Flags::FIRST_SEGMENT, 8, 13)?;
+        *r1_1.name_mut() = Some(BString::from("r1"));
+        let mut r1_2 = build_record(Flags::LAST_SEGMENT, 13, 21)?;
+        *r1_2.name_mut() = Some(BString::from("r1"));
+        let r0_1 = build_record(Flags::FIRST_SEGMENT, 21, 34)?;
+
+        let records = [Ok(r1_1), Ok(r1_2), Ok(r0_1)];
+        let actual: Vec<_> = FixMates::new(records.into_iter()).collect::<io::Result<_>>()?;
+
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[0].name(), Some(b"r1".as_bstr()));
+        assert_eq!(actual[0].mate_alignment_start(), Position::new(13));
+        assert_eq!(actual[1].mate_alignment_start(), Position::new(8));
+        assert_eq!(actual[2].name(), Some(b"r0".as_bstr()));
+        assert!(actual[2].mate_alignment_start().is_none());
+
+        Ok(())
+    }
+}
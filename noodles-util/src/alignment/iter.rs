@@ -1,5 +1,10 @@
 //! Composable iterators for alignment records.
 
+mod collate;
 mod pileup;
+mod template;
+mod templates;
 
-pub use self::pileup::Pileup as Depth;
+pub use self::{
+    collate::Collate, pileup::Pileup as Depth, template::Template, templates::Templates,
+};
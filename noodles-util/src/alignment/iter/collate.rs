@@ -0,0 +1,245 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+};
+
+use bstr::BString;
+use noodles_sam::alignment::Record;
+
+use super::Template;
+
+/// An iterator adapter that collates coordinate-sorted records into templates.
+///
+/// Records are buffered by name until their template is complete, i.e., the primary records of
+/// all segments have been read (see [`Template::is_complete`]). Templates are emitted in the order
+/// they are completed.
+///
+/// To bound memory usage, at most `capacity` incomplete templates are buffered. When this is
+/// exceeded, the oldest incomplete template is emitted as is. Incomplete templates are also
+/// emitted at the end of the input. Secondary and supplementary records read after their template
+/// was emitted are placed in a new template.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_sam::alignment::{record::Flags, RecordBuf};
+/// use noodles_util::alignment::iter::Collate;
+///
+/// let build_record = |name, flags, position| -> Result<_, Box<dyn std::error::Error>> {
+///     Ok(RecordBuf::builder()
+///         .set_name(name)
+///         .set_flags(Flags::SEGMENTED | flags)
+///         .set_alignment_start(Position::try_from(position)?)
+///         .build())
+/// };
+///
+/// let records = [
+///     Ok(build_record("r0", Flags::FIRST_SEGMENT, 8)?),
+///     Ok(build_record("r1", Flags::FIRST_SEGMENT, 13)?),
+///     Ok(build_record("r0", Flags::LAST_SEGMENT, 21)?),
+///     Ok(build_record("r1", Flags::LAST_SEGMENT, 34)?),
+/// ];
+///
+/// let templates: Vec<_> = Collate::new(records.into_iter(), 1024)
+///     .collect::<std::io::Result<_>>()?;
+///
+/// assert_eq!(templates.len(), 2);
+/// assert_eq!(templates[0].name(), Some("r0".into()));
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Collate<I, R> {
+    records: I,
+    capacity: usize,
+    pending: HashMap<BString, (u64, Template<R>)>,
+    queue: BTreeMap<u64, BString>,
+    ready: VecDeque<Template<R>>,
+    next_id: u64,
+    is_eof: bool,
+}
+
+impl<I, R> Collate<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    /// Creates a template collating iterator adapter.
+    ///
+    /// The given iterator is expected to be coordinate-sorted. `capacity` is the maximum number of
+    /// incomplete templates to buffer.
+    pub fn new(records: I, capacity: usize) -> Self {
+        Self {
+            records,
+            capacity,
+            pending: HashMap::new(),
+            queue: BTreeMap::new(),
+            ready: VecDeque::new(),
+            next_id: 0,
+            is_eof: false,
+        }
+    }
+
+    fn add(&mut self, record: R) -> io::Result<()> {
+        let Some(name) = record.name().map(BString::from) else {
+            let mut template = Template::new(None);
+            template.push(record)?;
+            self.ready.push_back(template);
+            return Ok(());
+        };
+
+        let (_, template) = match self.pending.get_mut(&name) {
+            Some(entry) => entry,
+            None => {
+                let id = self.next_id;
+                self.next_id += 1;
+
+                self.queue.insert(id, name.clone());
+
+                self.pending
+                    .entry(name.clone())
+                    .or_insert_with(|| (id, Template::new(Some(name.clone()))))
+            }
+        };
+
+        template.push(record)?;
+
+        if template.is_complete() {
+            if let Some((id, template)) = self.pending.remove(&name) {
+                self.queue.remove(&id);
+                self.ready.push_back(template);
+            }
+        } else if self.pending.len() > self.capacity {
+            self.evict();
+        }
+
+        Ok(())
+    }
+
+    fn evict(&mut self) -> bool {
+        let Some((_, name)) = self.queue.pop_first() else {
+            return false;
+        };
+
+        if let Some((_, template)) = self.pending.remove(&name) {
+            self.ready.push_back(template);
+        }
+
+        true
+    }
+}
+
+impl<I, R> Iterator for Collate<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(template) = self.ready.pop_front() {
+                return Some(Ok(template));
+            }
+
+            if self.is_eof {
+                if self.evict() {
+                    continue;
+                } else {
+                    return None;
+                }
+            }
+
+            match self.records.next() {
+                Some(Ok(record)) => {
+                    if let Err(e) = self.add(record) {
+                        return Some(Err(e));
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => self.is_eof = true,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::{RecordBuf, record::Flags};
+
+    use super::*;
+
+    fn build_record(name: &str, flags: Flags) -> io::Result<RecordBuf> {
+        Ok(RecordBuf::builder()
+            .set_name(name)
+            .set_flags(Flags::SEGMENTED | flags)
+            .build())
+    }
+
+    fn collect_names<I>(collate: Collate<I, RecordBuf>) -> io::Result<Vec<(Vec<u8>, usize)>>
+    where
+        I: Iterator<Item = io::Result<RecordBuf>>,
+    {
+        collate
+            .map(|result| {
+                result.map(|template| {
+                    (
+                        template
+                            .name()
+                            .map(|name| name.to_vec())
+                            .unwrap_or_default(),
+                        template.records().len(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let records = [
+            build_record("r0", Flags::FIRST_SEGMENT),
+            build_record("r1", Flags::FIRST_SEGMENT),
+            build_record("r1", Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY),
+            build_record("r2", Flags::LAST_SEGMENT),
+            build_record("r1", Flags::LAST_SEGMENT),
+            build_record("r0", Flags::LAST_SEGMENT),
+        ];
+
+        let actual = collect_names(Collate::new(records.into_iter(), 8))?;
+
+        let expected = [
+            (b"r1".to_vec(), 3),
+            (b"r0".to_vec(), 2),
+            (b"r2".to_vec(), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_capacity() -> io::Result<()> {
+        let records = [
+            build_record("r0", Flags::FIRST_SEGMENT),
+            build_record("r1", Flags::FIRST_SEGMENT),
+            build_record("r2", Flags::FIRST_SEGMENT),
+            build_record("r1", Flags::LAST_SEGMENT),
+            build_record("r2", Flags::LAST_SEGMENT),
+            build_record("r0", Flags::LAST_SEGMENT),
+        ];
+
+        let actual = collect_names(Collate::new(records.into_iter(), 2))?;
+
+        let expected = [
+            (b"r0".to_vec(), 1),
+            (b"r1".to_vec(), 2),
+            (b"r2".to_vec(), 2),
+            (b"r0".to_vec(), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,158 @@
+use std::io;
+
+use bstr::{BStr, BString};
+use noodles_sam::alignment::{Record, record::Flags};
+
+/// A group of alignment records that share a name.
+///
+/// A template holds the primary records of each segment, e.g., the first and last segments of a
+/// read pair, along with their secondary and supplementary records.
+#[derive(Debug)]
+pub struct Template<R> {
+    name: Option<BString>,
+    records: Vec<R>,
+    flags: Vec<Flags>,
+}
+
+impl<R> Template<R>
+where
+    R: Record,
+{
+    pub(super) fn new(name: Option<BString>) -> Self {
+        Self {
+            name,
+            records: Vec::new(),
+            flags: Vec::new(),
+        }
+    }
+
+    pub(super) fn push(&mut self, record: R) -> io::Result<()> {
+        let flags = record.flags()?;
+        self.records.push(record);
+        self.flags.push(flags);
+        Ok(())
+    }
+
+    /// Returns the template name.
+    pub fn name(&self) -> Option<&BStr> {
+        self.name.as_ref().map(|name| name.as_ref())
+    }
+
+    /// Returns the records in the template.
+    pub fn records(&self) -> &[R] {
+        &self.records
+    }
+
+    /// Returns the records in the template, consuming the template.
+    pub fn into_records(self) -> Vec<R> {
+        self.records
+    }
+
+    /// Returns the primary record of the first segment.
+    ///
+    /// For templates with a single segment, this is the primary record.
+    pub fn first_segment(&self) -> Option<&R> {
+        self.find(|flags| is_primary(flags) && (flags.is_first_segment() || !flags.is_segmented()))
+    }
+
+    /// Returns the primary record of the last segment.
+    pub fn last_segment(&self) -> Option<&R> {
+        self.find(|flags| is_primary(flags) && flags.is_last_segment())
+    }
+
+    /// Returns an iterator over secondary records.
+    pub fn secondary_records(&self) -> impl Iterator<Item = &R> {
+        self.filter(|flags| flags.is_secondary())
+    }
+
+    /// Returns an iterator over supplementary records.
+    pub fn supplementary_records(&self) -> impl Iterator<Item = &R> {
+        self.filter(|flags| flags.is_supplementary())
+    }
+
+    /// Returns whether the primary records of all segments are in the template.
+    ///
+    /// A template with multiple segments is complete when it has both a first and last segment.
+    pub fn is_complete(&self) -> bool {
+        self.find(is_primary).is_some_and(|_| {
+            let is_segmented = self.flags.iter().any(|flags| flags.is_segmented());
+
+            if is_segmented {
+                self.first_segment().is_some() && self.last_segment().is_some()
+            } else {
+                true
+            }
+        })
+    }
+
+    fn find<F>(&self, f: F) -> Option<&R>
+    where
+        F: Fn(Flags) -> bool,
+    {
+        self.filter(f).next()
+    }
+
+    fn filter<F>(&self, f: F) -> impl Iterator<Item = &R>
+    where
+        F: Fn(Flags) -> bool,
+    {
+        self.records
+            .iter()
+            .zip(&self.flags)
+            .filter(move |(_, flags)| f(**flags))
+            .map(|(record, _)| record)
+    }
+}
+
+fn is_primary(flags: Flags) -> bool {
+    !flags.is_secondary() && !flags.is_supplementary()
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_sam::alignment::RecordBuf;
+
+    use super::*;
+
+    fn build_record(flags: Flags) -> RecordBuf {
+        RecordBuf::builder().set_name("r0").set_flags(flags).build()
+    }
+
+    #[test]
+    fn test_segments() -> io::Result<()> {
+        let mut template = Template::new(Some(BString::from("r0")));
+
+        template.push(build_record(
+            Flags::SEGMENTED | Flags::FIRST_SEGMENT | Flags::SUPPLEMENTARY,
+        ))?;
+        assert!(!template.is_complete());
+
+        template.push(build_record(Flags::SEGMENTED | Flags::FIRST_SEGMENT))?;
+        assert!(!template.is_complete());
+
+        template.push(build_record(Flags::SEGMENTED | Flags::LAST_SEGMENT))?;
+        assert!(template.is_complete());
+
+        template.push(build_record(
+            Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::SECONDARY,
+        ))?;
+
+        assert_eq!(
+            template.first_segment().map(|record| record.flags()),
+            Some(Flags::SEGMENTED | Flags::FIRST_SEGMENT)
+        );
+        assert_eq!(
+            template.last_segment().map(|record| record.flags()),
+            Some(Flags::SEGMENTED | Flags::LAST_SEGMENT)
+        );
+        assert_eq!(template.secondary_records().count(), 1);
+        assert_eq!(template.supplementary_records().count(), 1);
+        assert_eq!(template.records().len(), 4);
+
+        let mut template = Template::new(Some(BString::from("r0")));
+        template.push(build_record(Flags::empty()))?;
+        assert!(template.is_complete());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::io;
+
+use bstr::BString;
+use noodles_sam::alignment::Record;
+
+use super::Template;
+
+/// An iterator adapter that groups query name-grouped records into templates.
+///
+/// Consecutive records with the same name are grouped into a single template. Records without a
+/// name are each placed in their own template.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_sam::alignment::RecordBuf;
+/// use noodles_util::alignment::iter::Templates;
+///
+/// let records = [
+///     Ok(RecordBuf::builder().set_name("r0").build()),
+///     Ok(RecordBuf::builder().set_name("r0").build()),
+///     Ok(RecordBuf::builder().set_name("r1").build()),
+/// ];
+///
+/// let templates: Vec<_> = Templates::new(records.into_iter()).collect::<std::io::Result<_>>()?;
+/// assert_eq!(templates.len(), 2);
+/// assert_eq!(templates[0].records().len(), 2);
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct Templates<I, R> {
+    records: I,
+    next_record: Option<R>,
+}
+
+impl<I, R> Templates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    /// Creates a template grouping iterator adapter.
+    ///
+    /// The given iterator must be grouped by query name, e.g., sorted or collated by name.
+    pub fn new(records: I) -> Self {
+        Self {
+            records,
+            next_record: None,
+        }
+    }
+
+    fn read_template(&mut self) -> io::Result<Option<Template<R>>> {
+        let first_record = match self.next_record.take() {
+            Some(record) => record,
+            None => match self.records.next().transpose()? {
+                Some(record) => record,
+                None => return Ok(None),
+            },
+        };
+
+        let name = first_record.name().map(BString::from);
+        let mut template = Template::new(name);
+        template.push(first_record)?;
+
+        if template.name().is_none() {
+            return Ok(Some(template));
+        }
+
+        while let Some(record) = self.records.next().transpose()? {
+            if record.name() == template.name() {
+                template.push(record)?;
+            } else {
+                self.next_record = Some(record);
+                break;
+            }
+        }
+
+        Ok(Some(template))
+    }
+}
+
+impl<I, R> Iterator for Templates<I, R>
+where
+    I: Iterator<Item = io::Result<R>>,
+    R: Record,
+{
+    type Item = io::Result<Template<R>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_template().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr::ByteSlice;
+    use noodles_sam::alignment::RecordBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_next() -> io::Result<()> {
+        let records = [
+            Ok(RecordBuf::builder().set_name("r0").build()),
+            Ok(RecordBuf::default()),
+            Ok(RecordBuf::builder().set_name("r1").build()),
+            Ok(RecordBuf::builder().set_name("r1").build()),
+            Ok(RecordBuf::builder().set_name("r1").build()),
+            Ok(RecordBuf::builder().set_name("r0").build()),
+        ];
+
+        let actual: Vec<_> = Templates::new(records.into_iter())
+            .map(|result| {
+                result.map(|template| {
+                    (
+                        template.name().map(|name| name.to_vec()),
+                        template.records().len(),
+                    )
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        let expected = [
+            (Some(b"r0".to_vec()), 1),
+            (None, 1),
+            (Some(b"r1".to_vec()), 3),
+            (Some(b"r0".to_vec()), 1),
+        ];
+
+        assert_eq!(actual, expected);
+
+        let records = [Ok(RecordBuf::builder().set_name("r0").build())];
+        let mut templates = Templates::new(records.into_iter());
+        let template = templates.next().transpose()?;
+        assert_eq!(
+            template.as_ref().and_then(|template| template.name()),
+            Some(b"r0".as_bstr())
+        );
+        assert!(templates.next().is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,1043 @@
+//! Alignment record duplicate marking.
+//!
+//! This follows the semantics of Picard `MarkDuplicates` and `samtools markdup`. Templates are
+//! grouped by library (the read group `LB` field) and the unclipped 5' positions and orientations
+//! of their primary alignments. In each group, the template with the highest sum of base quality
+//! scores is kept, and all other templates are duplicates. Fragments that share a 5' position and
+//! orientation with an end of a read pair are always duplicates.
+//!
+//! Records must be coordinate-sorted, and the primary records of read pairs must have their mate
+//! CIGAR (`MC`) and mate score (`ms`) data fields set, e.g., by [`super::fixmate`]. This allows
+//! duplicates to be marked in a single pass: a read pair is grouped when its first record is read,
+//! and a group is resolved once the records move past it.
+//!
+//! # Examples
+//!
+//! ```
+//! use noodles_sam::{self as sam, alignment::RecordBuf};
+//! use noodles_util::alignment::markdup::DuplicateMarker;
+//!
+//! let header = sam::Header::default();
+//! let records = [Ok(RecordBuf::default())];
+//!
+//! let mut marker = DuplicateMarker::new(&header, records.into_iter());
+//!
+//! for result in marker.by_ref() {
+//!     let record = result?;
+//!
+//!     if record.flags().is_duplicate() {
+//!         // ...
+//!     }
+//! }
+//!
+//! for (library, metrics) in marker.metrics() {
+//!     println!("{library}\t{}", metrics.percent_duplication());
+//! }
+//! # Ok::<_, std::io::Error>(())
+//! ```
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    io, mem,
+};
+
+use bstr::{BString, ByteSlice};
+use noodles_core::Position;
+use noodles_sam::{
+    self as sam,
+    alignment::{
+        RecordBuf,
+        record::{Cigar, Flags, cigar::op::Kind, data::field::Tag},
+        record_buf::data::field::Value,
+    },
+    header::record::value::map::read_group::tag as read_group_tag,
+};
+
+const UNKNOWN_LIBRARY: &[u8] = b"Unknown Library";
+
+// The minimum quality score included in a template score, as used by Picard `MarkDuplicates`.
+const MIN_QUALITY_SCORE: u8 = 15;
+
+const MATE_SCORE: Tag = Tag::new(b'm', b's');
+
+// The default maximum read length, as used by `samtools markdup`.
+const DEFAULT_MAX_READ_LENGTH: usize = 300;
+
+/// Duplicate marking metrics.
+///
+/// These are a subset of the metrics reported by Picard `MarkDuplicates` for a library.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Metrics {
+    unpaired_reads_examined: u64,
+    read_pairs_examined: u64,
+    secondary_or_supplementary_reads: u64,
+    unmapped_reads: u64,
+    unpaired_read_duplicates: u64,
+    read_pair_duplicates: u64,
+    read_pair_optical_duplicates: u64,
+}
+
+impl Metrics {
+    /// Returns the number of mapped reads examined that are not part of a mapped read pair.
+    pub fn unpaired_reads_examined(&self) -> u64 {
+        self.unpaired_reads_examined
+    }
+
+    /// Returns the number of mapped read pairs examined.
+    pub fn read_pairs_examined(&self) -> u64 {
+        self.read_pairs_examined
+    }
+
+    /// Returns the number of secondary and supplementary records.
+    pub fn secondary_or_supplementary_reads(&self) -> u64 {
+        self.secondary_or_supplementary_reads
+    }
+
+    /// Returns the number of unmapped reads.
+    pub fn unmapped_reads(&self) -> u64 {
+        self.unmapped_reads
+    }
+
+    /// Returns the number of unpaired reads that are duplicates.
+    pub fn unpaired_read_duplicates(&self) -> u64 {
+        self.unpaired_read_duplicates
+    }
+
+    /// Returns the number of read pairs that are duplicates.
+    pub fn read_pair_duplicates(&self) -> u64 {
+        self.read_pair_duplicates
+    }
+
+    /// Returns the number of read pair duplicates that are optical duplicates.
+    pub fn read_pair_optical_duplicates(&self) -> u64 {
+        self.read_pair_optical_duplicates
+    }
+
+    /// Calculates the fraction of mapped sequence that is duplicated.
+    pub fn percent_duplication(&self) -> f64 {
+        let duplicates = self.unpaired_read_duplicates + 2 * self.read_pair_duplicates;
+        let examined = self.unpaired_reads_examined + 2 * self.read_pairs_examined;
+
+        if examined == 0 {
+            0.0
+        } else {
+            duplicates as f64 / examined as f64
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+struct End {
+    reference_sequence_id: usize,
+    position: i64,
+    is_reverse_complemented: bool,
+}
+
+// A read pair whose first record was read.
+#[derive(Debug)]
+struct Template {
+    order: usize,
+    score: u64,
+    pending_records: Vec<usize>,
+    is_duplicate: Option<bool>,
+    is_mate_pending: bool,
+}
+
+#[derive(Debug)]
+struct Fragment {
+    order: usize,
+    score: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    record: RecordBuf,
+    is_pending: bool,
+}
+
+type PairKey = (End, End, BString);
+type FragmentKey = (End, BString);
+
+/// An iterator adapter that marks duplicate records.
+///
+/// The given records must be coordinate-sorted. Records are returned in the same order with the
+/// duplicate flag of each examined primary record set or cleared. To remove duplicates, filter
+/// the returned records by their duplicate flag.
+///
+/// Records are buffered until their duplicate groups are resolved. Secondary, supplementary,
+/// unmapped, and unnamed records are returned unchanged.
+pub struct DuplicateMarker<'h, I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    header: &'h sam::Header,
+    records: I,
+    optical_duplicate_pixel_distance: Option<u32>,
+    max_read_length: usize,
+    buf: VecDeque<Entry>,
+    buf_start: usize,
+    last_position: Option<(usize, Position)>,
+    is_unplaced: bool,
+    is_eof: bool,
+    templates: HashMap<BString, Template>,
+    pairs: BTreeMap<PairKey, Vec<BString>>,
+    fragments: BTreeMap<FragmentKey, Vec<Fragment>>,
+    pair_ends: BTreeSet<FragmentKey>,
+    metrics: BTreeMap<BString, Metrics>,
+}
+
+impl<'h, I> DuplicateMarker<'h, I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    /// Creates a duplicate marker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::markdup::DuplicateMarker;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [Ok(RecordBuf::default())];
+    /// let marker = DuplicateMarker::new(&header, records.into_iter());
+    /// ```
+    pub fn new(header: &'h sam::Header, records: I) -> Self {
+        Self {
+            header,
+            records,
+            optical_duplicate_pixel_distance: None,
+            max_read_length: DEFAULT_MAX_READ_LENGTH,
+            buf: VecDeque::new(),
+            buf_start: 0,
+            last_position: None,
+            is_unplaced: false,
+            is_eof: false,
+            templates: HashMap::new(),
+            pairs: BTreeMap::new(),
+            fragments: BTreeMap::new(),
+            pair_ends: BTreeSet::new(),
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// Enables optical duplicate detection.
+    ///
+    /// Read names are parsed as Illumina read names, and duplicate read pairs on the same tile
+    /// within the given pixel distance of each other are counted as optical duplicates. Picard
+    /// uses a distance of 100 for unpatterned flow cells and 2500 for patterned flow cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::markdup::DuplicateMarker;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [Ok(RecordBuf::default())];
+    /// let marker = DuplicateMarker::new(&header, records.into_iter())
+    ///     .set_optical_duplicate_pixel_distance(100);
+    /// ```
+    pub fn set_optical_duplicate_pixel_distance(mut self, distance: u32) -> Self {
+        self.optical_duplicate_pixel_distance = Some(distance);
+        self
+    }
+
+    /// Sets the maximum read length.
+    ///
+    /// A duplicate group is resolved once the records move this far past its 5' position, so
+    /// this must be at least the longest leading clip of any record. The default is 300.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use noodles_sam::{self as sam, alignment::RecordBuf};
+    /// use noodles_util::alignment::markdup::DuplicateMarker;
+    ///
+    /// let header = sam::Header::default();
+    /// let records = [Ok(RecordBuf::default())];
+    /// let marker = DuplicateMarker::new(&header, records.into_iter()).set_max_read_length(500);
+    /// ```
+    pub fn set_max_read_length(mut self, max_read_length: usize) -> Self {
+        self.max_read_length = max_read_length;
+        self
+    }
+
+    /// Returns the duplicate marking metrics of each library.
+    ///
+    /// These are only complete once the iterator is exhausted.
+    pub fn metrics(&self) -> &BTreeMap<BString, Metrics> {
+        &self.metrics
+    }
+
+    fn push(&mut self, mut record: RecordBuf) -> io::Result<()> {
+        let position = record.reference_sequence_id().zip(record.alignment_start());
+        self.advance(position)?;
+
+        let index = self.buf_start + self.buf.len();
+        let is_pending = self.add(&mut record, index)?;
+        self.buf.push_back(Entry { record, is_pending });
+
+        Ok(())
+    }
+
+    fn advance(&mut self, position: Option<(usize, Position)>) -> io::Result<()> {
+        match position {
+            Some(position) => {
+                if self.is_unplaced || self.last_position.is_some_and(|last| position < last) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "records are not coordinate-sorted",
+                    ));
+                }
+
+                self.last_position = Some(position);
+            }
+            None => self.is_unplaced = true,
+        }
+
+        self.resolve_groups(position);
+
+        Ok(())
+    }
+
+    fn add(&mut self, record: &mut RecordBuf, index: usize) -> io::Result<bool> {
+        let flags = record.flags();
+        let library = get_library(self.header, record)?;
+
+        if flags.is_secondary() || flags.is_supplementary() {
+            let metrics = self.metrics.entry(library).or_default();
+            metrics.secondary_or_supplementary_reads += 1;
+            return Ok(false);
+        } else if flags.is_unmapped() {
+            let metrics = self.metrics.entry(library).or_default();
+            metrics.unmapped_reads += 1;
+            return Ok(false);
+        }
+
+        let Some(name) = record.name().map(BString::from) else {
+            return Ok(false);
+        };
+
+        let end = build_end(record, flags)?;
+        let score = calculate_score(record);
+
+        if !is_paired_with_mapped_mate(flags) {
+            let metrics = self.metrics.entry(library.clone()).or_default();
+            metrics.unpaired_reads_examined += 1;
+
+            self.fragments
+                .entry((end, library))
+                .or_default()
+                .push(Fragment {
+                    order: index,
+                    score,
+                });
+
+            return Ok(true);
+        }
+
+        if let Some(template) = self.templates.get_mut(&name) {
+            template.is_mate_pending = false;
+
+            return match template.is_duplicate {
+                Some(is_duplicate) => {
+                    self.templates.remove(&name);
+                    record.flags_mut().set(Flags::DUPLICATE, is_duplicate);
+                    Ok(false)
+                }
+                None => {
+                    template.pending_records.push(index);
+                    Ok(true)
+                }
+            };
+        }
+
+        let mate_end = build_mate_end(record, flags)?;
+        let mate_score = get_mate_score(record)?;
+
+        let metrics = self.metrics.entry(library.clone()).or_default();
+        metrics.read_pairs_examined += 1;
+
+        let (a, b) = if end <= mate_end {
+            (end, mate_end)
+        } else {
+            (mate_end, end)
+        };
+
+        self.pair_ends.insert((a, library.clone()));
+        self.pair_ends.insert((b, library.clone()));
+
+        self.pairs
+            .entry((a, b, library))
+            .or_default()
+            .push(name.clone());
+
+        self.templates.insert(
+            name,
+            Template {
+                order: index,
+                score: score + mate_score,
+                pending_records: vec![index],
+                is_duplicate: None,
+                is_mate_pending: true,
+            },
+        );
+
+        Ok(true)
+    }
+
+    // Resolves the groups that no more records can be added to, i.e., whose 5' positions are
+    // more than the maximum read length before the given position. If the position is `None`,
+    // all groups are resolved.
+    fn resolve_groups(&mut self, position: Option<(usize, Position)>) {
+        let max_read_length = i64::try_from(self.max_read_length).unwrap_or(i64::MAX);
+
+        let is_complete = |end: &End| match position {
+            Some((reference_sequence_id, alignment_start)) => {
+                let alignment_start =
+                    i64::try_from(usize::from(alignment_start)).unwrap_or(i64::MAX);
+
+                end.reference_sequence_id < reference_sequence_id
+                    || (end.reference_sequence_id == reference_sequence_id
+                        && end.position.saturating_add(max_read_length) < alignment_start)
+            }
+            None => true,
+        };
+
+        while let Some(entry) = self.pairs.first_entry() {
+            if !is_complete(&entry.key().0) {
+                break;
+            }
+
+            let ((_, _, library), names) = entry.remove_entry();
+            self.resolve_pairs(library, names);
+        }
+
+        while let Some(entry) = self.fragments.first_entry() {
+            if !is_complete(&entry.key().0) {
+                break;
+            }
+
+            let (key, fragments) = entry.remove_entry();
+            let is_pair_end = self.pair_ends.contains(&key);
+            self.resolve_fragments(key.1, fragments, is_pair_end);
+        }
+
+        while let Some((end, _)) = self.pair_ends.first() {
+            if !is_complete(end) {
+                break;
+            }
+
+            self.pair_ends.pop_first();
+        }
+    }
+
+    fn resolve_pairs(&mut self, library: BString, mut names: Vec<BString>) {
+        names.sort_by_key(|name| {
+            let template = &self.templates[name];
+            (Reverse(template.score), template.order)
+        });
+
+        let metrics = self.metrics.entry(library).or_default();
+        metrics.read_pair_duplicates += (names.len() - 1) as u64;
+
+        if let Some(distance) = self.optical_duplicate_pixel_distance {
+            metrics.read_pair_optical_duplicates += count_optical_duplicates(&names, distance);
+        }
+
+        for (i, name) in names.into_iter().enumerate() {
+            let is_duplicate = i > 0;
+
+            let Some(template) = self.templates.get_mut(&name) else {
+                continue;
+            };
+
+            template.is_duplicate = Some(is_duplicate);
+            let pending_records = mem::take(&mut template.pending_records);
+
+            if !template.is_mate_pending {
+                self.templates.remove(&name);
+            }
+
+            for index in pending_records {
+                self.resolve(index, is_duplicate);
+            }
+        }
+    }
+
+    fn resolve_fragments(
+        &mut self,
+        library: BString,
+        mut fragments: Vec<Fragment>,
+        is_pair_end: bool,
+    ) {
+        fragments.sort_by_key(|fragment| (Reverse(fragment.score), fragment.order));
+
+        let duplicate_count = if is_pair_end {
+            fragments.len()
+        } else {
+            fragments.len() - 1
+        };
+
+        let metrics = self.metrics.entry(library).or_default();
+        metrics.unpaired_read_duplicates += duplicate_count as u64;
+
+        for (i, fragment) in fragments.into_iter().enumerate() {
+            let is_duplicate = is_pair_end || i > 0;
+            self.resolve(fragment.order, is_duplicate);
+        }
+    }
+
+    fn resolve(&mut self, index: usize, is_duplicate: bool) {
+        let entry = &mut self.buf[index - self.buf_start];
+        entry.record.flags_mut().set(Flags::DUPLICATE, is_duplicate);
+        entry.is_pending = false;
+    }
+}
+
+impl<I> Iterator for DuplicateMarker<'_, I>
+where
+    I: Iterator<Item = io::Result<RecordBuf>>,
+{
+    type Item = io::Result<RecordBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.buf.front().is_some_and(|entry| !entry.is_pending) {
+                self.buf_start += 1;
+                return self.buf.pop_front().map(|entry| Ok(entry.record));
+            } else if self.is_eof {
+                return None;
+            }
+
+            match self.records.next() {
+                Some(Ok(record)) => {
+                    if let Err(e) = self.push(record) {
+                        return Some(Err(e));
+                    }
+                }
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.resolve_groups(None);
+                    self.is_eof = true;
+                }
+            }
+        }
+    }
+}
+
+fn is_paired_with_mapped_mate(flags: Flags) -> bool {
+    flags.is_segmented() && !flags.is_mate_unmapped()
+}
+
+fn build_end(record: &RecordBuf, flags: Flags) -> io::Result<End> {
+    let reference_sequence_id = record.reference_sequence_id().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing reference sequence ID")
+    })?;
+
+    let alignment_start = record
+        .alignment_start()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing alignment start"))?;
+
+    let is_reverse_complemented = flags.is_reverse_complemented();
+    let position = calculate_unclipped_five_prime_position(
+        record.cigar(),
+        alignment_start,
+        is_reverse_complemented,
+    )?;
+
+    Ok(End {
+        reference_sequence_id,
+        position,
+        is_reverse_complemented,
+    })
+}
+
+fn build_mate_end(record: &RecordBuf, flags: Flags) -> io::Result<End> {
+    let reference_sequence_id = record.mate_reference_sequence_id().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "missing mate reference sequence ID",
+        )
+    })?;
+
+    let alignment_start = record.mate_alignment_start().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing mate alignment start")
+    })?;
+
+    let cigar = match record.data().get(&Tag::MATE_CIGAR) {
+        Some(Value::String(cigar)) => sam::record::Cigar::new(cigar),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid mate CIGAR field type",
+            ));
+        }
+        None => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "missing mate CIGAR",
+            ));
+        }
+    };
+
+    let is_reverse_complemented = flags.is_mate_reverse_complemented();
+    let position =
+        calculate_unclipped_five_prime_position(&cigar, alignment_start, is_reverse_complemented)?;
+
+    Ok(End {
+        reference_sequence_id,
+        position,
+        is_reverse_complemented,
+    })
+}
+
+fn calculate_unclipped_five_prime_position<C>(
+    cigar: &C,
+    alignment_start: Position,
+    is_reverse_complemented: bool,
+) -> io::Result<i64>
+where
+    C: Cigar + ?Sized,
+{
+    let to_i64 =
+        |n: usize| i64::try_from(n).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+
+    if is_reverse_complemented {
+        cigar
+            .unclipped_end(alignment_start)
+            .and_then(|position| to_i64(usize::from(position)))
+    } else {
+        let mut clip_length = 0;
+
+        for result in cigar.iter() {
+            let op = result?;
+
+            match op.kind() {
+                Kind::SoftClip | Kind::HardClip => clip_length += op.len(),
+                _ => break,
+            }
+        }
+
+        Ok(to_i64(usize::from(alignment_start))? - to_i64(clip_length)?)
+    }
+}
+
+fn calculate_score(record: &RecordBuf) -> u64 {
+    record
+        .quality_scores()
+        .iter()
+        .filter(|&n| n >= MIN_QUALITY_SCORE)
+        .map(u64::from)
+        .sum()
+}
+
+fn get_mate_score(record: &RecordBuf) -> io::Result<u64> {
+    let value = record
+        .data()
+        .get(&MATE_SCORE)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing mate score"))?;
+
+    value
+        .as_int()
+        .and_then(|n| u64::try_from(n).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid mate score"))
+}
+
+fn get_library(header: &sam::Header, record: &RecordBuf) -> io::Result<BString> {
+    let library = match record.data().get(&Tag::READ_GROUP) {
+        Some(Value::String(id)) => header
+            .read_groups()
+            .get(id)
+            .and_then(|read_group| read_group.other_fields().get(&read_group_tag::LIBRARY))
+            .map(|library| library.to_vec()),
+        Some(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid read group field type",
+            ));
+        }
+        None => None,
+    };
+
+    Ok(library.unwrap_or_else(|| UNKNOWN_LIBRARY.to_vec()).into())
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct PhysicalLocation<'a> {
+    tile: &'a [u8],
+    x: i64,
+    y: i64,
+}
+
+// Parses the physical location of a cluster from an Illumina read name, i.e.,
+// `<instrument>:<run>:<flow cell>:<lane>:<tile>:<x>:<y>` or `<instrument>:<lane>:<tile>:<x>:<y>`.
+//
+// The returned tile includes all fields preceding the x-coordinate.
+fn parse_physical_location(name: &[u8]) -> Option<PhysicalLocation<'_>> {
+    let mut fields = name.rsplitn(3, |&b| b == b':');
+
+    let y = fields.next().and_then(parse_i64)?;
+    let x = fields.next().and_then(parse_i64)?;
+    let tile = fields.next()?;
+
+    let field_count = tile.iter().filter(|&&b| b == b':').count() + 1;
+
+    if field_count == 3 || field_count == 5 {
+        Some(PhysicalLocation { tile, x, y })
+    } else {
+        None
+    }
+}
+
+fn parse_i64(buf: &[u8]) -> Option<i64> {
+    buf.to_str().ok().and_then(|s| s.parse().ok())
+}
+
+fn count_optical_duplicates(names: &[BString], distance: u32) -> u64 {
+    let distance = i64::from(distance);
+
+    let locations: Vec<_> = names
+        .iter()
+        .map(|name| parse_physical_location(name))
+        .collect();
+
+    let mut count = 0;
+
+    for (i, location) in locations.iter().enumerate().skip(1) {
+        let Some(location) = location else {
+            continue;
+        };
+
+        let is_optical_duplicate = locations.iter().enumerate().any(|(j, other)| {
+            i != j
+                && other.as_ref().is_some_and(|other| {
+                    location.tile == other.tile
+                        && (location.x - other.x).abs() <= distance
+                        && (location.y - other.y).abs() <= distance
+                })
+        });
+
+        if is_optical_duplicate {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use sam::{
+        alignment::record::cigar::Op,
+        header::record::value::{
+            Map,
+            map::{ReadGroup, ReferenceSequence},
+        },
+    };
+
+    use super::*;
+
+    fn build_header() -> Result<sam::Header, Box<dyn std::error::Error>> {
+        Ok(sam::Header::builder()
+            .add_reference_sequence("sq0", Map::<ReferenceSequence>::new(1000.try_into()?))
+            .add_read_group(
+                "rg0",
+                Map::<ReadGroup>::builder()
+                    .insert(read_group_tag::LIBRARY, "lb0")
+                    .build()?,
+            )
+            .add_read_group(
+                "rg1",
+                Map::<ReadGroup>::builder()
+                    .insert(read_group_tag::LIBRARY, "lb1")
+                    .build()?,
+            )
+            .build())
+    }
+
+    fn build_record(
+        name: &str,
+        flags: Flags,
+        alignment_start: usize,
+        ops: &[Op],
+        quality_score: u8,
+        read_group: &str,
+    ) -> Result<RecordBuf, Box<dyn std::error::Error>> {
+        let read_length = ops
+            .iter()
+            .filter(|op| op.kind().consumes_read())
+            .map(|op| op.len())
+            .sum();
+
+        Ok(RecordBuf::builder()
+            .set_name(name)
+            .set_flags(flags)
+            .set_reference_sequence_id(0)
+            .set_alignment_start(Position::try_from(alignment_start)?)
+            .set_cigar(ops.iter().copied().collect())
+            .set_quality_scores(vec![quality_score; read_length].into())
+            .set_data(
+                [(Tag::READ_GROUP, Value::from(read_group))]
+                    .into_iter()
+                    .collect(),
+            )
+            .build())
+    }
+
+    fn set_mate(record: &mut RecordBuf, mate: &RecordBuf) -> io::Result<()> {
+        let mut cigar = Vec::new();
+        sam::io::writer::record::write_cigar(&mut cigar, mate.cigar())?;
+
+        let score = i32::try_from(calculate_score(mate)).map_err(io::Error::other)?;
+
+        *record.mate_reference_sequence_id_mut() = mate.reference_sequence_id();
+        *record.mate_alignment_start_mut() = mate.alignment_start();
+        record.flags_mut().set(
+            Flags::MATE_REVERSE_COMPLEMENTED,
+            mate.flags().is_reverse_complemented(),
+        );
+
+        let data = record.data_mut();
+        data.insert(Tag::MATE_CIGAR, Value::String(cigar.into()));
+        data.insert(MATE_SCORE, Value::from(score));
+
+        Ok(())
+    }
+
+    fn build_pair(
+        name: &str,
+        (start_1, ops_1): (usize, &[Op]),
+        (start_2, ops_2): (usize, &[Op]),
+        quality_score: u8,
+        read_group: &str,
+    ) -> Result<[RecordBuf; 2], Box<dyn std::error::Error>> {
+        let mut record_1 = build_record(
+            name,
+            Flags::SEGMENTED | Flags::FIRST_SEGMENT,
+            start_1,
+            ops_1,
+            quality_score,
+            read_group,
+        )?;
+
+        let mut record_2 = build_record(
+            name,
+            Flags::SEGMENTED | Flags::LAST_SEGMENT | Flags::REVERSE_COMPLEMENTED,
+            start_2,
+            ops_2,
+            quality_score,
+            read_group,
+        )?;
+
+        set_mate(&mut record_1, &record_2)?;
+        set_mate(&mut record_2, &record_1)?;
+
+        Ok([record_1, record_2])
+    }
+
+    #[test]
+    fn test_next() -> Result<(), Box<dyn std::error::Error>> {
+        const M4: &[Op] = &[Op::new(Kind::Match, 4)];
+
+        let header = build_header()?;
+
+        let mut records = Vec::new();
+        records.extend(build_pair("r0", (8, M4), (55, M4), 30, "rg0")?);
+        records.extend(build_pair("r1", (8, M4), (55, M4), 40, "rg0")?);
+        records.extend(build_pair("r2", (8, M4), (55, M4), 30, "rg1")?);
+        records.extend(build_pair("r3", (8, M4), (56, M4), 30, "rg0")?);
+
+        // A soft-clipped read with the same unclipped start as r0 and r1.
+        let s2m2 = [Op::new(Kind::SoftClip, 2), Op::new(Kind::Match, 2)];
+        records.extend(build_pair("r4", (10, &s2m2), (55, M4), 20, "rg0")?);
+
+        // A fragment at the same 5' position as a read pair end.
+        records.push(build_record("r5", Flags::empty(), 8, M4, 50, "rg0")?);
+
+        // Fragments without read pairs.
+        records.push(build_record(
+            "r6",
+            Flags::REVERSE_COMPLEMENTED,
+            100,
+            M4,
+            30,
+            "rg0",
+        )?);
+        records.push(build_record(
+            "r7",
+            Flags::REVERSE_COMPLEMENTED,
+            100,
+            &[Op::new(Kind::Match, 2), Op::new(Kind::SoftClip, 2)],
+            40,
+            "rg0",
+        )?);
+
+        records.push(build_record("r8", Flags::UNMAPPED, 200, &[], 0, "rg0")?);
+
+        records.sort_by_key(|record| record.alignment_start());
+
+        let mut marker = DuplicateMarker::new(&header, records.into_iter().map(Ok));
+
+        let mut record_count = 0;
+        let mut duplicates = Vec::new();
+
+        for result in marker.by_ref() {
+            let record = result?;
+            record_count += 1;
+
+            if record.flags().is_duplicate() {
+                duplicates.extend(record.name().map(|name| name.to_string()));
+            }
+        }
+
+        duplicates.sort();
+
+        assert_eq!(record_count, 14);
+        assert_eq!(duplicates, ["r0", "r0", "r4", "r4", "r5", "r6"]);
+        assert!(marker.templates.is_empty());
+
+        let metrics = marker.metrics();
+
+        let lb0 = &metrics[&BString::from("lb0")];
+        assert_eq!(lb0.read_pairs_examined(), 4);
+        assert_eq!(lb0.unpaired_reads_examined(), 3);
+        assert_eq!(lb0.unmapped_reads(), 1);
+        assert_eq!(lb0.read_pair_duplicates(), 2);
+        assert_eq!(lb0.unpaired_read_duplicates(), 2);
+        assert_eq!(lb0.percent_duplication(), 6.0 / 11.0);
+
+        let lb1 = &metrics[&BString::from("lb1")];
+        assert_eq!(lb1.read_pairs_examined(), 1);
+        assert_eq!(lb1.read_pair_duplicates(), 0);
+        assert_eq!(lb1.percent_duplication(), 0.0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_distant_records() -> Result<(), Box<dyn std::error::Error>> {
+        const M4: &[Op] = &[Op::new(Kind::Match, 4)];
+
+        let header = build_header()?;
+
+        let mut records = vec![
+            build_record("r0", Flags::empty(), 1, M4, 30, "rg0")?,
+            build_record("r1", Flags::empty(), 1, M4, 20, "rg0")?,
+            build_record("r2", Flags::empty(), 900, M4, 30, "rg0")?,
+        ];
+
+        records.sort_by_key(|record| record.alignment_start());
+
+        let mut marker = DuplicateMarker::new(&header, records.into_iter().map(Ok));
+
+        // The group at position 1 is resolved and returned without buffering the entire input.
+        let record = marker.next().transpose()?;
+        assert!(record.is_some_and(|r| !r.flags().is_duplicate()));
+        assert_eq!(marker.buf.len(), 2);
+
+        let record = marker.next().transpose()?;
+        assert!(record.is_some_and(|r| r.flags().is_duplicate()));
+        assert_eq!(marker.buf.len(), 1);
+        assert_eq!(marker.fragments.len(), 1);
+
+        let record = marker.next().transpose()?;
+        assert!(record.is_some_and(|r| !r.flags().is_duplicate()));
+        assert!(marker.next().is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_unsorted_records() -> Result<(), Box<dyn std::error::Error>> {
+        const M4: &[Op] = &[Op::new(Kind::Match, 4)];
+
+        let header = build_header()?;
+
+        let records = [
+            build_record("r0", Flags::empty(), 8, M4, 30, "rg0")?,
+            build_record("r1", Flags::empty(), 1, M4, 30, "rg0")?,
+        ];
+
+        let mut marker = DuplicateMarker::new(&header, records.into_iter().map(Ok));
+
+        assert!(matches!(
+            marker.next(),
+            Some(Err(e)) if e.kind() == io::ErrorKind::InvalidData
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_with_optical_duplicates() -> Result<(), Box<dyn std::error::Error>> {
+        const M4: &[Op] = &[Op::new(Kind::Match, 4)];
+
+        let header = build_header()?;
+
+        let mut records = Vec::new();
+        records.extend(build_pair(
+            "i0:1:fc0:1:1101:1000:2000",
+            (8, M4),
+            (55, M4),
+            30,
+            "rg0",
+        )?);
+        records.extend(build_pair(
+            "i0:1:fc0:1:1101:1050:2050",
+            (8, M4),
+            (55, M4),
+            20,
+            "rg0",
+        )?);
+        records.extend(build_pair(
+            "i0:1:fc0:1:1102:1000:2000",
+            (8, M4),
+            (55, M4),
+            20,
+            "rg0",
+        )?);
+
+        records.sort_by_key(|record| record.alignment_start());
+
+        let mut marker = DuplicateMarker::new(&header, records.into_iter().map(Ok))
+            .set_optical_duplicate_pixel_distance(100);
+
+        for result in marker.by_ref() {
+            result?;
+        }
+
+        let metrics = &marker.metrics()[&BString::from("lb0")];
+        assert_eq!(metrics.read_pair_duplicates(), 2);
+        assert_eq!(metrics.read_pair_optical_duplicates(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_physical_location() {
+        assert_eq!(
+            parse_physical_location(b"i0:1:fc0:1:1101:1000:2000"),
+            Some(PhysicalLocation {
+                tile: b"i0:1:fc0:1:1101",
+                x: 1000,
+                y: 2000,
+            })
+        );
+
+        assert_eq!(
+            parse_physical_location(b"i0:1:1101:1000:2000"),
+            Some(PhysicalLocation {
+                tile: b"i0:1:1101",
+                x: 1000,
+                y: 2000,
+            })
+        );
+
+        assert!(parse_physical_location(b"r0").is_none());
+        assert!(parse_physical_location(b"a:b:c:d").is_none());
+        assert!(parse_physical_location(b"i0:1:1101:x:2000").is_none());
+    }
+}
@@ -26,7 +26,7 @@ where
     index_inner(&mut reader)
 }
 
-fn index_inner<R>(reader: &mut Reader<R>) -> io::Result<tabix::Index>
+pub(crate) fn index_inner<R>(reader: &mut Reader<R>) -> io::Result<tabix::Index>
 where
     R: bgzf::io::BufRead,
 {
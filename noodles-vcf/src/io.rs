@@ -2,10 +2,11 @@
 
 mod compression_method;
 pub mod indexed_reader;
+mod indexing_writer;
 pub mod reader;
 pub mod writer;
 
 pub use self::{
-    compression_method::CompressionMethod, indexed_reader::IndexedReader, reader::Reader,
-    writer::Writer,
+    compression_method::CompressionMethod, indexed_reader::IndexedReader,
+    indexing_writer::IndexingWriter, reader::Reader, writer::Writer,
 };
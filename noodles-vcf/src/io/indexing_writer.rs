@@ -0,0 +1,268 @@
+use std::{
+    collections::HashSet,
+    io::{self, Write},
+    mem,
+};
+
+use noodles_bgzf as bgzf;
+use noodles_core::Position;
+use noodles_csi::binning_index::index::{
+    header::Builder as HeaderBuilder, reference_sequence::bin::Chunk,
+};
+use noodles_tabix as tabix;
+
+use super::Writer;
+use crate::{Header, Record, variant::io::Write as _};
+
+/// A bgzipped VCF writer that builds a tabix index as records are written.
+///
+/// The index is equivalent to the one built by [`crate::fs::index`] but does not require a second
+/// pass over the written file.
+///
+/// Records must be coordinate-sorted, i.e., records of the same reference sequence are
+/// contiguous and ordered by position.
+///
+/// # Examples
+///
+/// ```
+/// use noodles_core::Position;
+/// use noodles_vcf::{self as vcf, variant::io::Write};
+///
+/// let header = vcf::Header::builder()
+///     .add_contig("sq0", Default::default())
+///     .build();
+///
+/// let mut writer = vcf::io::IndexingWriter::new(Vec::new());
+/// writer.write_header(&header)?;
+///
+/// let record = vcf::variant::RecordBuf::builder()
+///     .set_reference_sequence_name("sq0")
+///     .set_variant_start(Position::MIN)
+///     .set_reference_bases("A")
+///     .build();
+///
+/// writer.write_variant_record(&header, &record)?;
+///
+/// let index = writer.finish()?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+pub struct IndexingWriter<W>
+where
+    W: Write,
+{
+    inner: Writer<bgzf::io::Writer<W>>,
+    indexer: tabix::index::Indexer,
+    reference_sequence_names: HashSet<String>,
+    last_position: Option<(String, Position)>,
+}
+
+impl<W> IndexingWriter<W>
+where
+    W: Write,
+{
+    /// Creates an indexing VCF writer with a default compression level.
+    ///
+    /// The given stream is wrapped in a BGZF encoder.
+    pub fn new(inner: W) -> Self {
+        Self::from(Writer::new(bgzf::io::Writer::new(inner)))
+    }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &bgzf::io::Writer<W> {
+        self.inner.get_ref()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> bgzf::io::Writer<W> {
+        self.inner.into_inner()
+    }
+
+    /// Writes a VCF header.
+    pub fn write_header(&mut self, header: &Header) -> io::Result<()> {
+        self.inner.write_header(header)
+    }
+
+    /// Writes a VCF record.
+    ///
+    /// # Errors
+    ///
+    /// This returns an `io::Error` if the record is out of coordinate order.
+    pub fn write_record(&mut self, header: &Header, record: &Record) -> io::Result<()> {
+        self.write_variant_record(header, record)
+    }
+
+    /// Finishes the output stream and returns the tabix index.
+    pub fn finish(&mut self) -> io::Result<tabix::Index> {
+        self.inner.get_mut().try_finish()?;
+
+        let indexer = mem::take(&mut self.indexer);
+        Ok(indexer.build())
+    }
+
+    fn validate_order(&mut self, reference_sequence_name: &str, start: Position) -> io::Result<()> {
+        let is_sorted = match &self.last_position {
+            Some((last_name, last_start)) if last_name == reference_sequence_name => {
+                start >= *last_start
+            }
+            _ => !self
+                .reference_sequence_names
+                .contains(reference_sequence_name),
+        };
+
+        if !is_sorted {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "record is not coordinate-sorted",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<W> From<Writer<bgzf::io::Writer<W>>> for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn from(inner: Writer<bgzf::io::Writer<W>>) -> Self {
+        let mut indexer = tabix::index::Indexer::default();
+        indexer.set_header(HeaderBuilder::vcf().build());
+
+        Self {
+            inner,
+            indexer,
+            reference_sequence_names: HashSet::new(),
+            last_position: None,
+        }
+    }
+}
+
+impl<W> crate::variant::io::Write for IndexingWriter<W>
+where
+    W: Write,
+{
+    fn write_variant_header(&mut self, header: &Header) -> io::Result<()> {
+        self.write_header(header)
+    }
+
+    fn write_variant_record(
+        &mut self,
+        header: &Header,
+        record: &dyn crate::variant::Record,
+    ) -> io::Result<()> {
+        let reference_sequence_name = record.reference_sequence_name(header)?;
+
+        let start = record
+            .variant_start()
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing position"))?;
+
+        let end = record.variant_end(header)?;
+
+        self.validate_order(reference_sequence_name, start)?;
+
+        let start_position = self.inner.get_ref().virtual_position();
+        self.inner.write_variant_record(header, record)?;
+        let end_position = self.inner.get_ref().virtual_position();
+
+        let chunk = Chunk::new(start_position, end_position);
+        self.indexer
+            .add_record(reference_sequence_name, start, end, chunk)?;
+
+        if self
+            .last_position
+            .as_ref()
+            .is_none_or(|(last_name, _)| last_name != reference_sequence_name)
+        {
+            self.reference_sequence_names
+                .insert(reference_sequence_name.into());
+        }
+
+        self.last_position = Some((reference_sequence_name.into(), start));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use noodles_csi::BinningIndex;
+
+    use super::*;
+    use crate::{fs::index::index_inner, io::Reader, variant::RecordBuf};
+
+    fn build_record(reference_sequence_name: &str, position: usize) -> io::Result<RecordBuf> {
+        let position = Position::try_from(position)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+        Ok(RecordBuf::builder()
+            .set_reference_sequence_name(reference_sequence_name)
+            .set_variant_start(position)
+            .set_reference_bases("A")
+            .build())
+    }
+
+    #[test]
+    fn test_finish() -> io::Result<()> {
+        let header = Header::builder()
+            .add_contig("sq0", Default::default())
+            .add_contig("sq1", Default::default())
+            .build();
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        for i in 0..4096 {
+            writer.write_variant_record(&header, &build_record("sq0", i * 64 + 1)?)?;
+        }
+
+        writer.write_variant_record(&header, &build_record("sq1", 8)?)?;
+
+        let actual = writer.finish()?;
+
+        let data = writer.into_inner().into_inner();
+        let mut reader = Reader::new(bgzf::io::Reader::new(&data[..]));
+        let expected = index_inner(&mut reader)?;
+
+        // The reader ends the last chunk at the start of the next (EOF) block rather than at the
+        // end of the final data block, so only the complete reference sequences are compared.
+        assert_eq!(actual.header(), expected.header());
+        assert_eq!(
+            actual.reference_sequences().len(),
+            expected.reference_sequences().len()
+        );
+        assert_eq!(
+            actual.reference_sequences()[0],
+            expected.reference_sequences()[0]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_variant_record_with_unsorted_records() -> io::Result<()> {
+        let header = Header::builder()
+            .add_contig("sq0", Default::default())
+            .add_contig("sq1", Default::default())
+            .build();
+
+        let mut writer = IndexingWriter::new(Vec::new());
+        writer.write_header(&header)?;
+
+        writer.write_variant_record(&header, &build_record("sq0", 13)?)?;
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 8)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        writer.write_variant_record(&header, &build_record("sq1", 8)?)?;
+
+        assert!(matches!(
+            writer.write_variant_record(&header, &build_record("sq0", 21)?),
+            Err(e) if e.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        Ok(())
+    }
+}